@@ -6,20 +6,20 @@ extern crate test;
 #[cfg(feature = "auto_save")]
 mod blackjack {
     use games_microservice::games::blackjack::BlackJack;
-    use games_microservice::establish_connection_pool;
+    use games_microservice::establish_test_connection_pool;
     use test::Bencher;
 
     #[bench]
     fn bench_mark(b: &mut Bencher) {
         use games_microservice::games::blackjack::BlackJackError::*;
-        let pool = establish_connection_pool();
+        let pool = establish_test_connection_pool();
         let mut uid = 10_000_000;
 
         b.iter(move || {
             uid += 1;
 
             {
-                let mut bj = BlackJack::new(uid, 0, pool.clone())
+                let mut bj = BlackJack::new(0, uid, 0, pool.clone())
                     .expect("Failed to create BlackJack Session");
                 match bj.player_hit() {
                     Ok(_) => bj.player_stay().expect("Player Failed to stay"),
@@ -30,7 +30,7 @@ mod blackjack {
                     Err(e) => panic!(e),
                 }
             }
-            BlackJack::restore(&pool, uid)
+            BlackJack::restore(&pool, 0, uid)
                 .expect("Restore failed")
                 .claim()
                 .ok();
@@ -85,7 +85,7 @@ mod slot_machine {
         b.iter(|| {
             let gain = SlotMachine::new(100).gain;
 
-            assert!([-100, 50, 100].iter().any(|i| *i == gain));
+            assert!([-100, 75, 400, 900, 4900].iter().any(|i| *i == gain));
         })
     }
 }
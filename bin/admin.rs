@@ -0,0 +1,51 @@
+//! Manages the API keys clients use to reach the service
+//!
+//! Usage:
+//!     game-service-admin create <name>
+//!     game-service-admin list
+//!     game-service-admin revoke <name>
+
+extern crate games_microservice;
+
+use games_microservice::auth::ApiKey;
+use games_microservice::establish_connection_pool;
+use std::env;
+use std::process;
+
+const USAGE: &str = "Usage: game-service-admin <create <name> | list | revoke <name>>";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let pool = establish_connection_pool();
+    let conn = pool.get().expect("Failed to get a DB connection");
+
+    let result = match (args.get(0).map(|s| &s[..]), args.get(1)) {
+        (Some("create"), Some(name)) => ApiKey::create(&*conn, name).map(|(key, raw_key)| {
+            println!("Created key {:?} (namespace {})", key.name, key.namespace());
+            println!("{}", raw_key);
+            println!("This key will not be shown again");
+        }),
+        (Some("list"), None) => ApiKey::list(&*conn).map(|keys| {
+            for key in keys {
+                println!(
+                    "{}\t{}{}",
+                    key.namespace(),
+                    key.name,
+                    if key.revoked { "\t(revoked)" } else { "" }
+                );
+            }
+        }),
+        (Some("revoke"), Some(name)) => {
+            ApiKey::revoke(&*conn, name).map(|_| println!("Revoked key {:?}", name))
+        }
+        _ => {
+            eprintln!("{}", USAGE);
+            process::exit(2);
+        }
+    };
+
+    if let Err(why) = result {
+        eprintln!("Error: {}", why);
+        process::exit(1);
+    }
+}
@@ -0,0 +1,48 @@
+use games_microservice::auth::{ApiKey, AuthError};
+use rocket::http::Status;
+use rocket::request::{self, FromRequest, Request};
+use rocket::{Outcome, State};
+use ConnectionPool;
+
+/// Header clients send their API key in
+pub const API_KEY_HEADER: &str = "X-Api-Key";
+
+/// An authenticated client, user ids in routes are scoped to its namespace
+#[derive(Clone, Debug)]
+pub struct ApiClient {
+    pub name: String,
+    pub namespace: i64,
+}
+
+impl<'a, 'r> FromRequest<'a, 'r> for ApiClient {
+    type Error = AuthError;
+
+    fn from_request(request: &'a Request<'r>) -> request::Outcome<Self, Self::Error> {
+        let raw_key = match request.headers().get_one(API_KEY_HEADER) {
+            Some(key) => key,
+            None => return Outcome::Failure((Status::Unauthorized, AuthError::UnknownKey)),
+        };
+
+        let db_pool = match request.guard::<State<ConnectionPool>>() {
+            Outcome::Success(pool) => pool,
+            _ => return Outcome::Failure((Status::InternalServerError, AuthError::NoPool)),
+        };
+
+        let conn = match db_pool.get() {
+            Ok(conn) => conn,
+            Err(err) => return Outcome::Failure((Status::ServiceUnavailable, err.into())),
+        };
+
+        match ApiKey::verify(&*conn, raw_key) {
+            Ok(key) => Outcome::Success(ApiClient {
+                namespace: key.namespace(),
+                name: key.name,
+            }),
+            Err(AuthError::DieselResult(err)) => Outcome::Failure((
+                Status::InternalServerError,
+                AuthError::DieselResult(err),
+            )),
+            Err(err) => Outcome::Failure((Status::Unauthorized, err)),
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use games::baccarat::{baccarat, BaccaratBets, BaccaratError, BaccaratResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+
+fn play(bets: Result<BaccaratBets, BaccaratError>, config: &Config) -> Json<BaccaratResponse> {
+    Json(BaccaratResponse::new(bets.and_then(|bets| {
+        for &bet in bets.spots().iter().filter(|&&bet| bet > 0) {
+            config.bets.check(bet).map_err(BaccaratError::InvalidBet)?;
+        }
+        config
+            .bets
+            .check(bets.total()?)
+            .map_err(BaccaratError::InvalidBet)?;
+        baccarat(bets, &config.baccarat)
+    })))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<spot>/<bet>")]
+fn single(config: State<Config>, spot: String, bet: u64) -> Json<BaccaratResponse> {
+    play(BaccaratBets::single(&spot, bet), &config)
+}
+
+/// Several spots bet at once, from a JSON body
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/bets", format = "application/json", data = "<bets>")]
+fn bets(config: State<Config>, bets: Json<BaccaratBets>) -> Json<BaccaratResponse> {
+    play(Ok(bets.into_inner()), &config)
+}
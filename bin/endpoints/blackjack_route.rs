@@ -1,30 +1,24 @@
-use games::blackjack::{BlackJack, Response, SessionCount};
-use diesel::prelude::*;
+use auth::ApiClient;
+use games::blackjack::{BlackJack, BlackJackError, Response, SessionCount};
+use games_microservice::config::Config;
+use games_microservice::jackpot::Jackpot;
 use rocket::State;
 use rocket_contrib::Json;
 use ConnectionPool;
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
 #[get("/")]
-fn active_sessions(db_pool: State<ConnectionPool>) -> Json<SessionCount> {
-    use games_microservice::schema::blackjack::dsl::*;
-
-    let conn = db_pool.get().unwrap();
-    let result = blackjack
-        .filter(status.is_null())
-        .count()
-        .get_result::<i64>(&*conn);
-
-    Json(match result {
-        Ok(session_count) => SessionCount::count(session_count as u64),
+fn active_sessions(db_pool: State<ConnectionPool>, client: ApiClient) -> Json<SessionCount> {
+    Json(match BlackJack::active_sessions(&db_pool, Some(client.namespace)) {
+        Ok(session_count) => SessionCount::count(session_count),
         Err(_) => SessionCount::err("Failed to get active sessions"),
     })
 }
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
 #[get("/<user>")]
-fn user_info(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
-    Json(match BlackJack::restore(&db_pool, user) {
+fn user_info(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<Response> {
+    Json(match BlackJack::restore(&db_pool, client.namespace, user) {
         Ok(bj) => Response::success(&bj),
         Err(err) => Response::error(&err),
     })
@@ -32,17 +26,37 @@ fn user_info(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
 #[post("/<user>/create/<bet>")]
-fn create_user(db_pool: State<ConnectionPool>, user: u64, bet: u64) -> Json<Response> {
-    Json(match BlackJack::new(user, bet, db_pool.clone()) {
-        Ok(bj) => Response::success(&bj),
+fn create_user(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    bet: u64,
+) -> Json<Response> {
+    if config.bets.check(bet).is_err() {
+        return Json(Response::error(&BlackJackError::InvalidBet));
+    }
+
+    Json(match BlackJack::new(client.namespace, user, bet, db_pool.clone()) {
+        Ok(bj) => {
+            let contribution = config.jackpot.blackjack_contribution_of(bet);
+            if contribution > 0 {
+                // The game is already saved, a failed contribution shouldn't fail it
+                if let Ok(conn) = db_pool.get() {
+                    let _ = Jackpot::contribute(&*conn, contribution, config.jackpot.seed);
+                }
+            }
+
+            Response::success(&bj)
+        }
         Err(err) => Response::error(&err),
     })
 }
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
 #[post("/<user>/hit")]
-fn player_hit(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
-    Json(match BlackJack::restore(&db_pool, user) {
+fn player_hit(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<Response> {
+    Json(match BlackJack::restore(&db_pool, client.namespace, user) {
         Ok(mut bj) => match bj.player_hit() {
             Ok(_) => Response::success(&bj),
             Err(err) => Response::error(&err),
@@ -53,8 +67,8 @@ fn player_hit(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
 #[post("/<user>/stay")]
-fn player_stay(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
-    Json(match BlackJack::restore(&db_pool, user) {
+fn player_stay(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<Response> {
+    Json(match BlackJack::restore(&db_pool, client.namespace, user) {
         Ok(mut bj) => {
             // TODO: check if this is an error and don't return success if so
             let _ = bj.player_stay();
@@ -67,8 +81,8 @@ fn player_stay(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
 #[post("/<user>/claim")]
-fn claim(db_pool: State<ConnectionPool>, user: u64) -> Json<Response> {
-    Json(match BlackJack::restore(&db_pool, user) {
+fn claim(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<Response> {
+    Json(match BlackJack::restore(&db_pool, client.namespace, user) {
         Ok(mut bj) => match bj.claim() {
             Ok(_) => Response::success(&bj),
             Err(err) => Response::error(&err),
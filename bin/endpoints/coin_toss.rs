@@ -1,8 +1,117 @@
-use games::coin_toss::{guess_side, CoinTossResponse};
+use auth::ApiClient;
+use games::coin_toss::{guess_side_with_rules, toss_coins, Coin, CoinBet, CoinTossError,
+                       CoinTossResponse, MultiCoinResponse, PaytableResponse, Streak,
+                       StreakResponse, StreakStatusResponse};
+use games_microservice::config::Config;
+use rocket::State;
 use rocket_contrib::Json;
+use ConnectionPool;
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
-#[get("/<guess>/<bet>")]
-fn coin_toss(guess: String, bet: u64) -> Json<CoinTossResponse> {
-    Json(guess_side(bet, &guess))
+#[get("/<guess>/<bet>", rank = 3)]
+fn coin_toss(config: State<Config>, guess: String, bet: u64) -> Json<CoinTossResponse> {
+    Json(match config.bets.check(bet) {
+        Ok(_) => guess_side_with_rules(bet, &guess, &config.coin_toss),
+        Err(why) => CoinTossResponse::err(why),
+    })
+}
+
+/// Odds and payouts of every bet on `coins` coins
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/paytable/<coins>", rank = 1)]
+fn paytable(config: State<Config>, coins: u32) -> Json<PaytableResponse> {
+    Json(PaytableResponse::new(coins, &config.coin_toss))
+}
+
+fn multi(config: &Config, coins: u32, on: CoinBet, bet: u64) -> MultiCoinResponse {
+    MultiCoinResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(CoinTossError::InvalidBet)
+            .and_then(|_| toss_coins(bet, coins, on, &config.coin_toss)),
+    )
+}
+
+/// Bets on exactly `heads` of `coins` coins landing heads
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<coins>/heads/<heads>/<bet>")]
+fn heads(config: State<Config>, coins: u32, heads: u32, bet: u64) -> Json<MultiCoinResponse> {
+    Json(multi(&config, coins, CoinBet::Heads(heads), bet))
+}
+
+/// Bets on all of `coins` coins landing on the same side
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<coins>/same/<bet>")]
+fn same(config: State<Config>, coins: u32, bet: u64) -> Json<MultiCoinResponse> {
+    Json(multi(&config, coins, CoinBet::AllSame, bet))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>/streak", rank = 2)]
+fn streak(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<StreakStatusResponse> {
+    Json(StreakStatusResponse::new(Streak::restore(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+/// Starts a double or nothing streak with `bet` on `guess`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/streak/<guess>/<bet>")]
+fn start_streak(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    guess: String,
+    bet: u64,
+) -> Json<StreakResponse> {
+    Json(StreakResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(CoinTossError::InvalidBet)
+            .and_then(|_| guess.parse::<Coin>())
+            .and_then(|guess| {
+                Streak::start(
+                    &db_pool,
+                    client.namespace,
+                    user,
+                    guess,
+                    bet,
+                    &config.coin_toss,
+                )
+            }),
+    ))
+}
+
+/// Double or nothing on the pot of the streak
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/streak/<guess>", rank = 2)]
+fn double_streak(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    guess: String,
+) -> Json<StreakResponse> {
+    Json(StreakResponse::new(guess.parse::<Coin>().and_then(|guess| {
+        Streak::double(&db_pool, client.namespace, user, guess, &config.coin_toss)
+    })))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/streak/cash_out")]
+fn cash_out(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<StreakResponse> {
+    Json(StreakResponse::new(Streak::cash_out(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
 }
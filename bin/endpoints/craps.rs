@@ -0,0 +1,140 @@
+use auth::ApiClient;
+use games::craps::{BetKind, CrapsError, CrapsSession, CrapsStatusResponse, RollResponse,
+                   WagerResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>")]
+fn table(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<CrapsStatusResponse> {
+    Json(CrapsStatusResponse::new(CrapsSession::restore(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/bet/<kind>/<bet>")]
+fn bet(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    kind: String,
+    bet: u64,
+) -> Json<WagerResponse> {
+    Json(WagerResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(CrapsError::InvalidBet)
+            .and_then(|_| kind.parse::<BetKind>())
+            .and_then(|kind| match kind {
+                BetKind::Place => Err(CrapsError::InvalidNumber(0)),
+                kind => CrapsSession::bet(&db_pool, client.namespace, user, kind, None, bet),
+            }),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/place/<number>/<bet>")]
+fn place(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    number: u8,
+    bet: u64,
+) -> Json<WagerResponse> {
+    Json(WagerResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(CrapsError::InvalidBet)
+            .and_then(|_| {
+                CrapsSession::bet(
+                    &db_pool,
+                    client.namespace,
+                    user,
+                    BetKind::Place,
+                    Some(number),
+                    bet,
+                )
+            }),
+    ))
+}
+
+fn odds(
+    db_pool: &ConnectionPool,
+    config: &Config,
+    namespace: i64,
+    user: u64,
+    kind: &str,
+    point: Option<u8>,
+    bet: u64,
+) -> Json<WagerResponse> {
+    Json(WagerResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(CrapsError::InvalidBet)
+            .and_then(|_| kind.parse::<BetKind>())
+            .and_then(|kind| {
+                CrapsSession::odds(db_pool, namespace, user, kind, point, bet, &config.craps)
+            }),
+    ))
+}
+
+/// Odds behind the pass or don't pass bet
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/odds/<kind>/<bet>")]
+fn line_odds(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    kind: String,
+    bet: u64,
+) -> Json<WagerResponse> {
+    odds(&db_pool, &config, client.namespace, user, &kind, None, bet)
+}
+
+/// Odds behind the come or don't come bet on `point`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/odds/<kind>/<point>/<bet>")]
+fn come_odds(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    kind: String,
+    point: u8,
+    bet: u64,
+) -> Json<WagerResponse> {
+    odds(
+        &db_pool,
+        &config,
+        client.namespace,
+        user,
+        &kind,
+        Some(point),
+        bet,
+    )
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/roll")]
+fn roll(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<RollResponse> {
+    Json(RollResponse::new(CrapsSession::roll(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
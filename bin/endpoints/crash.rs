@@ -0,0 +1,80 @@
+use auth::ApiClient;
+use games::crash::{self, BetResponse, BetStatusResponse, CrashError, RoundResponse, Rounds};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+/// Latest round and its multiplier, polled while it runs
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/current", rank = 1)]
+fn current(db_pool: State<ConnectionPool>, config: State<Config>) -> Json<RoundResponse> {
+    Json(RoundResponse::new(Rounds::current(
+        &db_pool,
+        &config.crash,
+        crash::unix_millis(),
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/rounds/<round>")]
+fn round(db_pool: State<ConnectionPool>, config: State<Config>, round: u64) -> Json<RoundResponse> {
+    Json(RoundResponse::new(Rounds::round(
+        &db_pool,
+        round,
+        &config.crash,
+        crash::unix_millis(),
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>", rank = 2)]
+fn status(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<BetStatusResponse> {
+    Json(BetStatusResponse::new(Rounds::status(
+        &db_pool,
+        client.namespace,
+        user,
+        crash::unix_millis(),
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/bet/<bet>")]
+fn bet(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    bet: u64,
+) -> Json<BetResponse> {
+    Json(BetResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(CrashError::InvalidBet)
+            .and_then(|_| {
+                Rounds::bet(&db_pool, client.namespace, user, bet, crash::unix_millis())
+            }),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/cash_out")]
+fn cash_out(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<BetResponse> {
+    Json(BetResponse::new(Rounds::cash_out(
+        &db_pool,
+        client.namespace,
+        user,
+        &config.crash,
+        crash::unix_millis(),
+    )))
+}
@@ -0,0 +1,79 @@
+use games::dice::{self, total_bet, DiceError, DiceRoll, DiceRollResponse, DiceRoller, Direction,
+                  OverUnderResponse, SicBoBet, SicBoResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+
+fn roll_with(dice: &str, roller: &mut DiceRoller) -> Json<DiceRollResponse> {
+    Json(DiceRollResponse::new(
+        dice.parse().map(|dice| DiceRoll::new(dice, roller)),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/roll/<dice>")]
+fn roll(dice: String) -> Json<DiceRollResponse> {
+    roll_with(&dice, &mut DiceRoller::new())
+}
+
+/// Rolls `dice` from `seed`, the same seed always rolls the same
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/roll/<dice>/<seed>")]
+fn roll_seeded(dice: String, seed: u64) -> Json<DiceRollResponse> {
+    roll_with(&dice, &mut DiceRoller::seeded(seed))
+}
+
+/// Every sic bo bet and their total have to be within the limits
+fn check_sic_bo_bets(bets: &[SicBoBet], config: &Config) -> Result<(), DiceError> {
+    for bet in bets {
+        config.bets.check(bet.amount).map_err(DiceError::InvalidBet)?;
+    }
+
+    let total = total_bet(bets)?;
+    // A roll with nothing on it is refused by play_sic_bo with its own error
+    if total > 0 {
+        config.bets.check(total).map_err(DiceError::InvalidBet)?;
+    }
+    Ok(())
+}
+
+/// Settles every bet of the body against one roll of 3 dice
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/sic_bo", format = "application/json", data = "<bets>")]
+fn sic_bo(config: State<Config>, bets: Json<Vec<SicBoBet>>) -> Json<SicBoResponse> {
+    if let Err(err) = check_sic_bo_bets(&bets, &config) {
+        return Json(SicBoResponse::new(Err(err)));
+    }
+
+    Json(SicBoResponse::new(dice::sic_bo(
+        &bets,
+        &mut DiceRoller::new(),
+        &config.sic_bo,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/over_under/<direction>/<target>/<bet>")]
+fn over_under(
+    config: State<Config>,
+    direction: String,
+    target: u32,
+    bet: u64,
+) -> Json<OverUnderResponse> {
+    Json(OverUnderResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(DiceError::InvalidBet)
+            .and_then(|_| direction.parse::<Direction>())
+            .and_then(|direction| {
+                dice::over_under(
+                    bet,
+                    direction,
+                    target,
+                    &mut DiceRoller::new(),
+                    &config.over_under,
+                )
+            }),
+    ))
+}
@@ -0,0 +1,61 @@
+use games_microservice::pending_migrations;
+use rocket::State;
+use rocket::http::Status;
+use rocket::response::status::Custom;
+use rocket_contrib::Json;
+use std::time::Duration;
+use ConnectionPool;
+
+/// How long readiness waits for a DB connection
+const READY_TIMEOUT_SECS: u64 = 2;
+
+#[derive(Serialize, Deserialize)]
+pub struct Health {
+    pub status_code: u16,
+    pub status: Result<String, String>,
+}
+
+impl Health {
+    fn ok() -> Custom<Json<Self>> {
+        Custom(
+            Status::Ok,
+            Json(Self {
+                status_code: 200,
+                status: Ok(String::from("ok")),
+            }),
+        )
+    }
+
+    fn unavailable(why: &str) -> Custom<Json<Self>> {
+        Custom(
+            Status::ServiceUnavailable,
+            Json(Self {
+                status_code: 503,
+                status: Err(why.to_owned()),
+            }),
+        )
+    }
+}
+
+/// Liveness, the process is up and serving requests
+#[get("/healthz")]
+fn healthz() -> Custom<Json<Health>> {
+    Health::ok()
+}
+
+/// Readiness, the DB is reachable and migrations are applied
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/readyz")]
+fn readyz(db_pool: State<ConnectionPool>) -> Custom<Json<Health>> {
+    let conn = match db_pool.get_timeout(Duration::from_secs(READY_TIMEOUT_SECS)) {
+        Ok(conn) => conn,
+        Err(_) => return Health::unavailable("No DB connection available"),
+    };
+
+    // Queries the applied migrations, so it fails as well if the DB went down
+    match pending_migrations(&*conn) {
+        Ok(false) => Health::ok(),
+        Ok(true) => Health::unavailable("Migrations have not been applied"),
+        Err(_) => Health::unavailable("Failed to check the migrations"),
+    }
+}
@@ -0,0 +1,62 @@
+use auth::ApiClient;
+use games::hi_lo::{Guess, HiLo, HiLoError, HiLoResponse, HiLoStatusResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>")]
+fn streak(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<HiLoStatusResponse> {
+    Json(HiLoStatusResponse::new(HiLo::restore(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/start/<bet>")]
+fn start(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    bet: u64,
+) -> Json<HiLoResponse> {
+    Json(HiLoResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(HiLoError::InvalidBet)
+            .and_then(|_| HiLo::start(&db_pool, client.namespace, user, bet)),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/guess/<guess>")]
+fn guess(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    guess: String,
+) -> Json<HiLoResponse> {
+    Json(HiLoResponse::new(guess.parse::<Guess>().and_then(|guess| {
+        HiLo::guess(&db_pool, client.namespace, user, guess, &config.hi_lo)
+    })))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/cash_out")]
+fn cash_out(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<HiLoResponse> {
+    Json(HiLoResponse::new(HiLo::cash_out(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
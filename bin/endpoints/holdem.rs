@@ -0,0 +1,64 @@
+use auth::ApiClient;
+use games::holdem::{Holdem, HoldemError, HoldemResponse, HoldemStatusResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>")]
+fn hand(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<HoldemStatusResponse> {
+    Json(HoldemStatusResponse::new(Holdem::restore(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/ante/<bet>")]
+fn ante(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    bet: u64,
+) -> Json<HoldemResponse> {
+    Json(HoldemResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(HoldemError::InvalidBet)
+            .and_then(|_| Holdem::ante(&db_pool, client.namespace, user, bet)),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/call")]
+fn call(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<HoldemResponse> {
+    Json(HoldemResponse::new(Holdem::call(
+        &db_pool,
+        client.namespace,
+        user,
+        &config.holdem,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/fold")]
+fn fold(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<HoldemResponse> {
+    Json(HoldemResponse::new(Holdem::fold(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
@@ -0,0 +1,39 @@
+use games_microservice::config::Config;
+use games_microservice::jackpot::Jackpot;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[derive(Serialize, Deserialize)]
+pub struct JackpotInfo {
+    pub pool: u64,
+    pub seed: u64,
+    /// Fraction of every slot wager added to the pool
+    pub contribution: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct JackpotResponse {
+    pub status_code: u16,
+    pub status: Result<JackpotInfo, String>,
+}
+
+/// Current progressive jackpot pool
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/jackpot")]
+fn jackpot(db_pool: State<ConnectionPool>, config: State<Config>) -> Json<JackpotResponse> {
+    Json(match Jackpot::pool(&db_pool, config.jackpot.seed) {
+        Ok(pool) => JackpotResponse {
+            status_code: 200,
+            status: Ok(JackpotInfo {
+                pool,
+                seed: config.jackpot.seed,
+                contribution: config.jackpot.contribution,
+            }),
+        },
+        Err(err) => JackpotResponse {
+            status_code: 500,
+            status: Err(err.to_string()),
+        },
+    })
+}
@@ -0,0 +1,38 @@
+use games::keno::{self, KenoError, KenoResponse, PaytableResponse, Picks};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/paytable")]
+fn paytable(config: State<Config>) -> Json<PaytableResponse> {
+    Json(PaytableResponse::new(&config.keno))
+}
+
+/// Plays `picks` like `3-17-42`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<picks>/<bet>")]
+fn play(config: State<Config>, picks: String, bet: u64) -> Json<KenoResponse> {
+    Json(KenoResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(KenoError::InvalidBet)
+            .and_then(|_| Picks::parse(&picks))
+            .map(|picks| keno::keno(bet, &picks, &config.keno)),
+    ))
+}
+
+/// Plays `count` random picks
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/quick_pick/<count>/<bet>")]
+fn quick_pick(config: State<Config>, count: usize, bet: u64) -> Json<KenoResponse> {
+    Json(KenoResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(KenoError::InvalidBet)
+            .and_then(|_| Picks::random(count))
+            .map(|picks| keno::keno(bet, &picks, &config.keno)),
+    ))
+}
@@ -0,0 +1,90 @@
+use auth::ApiClient;
+use games::lottery::{self, ClaimResponse, CurrentDrawResponse, DrawResponse, Lottery,
+                     LotteryError, TicketResponse, TicketsResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/current")]
+fn current(db_pool: State<ConnectionPool>, config: State<Config>) -> Json<CurrentDrawResponse> {
+    Json(CurrentDrawResponse::new(Lottery::current(
+        &db_pool,
+        &config.lottery,
+        lottery::unix_time(),
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/draws/<draw>", rank = 1)]
+fn draw(db_pool: State<ConnectionPool>, draw: u64) -> Json<DrawResponse> {
+    Json(DrawResponse::new(Lottery::result(&db_pool, draw)))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>/tickets", rank = 2)]
+fn tickets(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<TicketsResponse> {
+    Json(TicketsResponse::new(Lottery::tickets(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+fn buy(
+    db_pool: &ConnectionPool,
+    config: &Config,
+    namespace: i64,
+    user: u64,
+    numbers: Option<&str>,
+) -> Json<TicketResponse> {
+    let rules = &config.lottery;
+    Json(TicketResponse::new(
+        config
+            .bets
+            .check(rules.ticket_price)
+            .map_err(LotteryError::InvalidBet)
+            .and_then(|_| {
+                Lottery::buy(db_pool, namespace, user, numbers, rules, lottery::unix_time())
+            }),
+    ))
+}
+
+/// Buys a ticket with `numbers` like `3-7-12`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/ticket/<numbers>")]
+fn ticket(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    numbers: String,
+) -> Json<TicketResponse> {
+    buy(&db_pool, &config, client.namespace, user, Some(&numbers))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/quick_pick")]
+fn quick_pick(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<TicketResponse> {
+    buy(&db_pool, &config, client.namespace, user, None)
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/claim")]
+fn claim(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<ClaimResponse> {
+    Json(ClaimResponse::new(Lottery::claim(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
@@ -0,0 +1,49 @@
+use games::blackjack::BlackJack;
+use games_microservice::config::Config;
+use games_microservice::jackpot::Jackpot;
+use games_microservice::metrics::{Gauge, METRICS};
+use rocket::State;
+use rocket::response::content::Plain;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/metrics")]
+fn metrics(db_pool: State<ConnectionPool>, config: State<Config>) -> Plain<String> {
+    let pool_state = db_pool.state();
+    let mut gauges = vec![
+        Gauge {
+            name: "game_service_db_pool_connections",
+            help: "Connections currently held by the DB pool",
+            value: f64::from(pool_state.connections),
+        },
+        Gauge {
+            name: "game_service_db_pool_idle_connections",
+            help: "Idle connections in the DB pool",
+            value: f64::from(pool_state.idle_connections),
+        },
+        Gauge {
+            name: "game_service_db_pool_max_connections",
+            help: "Maximum size of the DB pool",
+            value: f64::from(db_pool.max_size()),
+        },
+    ];
+
+    // Skipped rather than failing the whole scrape
+    if let Ok(active) = BlackJack::active_sessions(&db_pool, None) {
+        gauges.push(Gauge {
+            name: "game_service_blackjack_active_sessions",
+            help: "BlackJack sessions that are still in progress",
+            value: active as f64,
+        });
+    }
+
+    if let Ok(pool) = Jackpot::pool(&db_pool, config.jackpot.seed) {
+        gauges.push(Gauge {
+            name: "game_service_jackpot_pool",
+            help: "Current progressive jackpot pool",
+            value: pool as f64,
+        });
+    }
+
+    Plain(METRICS.render(&gauges))
+}
@@ -0,0 +1,85 @@
+use auth::ApiClient;
+use games::mines::{self, Mines, MinesError, MinesResponse, MinesStatusResponse,
+                   PaytableResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/paytable/<count>")]
+fn paytable(config: State<Config>, count: u8) -> Json<PaytableResponse> {
+    Json(PaytableResponse::new(mines::paytable(count, &config.mines)))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>")]
+fn game(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<MinesStatusResponse> {
+    Json(MinesStatusResponse::new(Mines::restore(
+        &db_pool,
+        client.namespace,
+        user,
+        &config.mines,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/start/<count>/<bet>")]
+fn start(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    count: u8,
+    bet: u64,
+) -> Json<MinesResponse> {
+    Json(MinesResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(MinesError::InvalidBet)
+            .and_then(|_| {
+                Mines::start(&db_pool, client.namespace, user, count, bet, &config.mines)
+            }),
+    ))
+}
+
+/// Reveals `tile`, numbered 0 to 24 row by row
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/reveal/<tile>")]
+fn reveal(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    tile: u8,
+) -> Json<MinesResponse> {
+    Json(MinesResponse::new(Mines::reveal(
+        &db_pool,
+        client.namespace,
+        user,
+        tile,
+        &config.mines,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/cash_out")]
+fn cash_out(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<MinesResponse> {
+    Json(MinesResponse::new(Mines::cash_out(
+        &db_pool,
+        client.namespace,
+        user,
+        &config.mines,
+    )))
+}
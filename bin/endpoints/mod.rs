@@ -3,6 +3,23 @@ mod blackjack_route;
 mod slot_machine;
 mod coin_toss;
 mod rps;
+mod video_poker;
+mod holdem;
+mod baccarat;
+mod roulette;
+mod craps;
+mod dice;
+mod war;
+mod hi_lo;
+mod keno;
+mod lottery;
+mod crash;
+mod mines;
+mod plinko;
+mod rate_limited;
+mod metrics;
+mod health;
+mod jackpot;
 
 pub fn router(rocket: Rocket) -> Rocket {
     rocket
@@ -17,9 +34,135 @@ pub fn router(rocket: Rocket) -> Rocket {
                 blackjack_route::claim
             ],
         )
-        .mount("/slot_machine", routes![slot_machine::slots])
-        .mount("/coin_toss", routes![coin_toss::coin_toss])
-        .mount("/rps", routes![rps::rps])
+        .mount(
+            "/slot_machine",
+            routes![
+                slot_machine::slots,
+                slot_machine::info,
+                slot_machine::grid,
+                slot_machine::grid_info,
+                slot_machine::free_spin,
+                slot_machine::free_spins
+            ],
+        )
+        .mount(
+            "/coin_toss",
+            routes![
+                coin_toss::coin_toss,
+                coin_toss::paytable,
+                coin_toss::heads,
+                coin_toss::same,
+                coin_toss::streak,
+                coin_toss::start_streak,
+                coin_toss::double_streak,
+                coin_toss::cash_out
+            ],
+        )
+        .mount(
+            "/rps",
+            routes![
+                rps::rps,
+                rps::rps_variant,
+                rps::variants,
+                rps::rps_vs,
+                rps::rps_variant_vs,
+                rps::rps_match,
+                rps::start_match,
+                rps::throw,
+                rps::challenge,
+                rps::create_challenge,
+                rps::accept_challenge,
+                rps::reveal_challenge,
+                rps::time_out_challenge
+            ],
+        )
+        .mount(
+            "/video_poker",
+            routes![
+                video_poker::paytable,
+                video_poker::hand,
+                video_poker::deal,
+                video_poker::draw
+            ],
+        )
+        .mount(
+            "/holdem",
+            routes![holdem::hand, holdem::ante, holdem::call, holdem::fold],
+        )
+        .mount("/baccarat", routes![baccarat::single, baccarat::bets])
+        .mount(
+            "/roulette",
+            routes![roulette::tables, roulette::spin, roulette::history],
+        )
+        .mount(
+            "/craps",
+            routes![
+                craps::table,
+                craps::bet,
+                craps::place,
+                craps::line_odds,
+                craps::come_odds,
+                craps::roll
+            ],
+        )
+        .mount(
+            "/dice",
+            routes![dice::roll, dice::roll_seeded, dice::sic_bo, dice::over_under],
+        )
+        .mount(
+            "/war",
+            routes![war::tie, war::deal, war::war, war::surrender],
+        )
+        .mount(
+            "/hi_lo",
+            routes![hi_lo::streak, hi_lo::start, hi_lo::guess, hi_lo::cash_out],
+        )
+        .mount(
+            "/keno",
+            routes![keno::paytable, keno::play, keno::quick_pick],
+        )
+        .mount(
+            "/lottery",
+            routes![
+                lottery::current,
+                lottery::draw,
+                lottery::tickets,
+                lottery::ticket,
+                lottery::quick_pick,
+                lottery::claim
+            ],
+        )
+        .mount(
+            "/crash",
+            routes![
+                crash::current,
+                crash::round,
+                crash::status,
+                crash::bet,
+                crash::cash_out
+            ],
+        )
+        .mount(
+            "/mines",
+            routes![
+                mines::paytable,
+                mines::game,
+                mines::start,
+                mines::reveal,
+                mines::cash_out
+            ],
+        )
+        .mount("/plinko", routes![plinko::paytable, plinko::play])
+        .mount(
+            "/",
+            routes![
+                rate_limited::rate_limited,
+                metrics::metrics,
+                health::healthz,
+                health::readyz,
+                jackpot::jackpot
+            ],
+        )
 }
 
 #[cfg(test)]
@@ -0,0 +1,25 @@
+use games::plinko::{self, PaytableResponse, PlinkoError, PlinkoResponse, Risk};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/paytable/<rows>/<risk>", rank = 1)]
+fn paytable(config: State<Config>, rows: u8, risk: String) -> Json<PaytableResponse> {
+    Json(PaytableResponse::new(risk.parse::<Risk>().and_then(|risk| {
+        plinko::paytable(rows, risk, &config.plinko)
+    })))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<rows>/<risk>/<bet>", rank = 2)]
+fn play(config: State<Config>, rows: u8, risk: String, bet: u64) -> Json<PlinkoResponse> {
+    Json(PlinkoResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(PlinkoError::InvalidBet)
+            .and_then(|_| risk.parse::<Risk>())
+            .and_then(|risk| plinko::plinko(bet, rows, risk, &config.plinko)),
+    ))
+}
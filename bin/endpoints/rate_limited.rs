@@ -0,0 +1,33 @@
+use rocket::http::{Header, Status};
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
+use rocket_contrib::Json;
+
+#[derive(Serialize)]
+pub struct RateLimitedResponse {
+    status_code: u16,
+    status: Result<(), String>,
+}
+
+/// 429 with a `Retry-After` header
+pub struct RateLimited(u64);
+
+impl<'r> Responder<'r> for RateLimited {
+    fn respond_to(self, request: &Request) -> response::Result<'r> {
+        let body = Json(RateLimitedResponse {
+            status_code: 429,
+            status: Err(format!("Rate limited, retry in {} seconds", self.0)),
+        });
+
+        Response::build_from(body.respond_to(request)?)
+            .status(Status::TooManyRequests)
+            .header(Header::new("Retry-After", self.0.to_string()))
+            .ok()
+    }
+}
+
+/// Requests over their limit are rerouted here by the rate limit fairing
+#[get("/rate_limited/<retry_after>")]
+fn rate_limited(retry_after: u64) -> RateLimited {
+    RateLimited(retry_after)
+}
@@ -0,0 +1,66 @@
+use auth::ApiClient;
+use games::roulette::{total_bet, HistoryResponse, Roulette, RouletteBet, RouletteError,
+                      RouletteResponse, TablesResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+/// Every bet and their total have to be within the limits
+fn check_bets(bets: &[RouletteBet], config: &Config) -> Result<(), RouletteError> {
+    for bet in bets {
+        config.bets.check(bet.amount).map_err(RouletteError::InvalidBet)?;
+    }
+
+    let total = total_bet(bets)?;
+    // Nothing bet is reported by the spin itself
+    if total > 0 {
+        config.bets.check(total).map_err(RouletteError::InvalidBet)?;
+    }
+    Ok(())
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/tables")]
+fn tables(config: State<Config>) -> Json<TablesResponse> {
+    Json(TablesResponse::new(&config.roulette))
+}
+
+/// Settles every bet of the body against one spin of `table`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<table>/spin", format = "application/json", data = "<bets>")]
+fn spin(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    table: String,
+    bets: Json<Vec<RouletteBet>>,
+) -> Json<RouletteResponse> {
+    if let Err(err) = check_bets(&bets, &config) {
+        return Json(RouletteResponse::new(Err(err)));
+    }
+
+    Json(RouletteResponse::new(Roulette::spin(
+        &db_pool,
+        client.namespace,
+        &table,
+        &bets,
+        &config.roulette,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<table>/history")]
+fn history(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    table: String,
+) -> Json<HistoryResponse> {
+    Json(HistoryResponse::new(Roulette::history(
+        &db_pool,
+        client.namespace,
+        &table,
+        &config.roulette,
+    )))
+}
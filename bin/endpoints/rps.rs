@@ -1,10 +1,288 @@
+use auth::ApiClient;
 use games::rps as rps_game;
+use games_microservice::config::Config;
+use rocket::State;
 use rocket_contrib::Json;
+use ConnectionPool;
 
-use self::rps_game::Response;
+use self::rps_game::{AdaptiveResponse, Challenge, ChallengeResponse, ChallengeStatusResponse,
+                     MatchResponse, MatchStatusResponse, Response, RpsError, RpsMatch, Strategy,
+                     Variant, VariantsResponse, Weapons};
 
 #[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
-#[get("/<weapon>/<bet>")]
-fn rps(weapon: String, bet: u64) -> Json<Response> {
-    Json(rps_game::rps(bet, &weapon))
+#[get("/<weapon>/<bet>", rank = 2)]
+fn rps(config: State<Config>, weapon: String, bet: u64) -> Json<Response> {
+    Json(match config.bets.check(bet) {
+        Ok(_) => rps_game::rps_with_rules(bet, &weapon, &config.rps),
+        Err(why) => Response::error(bet, why),
+    })
+}
+
+/// Plays the `[[rps.variants]]` entry named `variant`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<variant>/<weapon>/<bet>", rank = 2)]
+fn rps_variant(
+    config: State<Config>,
+    variant: String,
+    weapon: String,
+    bet: u64,
+) -> Json<Response> {
+    Json(match config.bets.check(bet) {
+        Ok(_) => rps_game::rps_variant(bet, &variant, &weapon, &config.rps),
+        Err(why) => Response::error(bet, why),
+    })
+}
+
+/// Every variant and who beats who
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/variants")]
+fn variants(config: State<Config>) -> Json<VariantsResponse> {
+    Json(VariantsResponse::new(&config.rps))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+fn adaptive(
+    db_pool: &ConnectionPool,
+    config: &Config,
+    namespace: i64,
+    user: u64,
+    strategy: &str,
+    variant: &str,
+    weapon: &str,
+    bet: u64,
+) -> AdaptiveResponse {
+    AdaptiveResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(RpsError::InvalidBet)
+            .and_then(|_| strategy.parse::<Strategy>())
+            .and_then(|strategy| {
+                rps_game::rps_adaptive(
+                    db_pool,
+                    namespace,
+                    user,
+                    variant,
+                    strategy,
+                    weapon,
+                    bet,
+                    &config.rps,
+                )
+            }),
+    )
+}
+
+/// Classic RPS against a `strategy` opponent (or difficulty), which learns from `user`'s throws
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/vs/<strategy>/<weapon>/<bet>")]
+fn rps_vs(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    strategy: String,
+    weapon: String,
+    bet: u64,
+) -> Json<AdaptiveResponse> {
+    Json(adaptive(
+        &db_pool,
+        &config,
+        client.namespace,
+        user,
+        &strategy,
+        "rps",
+        &weapon,
+        bet,
+    ))
+}
+
+/// Plays `variant` against a `strategy` opponent (or difficulty)
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value, too_many_arguments))]
+#[post("/<user>/vs/<strategy>/<variant>/<weapon>/<bet>")]
+fn rps_variant_vs(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    strategy: String,
+    variant: String,
+    weapon: String,
+    bet: u64,
+) -> Json<AdaptiveResponse> {
+    Json(adaptive(
+        &db_pool,
+        &config,
+        client.namespace,
+        user,
+        &strategy,
+        &variant,
+        &weapon,
+        bet,
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>/match", rank = 1)]
+fn rps_match(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<MatchStatusResponse> {
+    Json(MatchStatusResponse::new(RpsMatch::restore(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+/// Starts a best of `best_of` match, taking `bet`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/match/<best_of>/<bet>")]
+fn start_match(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    best_of: u32,
+    bet: u64,
+) -> Json<MatchResponse> {
+    Json(MatchResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(RpsError::InvalidBet)
+            .and_then(|_| RpsMatch::start(&db_pool, client.namespace, user, best_of, bet)),
+    ))
+}
+
+/// Plays the next round of the match
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/match/<weapon>")]
+fn throw(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    weapon: String,
+) -> Json<MatchResponse> {
+    Json(MatchResponse::new(
+        weapon
+            .parse::<Weapons>()
+            .map_err(|_| RpsError::InvalidWeapon(Variant::classic().choices()))
+            .and_then(|weapon| {
+                RpsMatch::throw(&db_pool, client.namespace, user, weapon, &config.rps)
+            }),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>/challenge/<id>", rank = 1)]
+fn challenge(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    id: i64,
+) -> Json<ChallengeStatusResponse> {
+    Json(ChallengeStatusResponse::new(Challenge::restore(
+        &db_pool,
+        client.namespace,
+        user,
+        id,
+        &config.rps,
+    )))
+}
+
+/// Challenges `opponent` to `variant` for `stake`, see `rps_game::commitment` for `commitment`
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value, too_many_arguments))]
+#[post("/<user>/challenge/<opponent>/<variant>/<stake>/<commitment>")]
+fn create_challenge(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    opponent: u64,
+    variant: String,
+    stake: u64,
+    commitment: String,
+) -> Json<ChallengeResponse> {
+    Json(ChallengeResponse::new(
+        config
+            .bets
+            .check(stake)
+            .map_err(RpsError::InvalidBet)
+            .and_then(|_| {
+                Challenge::create(
+                    &db_pool,
+                    client.namespace,
+                    user,
+                    opponent,
+                    &variant,
+                    stake,
+                    &commitment,
+                    &config.rps,
+                )
+            }),
+    ))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/accept/<id>/<weapon>")]
+fn accept_challenge(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    id: i64,
+    weapon: String,
+) -> Json<ChallengeResponse> {
+    Json(ChallengeResponse::new(Challenge::accept(
+        &db_pool,
+        client.namespace,
+        user,
+        id,
+        &weapon,
+        &config.rps,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/reveal/<id>/<weapon>/<salt>")]
+fn reveal_challenge(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    id: i64,
+    weapon: String,
+    salt: String,
+) -> Json<ChallengeResponse> {
+    Json(ChallengeResponse::new(Challenge::reveal(
+        &db_pool,
+        client.namespace,
+        user,
+        id,
+        &weapon,
+        &salt,
+        &config.rps,
+    )))
+}
+
+/// Refunds an unaccepted challenge or forfeits an unrevealed one, once past its deadline
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/timeout/<id>")]
+fn time_out_challenge(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    id: i64,
+) -> Json<ChallengeResponse> {
+    Json(ChallengeResponse::new(Challenge::time_out(
+        &db_pool,
+        client.namespace,
+        user,
+        id,
+        &config.rps,
+    )))
 }
@@ -1,10 +1,115 @@
-use games::slot_machine::{Response, SlotMachine};
+use auth::ApiClient;
+use games::slot_machine::{FreeSpins, FreeSpinsResponse, GridInfoResponse, GridResponse,
+                          InfoResponse, Response, SlotMachine};
+use games_microservice::config::Config;
+use rocket::State;
 use rocket_contrib::Json;
+use ConnectionPool;
 
-#[get("/<bet>")]
-fn slots(bet: u64) -> Json<Response> {
-    Json(Response {
-        status_code: 200,
-        status: Ok(SlotMachine::new(bet)),
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<bet>", rank = 2)]
+fn slots(db_pool: State<ConnectionPool>, config: State<Config>, bet: u64) -> Json<Response> {
+    if let Err(why) = config.bets.check(bet) {
+        return Json(Response {
+            status_code: 501,
+            status: Err(why),
+        });
+    }
+
+    Json(
+        match SlotMachine::with_jackpot(bet, &config.slot_machine, &config.jackpot, &db_pool) {
+            Ok(spin) => Response {
+                status_code: 200,
+                status: Ok(spin),
+            },
+            Err(err) => Response {
+                status_code: 500,
+                status: Err(err.to_string()),
+            },
+        },
+    )
+}
+
+/// RTP and paytable of the configured machine
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/info")]
+fn info(config: State<Config>) -> Json<InfoResponse> {
+    Json(InfoResponse::new(&config.slot_machine))
+}
+
+/// Spins the multi-line machine for `user`, `bet` is wagered on each of the first `lines` paylines
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/grid/<lines>/<bet>")]
+fn grid(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    lines: usize,
+    bet: u64,
+) -> Json<GridResponse> {
+    if let Err(why) = config.bets.check(bet.saturating_mul(lines as u64)) {
+        return Json(GridResponse {
+            status_code: 501,
+            status: Err(why),
+        });
+    }
+
+    Json(
+        match FreeSpins::spin(
+            &db_pool,
+            client.namespace,
+            user,
+            bet,
+            lines,
+            &config.slot_machine.grid,
+            &config.jackpot,
+        ) {
+            Ok(spin) => GridResponse::success(spin),
+            Err(err) => GridResponse::error(&err),
+        },
+    )
+}
+
+/// Plays one of `user`'s free spins at the bet that won them
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/free_spin")]
+fn free_spin(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<GridResponse> {
+    Json(
+        match FreeSpins::free_spin(
+            &db_pool,
+            client.namespace,
+            user,
+            &config.slot_machine.grid,
+            &config.jackpot,
+        ) {
+            Ok(spin) => GridResponse::success(spin),
+            Err(err) => GridResponse::error(&err),
+        },
+    )
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>/free_spins")]
+fn free_spins(
+    db_pool: State<ConnectionPool>,
+    client: ApiClient,
+    user: u64,
+) -> Json<FreeSpinsResponse> {
+    Json(match FreeSpins::restore(&db_pool, client.namespace, user) {
+        Ok(free_spins) => FreeSpinsResponse::success(free_spins),
+        Err(err) => FreeSpinsResponse::error(&err),
     })
 }
+
+/// Paylines, paytable and per line RTP of the multi-line machine
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/grid/info")]
+fn grid_info(config: State<Config>) -> Json<GridInfoResponse> {
+    Json(GridInfoResponse::new(&config.slot_machine.grid))
+}
@@ -1,13 +1,23 @@
 extern crate serde_json;
 extern crate test;
 
+use auth::API_KEY_HEADER;
 use games::blackjack::Response;
+use games::crash::{self, Rounds};
+use games::rps::commitment;
+use games_microservice::auth::ApiKey;
+use games_microservice::rate_limit::{GroupLimits, Quota, RateLimiter};
+use rate_limit::RateLimit;
 use endpoints::router;
 use rocket;
+use rocket::http::{ContentType, Header, Status};
 use rocket::local::Client;
 use serde_json::Value;
 use self::test::Bencher;
-use establish_connection_pool;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+use establish_test_connection_pool;
+use games_microservice::config::Config;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct ActiveSessionsCount {
@@ -26,23 +36,76 @@ pub struct Claim {
     pub status: Result<u64, String>,
 }
 
+/// Creates a client along with a fresh API key, so every run gets its own namespace
+fn create_client_with_key() -> (Client, Header<'static>) {
+    let pool = establish_test_connection_pool();
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .subsec_nanos();
+    let (_, raw_key) = ApiKey::create(&*pool.get().unwrap(), &format!("test-{}", nanos))
+        .expect("Failed to create API key");
+
+    (
+        Client::new(router(
+            rocket::ignite().manage(pool).manage(Config::default()),
+        )).unwrap(),
+        Header::new(API_KEY_HEADER, raw_key),
+    )
+}
+
 fn create_client(use_db: bool) -> Client {
     if use_db {
         Client::new(router(
-            rocket::ignite().manage(establish_connection_pool().clone()),
+            rocket::ignite()
+                .manage(establish_test_connection_pool())
+                .manage(Config::default()),
         )).unwrap()
     } else {
-        Client::new(router(rocket::ignite())).unwrap()
+        Client::new(router(rocket::ignite().manage(Config::default()))).unwrap()
     }
 }
 
 #[test]
-fn test_blackjack_routes() {
+fn test_blackjack_requires_key() {
     let client = create_client(true);
 
+    let resp = client.get("/blackjack/0").dispatch();
+    assert_eq!(resp.status(), Status::Unauthorized);
+
+    let resp = client
+        .get("/blackjack/0")
+        .header(Header::new(API_KEY_HEADER, "not a key"))
+        .dispatch();
+    assert_eq!(resp.status(), Status::Unauthorized);
+}
+
+#[test]
+fn test_blackjack_namespaces() {
+    let (client, key) = create_client_with_key();
+    let (_, other_key) = create_client_with_key();
+
+    // The same user id under two keys are two different players
+    for key in &[key, other_key] {
+        let mut resp = client
+            .post("/blackjack/0/create/1")
+            .header(key.clone())
+            .dispatch();
+        let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp.status_code, 200);
+    }
+}
+
+#[test]
+fn test_blackjack_routes() {
+    let (client, key) = create_client_with_key();
+
     // Test session counter (should be 0)
     {
-        let mut resp = client.get("/blackjack/").dispatch();
+        let mut resp = client
+            .get("/blackjack/")
+            .header(key.clone())
+            .dispatch();
         let resp: ActiveSessions = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 200);
         assert_eq!(
@@ -55,14 +118,20 @@ fn test_blackjack_routes() {
 
     // Test Creation and info route
     {
-        let mut resp = client.post("/blackjack/0/create/1").dispatch();
+        let mut resp = client
+            .post("/blackjack/0/create/1")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 200);
         let resp = resp.status
             .expect("An Error has occurred on session creation");
         assert_eq!(resp.dealer_hand.len(), 1);
         assert!(resp.game_state.is_none());
-        let mut resp = client.get("/blackjack/0").dispatch();
+        let mut resp = client
+            .get("/blackjack/0")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 200);
         assert!(
@@ -74,13 +143,19 @@ fn test_blackjack_routes() {
     }
     // Test Creation route fails
     {
-        let mut resp = client.post("/blackjack/0/create/1").dispatch();
+        let mut resp = client
+            .post("/blackjack/0/create/1")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 501);
     }
     // Test Hit Route
     {
-        let mut resp = client.post("/blackjack/0/hit").dispatch();
+        let mut resp = client
+            .post("/blackjack/0/hit")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 200);
         assert!(match resp.status {
@@ -90,7 +165,10 @@ fn test_blackjack_routes() {
     }
     // Test Stay Route
     {
-        let mut resp = client.post("/blackjack/0/stay").dispatch();
+        let mut resp = client
+            .post("/blackjack/0/stay")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 200);
         assert!(
@@ -102,13 +180,19 @@ fn test_blackjack_routes() {
     }
     // Test Hit doesn't work
     {
-        let mut resp = client.post("/blackjack/0/hit").dispatch();
+        let mut resp = client
+            .post("/blackjack/0/hit")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 501);
     }
     // Test claim route works
     {
-        let mut resp = client.get("/blackjack/0").dispatch();
+        let mut resp = client
+            .get("/blackjack/0")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         assert_eq!(resp.status_code, 200);
         let status = resp.status.unwrap();
@@ -116,7 +200,10 @@ fn test_blackjack_routes() {
         if status.game_state.unwrap() {
             expected_gain = 1;
         }
-        let mut resp = client.post("/blackjack/0/claim").dispatch();
+        let mut resp = client
+            .post("/blackjack/0/claim")
+            .header(key.clone())
+            .dispatch();
         let resp: Response = serde_json::from_str(&resp.body_string().unwrap()).unwrap();
         let status_code: u16 = resp.status_code as u16;
         let returned_gain: i64 = resp.status.unwrap().gain;
@@ -125,27 +212,373 @@ fn test_blackjack_routes() {
     }
 }
 
+#[test]
+fn test_rate_limited_slots() {
+    let mut groups = HashMap::new();
+    groups.insert(
+        String::from("slot_machine"),
+        GroupLimits {
+            per_key: Quota::new(1, 60),
+            per_user: Quota::new(1, 60),
+        },
+    );
+    let client = Client::new(
+        router(
+            rocket::ignite()
+                .manage(establish_test_connection_pool())
+                .manage(Config::default()),
+        ).attach(RateLimit(RateLimiter::new(groups))),
+    ).unwrap();
+
+    let resp = client.get("/slot_machine/1").dispatch();
+    assert_eq!(resp.status(), Status::Ok);
+
+    let resp = client.get("/slot_machine/1").dispatch();
+    assert_eq!(resp.status(), Status::TooManyRequests);
+    assert_eq!(resp.headers().get_one("Retry-After"), Some("60"));
+
+    // Neither made up keys nor other bets get a fresh bucket
+    let resp = client
+        .get("/slot_machine/2")
+        .header(Header::new(API_KEY_HEADER, "made up"))
+        .dispatch();
+    assert_eq!(resp.status(), Status::TooManyRequests);
+}
+
+#[test]
+fn test_grid_slots_route() {
+    let (client, key) = create_client_with_key();
+
+    let resp = client.post("/slot_machine/0/grid/9/10").dispatch();
+    assert_eq!(resp.status(), Status::Unauthorized);
+
+    let mut resp = client
+        .post("/slot_machine/0/grid/10/10")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post("/slot_machine/0/grid/9/10")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+    assert_eq!(resp["status"]["Ok"]["bet"], 90);
+    assert_eq!(resp["status"]["Ok"]["grid"].as_array().unwrap().len(), 3);
+
+    // Free spins carry over to the next requests until they are played
+    let won = resp["status"]["Ok"]["free_spins_won"].as_u64().unwrap();
+    let mut resp = client
+        .get("/slot_machine/0/free_spins")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["remaining"].as_u64(), Some(won));
+
+    let mut resp = client
+        .post("/slot_machine/0/free_spin")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    if won == 0 {
+        assert_eq!(resp["status_code"], 501);
+    } else {
+        assert_eq!(resp["status"]["Ok"]["bet"], 0);
+        assert!(resp["status"]["Ok"]["free_spin"].as_bool().unwrap());
+    }
+}
+
+#[test]
+fn test_jackpot_route() {
+    let client = create_client(true);
+
+    let mut resp = client.get("/slot_machine/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+    // The pool never drops below the seed, even right after being won
+    assert!(resp["status"]["Ok"]["jackpot"].as_u64().unwrap() >= 1000);
+
+    let mut resp = client.get("/jackpot").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+    assert!(resp["status"]["Ok"]["pool"].as_u64().unwrap() >= 1000);
+}
+
+#[test]
+fn test_coin_streak_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client
+        .post("/coin_toss/0/streak/cash_out")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    // Start streaks until one is won, then it carries over to the next requests
+    loop {
+        let mut resp = client
+            .post("/coin_toss/0/streak/h/100")
+            .header(key.clone())
+            .dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status"]["Ok"]["gain"], -100);
+        if !resp["status"]["Ok"]["streak"].is_null() {
+            assert_eq!(resp["status"]["Ok"]["streak"]["pot"], 196);
+            break;
+        }
+    }
+
+    let mut resp = client
+        .get("/coin_toss/0/streak")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["wins"], 1);
+
+    let mut resp = client
+        .post("/coin_toss/0/streak/h/100")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post("/coin_toss/0/streak/cash_out")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], 196);
+}
+
+#[test]
+fn test_rps_variant_routes() {
+    let client = create_client(false);
+
+    let mut resp = client.get("/rps/rpsls/spock/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["player"], "Spock");
+
+    let mut resp = client.get("/rps/rpsls/s/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/rps/variants").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].as_array().unwrap().len(), 2);
+}
+
+#[test]
+fn test_rps_adaptive_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.post("/rps/0/vs/easy/rock/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["win_multiplier"], 0.25);
+    assert!(resp["status"]["Ok"]["prediction"].is_null());
+
+    // The model kept the throw above, the opponent still throws at random part of the time
+    let mut gains = Vec::new();
+    for _ in 0..5 {
+        let mut resp = client
+            .post("/rps/0/vs/frequency/rock/100")
+            .header(key.clone())
+            .dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert!(resp["status"]["Ok"]["prediction"].is_null());
+        gains.push(resp["status"]["Ok"]["gain"].as_i64().unwrap());
+    }
+    // Half of the throws random, a win pays about (1/6 - 2%) / (2/3) of the bet
+    assert!(gains.iter().all(|&gain| gain == -100 || gain == 0 || (gain > 0 && gain < 25)));
+
+    let mut resp = client
+        .post("/rps/0/vs/hard/rpsls/spock/100")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    let multiplier = resp["status"]["Ok"]["win_multiplier"].as_f64().unwrap();
+    assert!(multiplier > 0.0 && multiplier < 0.25);
+
+    let mut resp = client
+        .post("/rps/0/vs/impossible/rock/100")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_rps_match_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.post("/rps/0/match/4/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/rps/0/match/3/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -100);
+
+    let mut resp = client.post("/rps/0/match/3/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    // Throw until the match settles, the state carries over between requests
+    let mut rounds = 0;
+    loop {
+        let mut resp = client.post("/rps/0/match/rock").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        rounds += 1;
+        let rps_match = &resp["status"]["Ok"]["match"];
+        assert_eq!(rps_match["rounds"].as_array().unwrap().len(), rounds);
+        if rps_match["player_score"] == 2 {
+            assert_eq!(resp["status"]["Ok"]["gain"], 190);
+            break;
+        }
+        if rps_match["computer_score"] == 2 {
+            assert_eq!(resp["status"]["Ok"]["gain"], 0);
+            break;
+        }
+
+        let mut resp = client.get("/rps/0/match").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status"]["Ok"]["rounds"].as_array().unwrap().len(), rounds);
+    }
+
+    let mut resp = client.get("/rps/0/match").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert!(resp["status"]["Ok"].is_null());
+}
+
+#[test]
+fn test_rps_challenge_routes() {
+    let (client, key) = create_client_with_key();
+    let committed = commitment("rock", "some long random salt");
+
+    let mut resp = client
+        .post(format!("/rps/1/challenge/1/rps/100/{}", committed))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post(format!("/rps/1/challenge/2/rps/100/{}", committed))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["bet"], 100);
+    let id = resp["status"]["Ok"]["challenge"]["id"].as_i64().unwrap();
+
+    // Only the challenged user can accept, and the challenger can't reveal before that
+    let mut resp = client
+        .post(format!("/rps/3/accept/{}/paper", id))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post(format!("/rps/2/accept/{}/paper", id))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["challenge"]["status"], "Accepted");
+
+    let mut resp = client
+        .post(format!("/rps/1/timeout/{}", id))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post(format!("/rps/1/reveal/{}/scissors/some%20long%20random%20salt", id))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post(format!("/rps/1/reveal/{}/rock/some%20long%20random%20salt", id))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["challenge"]["winner"], 2);
+    assert_eq!(resp["status"]["Ok"]["opponent_payout"], 200);
+
+    let mut resp = client
+        .get(format!("/rps/1/challenge/{}", id))
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["status"], "Settled");
+}
+
+#[test]
+fn test_multi_coin_routes() {
+    let client = create_client(false);
+
+    let mut resp = client.get("/coin_toss/paytable/2").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].as_array().unwrap().len(), 4);
+
+    let mut resp = client.get("/coin_toss/4/heads/2/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["coins"].as_array().unwrap().len(), 4);
+
+    let mut resp = client.get("/coin_toss/4/same/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+
+    let mut resp = client.get("/coin_toss/100/same/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+    // A single guess pays the true odds of a fair coin minus the 2% edge
+    let mut resp = client.get("/coin_toss/h/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    let gain = resp["status"]["Ok"]["gain"].as_i64().unwrap();
+    assert!(gain == -100 || gain == 95 || gain == 96);
+}
+
+#[test]
+fn test_health_routes() {
+    let client = create_client(false);
+    let resp = client.get("/healthz").dispatch();
+    assert_eq!(resp.status(), Status::Ok);
+
+    let client = create_client(true);
+    let resp = client.get("/readyz").dispatch();
+    assert_eq!(resp.status(), Status::Ok);
+}
+
 #[bench]
 fn bench_blackjack_routes(b: &mut Bencher) {
-    let client = create_client(true);
+    let (client, key) = create_client_with_key();
 
     b.iter(|| {
-        client.post("/blackjack/16/create/1").dispatch();
-        client.post("/blackjack/16/stay").dispatch();
-        client.post("/blackjack/16/claim").dispatch();
+        client
+            .post("/blackjack/16/create/1")
+            .header(key.clone())
+            .dispatch();
+        client.post("/blackjack/16/stay").header(key.clone()).dispatch();
+        client.post("/blackjack/16/claim").header(key.clone()).dispatch();
     })
 }
 
 #[bench]
 fn test_slot_route(b: &mut Bencher) {
-    let client = create_client(false);
+    let client = create_client(true);
 
     b.iter(|| {
         let mut resp = client.get("/slot_machine/23").dispatch();
         let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
-        let ret: &i64 = &resp["status"]["Ok"]["gain"].as_i64().unwrap();
+        let jackpot_won = resp["status"]["Ok"]["jackpot_won"].as_i64().unwrap();
+        let ret = resp["status"]["Ok"]["gain"].as_i64().unwrap() - jackpot_won;
 
-        if !vec![-23, 11, 23].iter().any(|i| i == ret) {
+        if !vec![-23, 17, 92, 207, 1127].iter().any(|i| *i == ret) {
             panic!("SLOTS DID BAD MATH");
         }
     })
@@ -156,3 +589,472 @@ fn bench_coin_toss_route(b: &mut Bencher) {
     let client = create_client(false);
     b.iter(|| client.get("/coin_toss/h/100").dispatch())
 }
+
+#[test]
+fn test_video_poker_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.get("/video_poker/paytable").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"][0]["pays"], 800);
+
+    let mut resp = client.post("/video_poker/0/draw/11111").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/video_poker/0/deal/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -100);
+    let dealt = resp["status"]["Ok"]["hand"].clone();
+
+    let mut resp = client.post("/video_poker/0/deal/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/video_poker/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["hand"], dealt);
+
+    let mut resp = client.post("/video_poker/0/draw/110").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    // Holding everything keeps the dealt hand
+    let mut resp = client.post("/video_poker/0/draw/11111").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["hand"], dealt);
+    assert!(resp["status"]["Ok"]["gain"].as_i64().unwrap() >= 0);
+
+    let mut resp = client.get("/video_poker/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert!(resp["status"]["Ok"].is_null());
+}
+
+#[test]
+fn test_holdem_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.post("/holdem/0/call").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/holdem/0/ante/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -100);
+    assert_eq!(resp["status"]["Ok"]["board"].as_array().unwrap().len(), 3);
+    assert!(resp["status"]["Ok"]["dealer"].is_null());
+
+    let mut resp = client.post("/holdem/0/ante/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/holdem/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["ante"], 100);
+
+    let mut resp = client.post("/holdem/0/call").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["board"].as_array().unwrap().len(), 5);
+    assert_eq!(resp["status"]["Ok"]["call"], 200);
+    assert!(!resp["status"]["Ok"]["outcome"].is_null());
+
+    let mut resp = client.post("/holdem/0/ante/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+
+    let mut resp = client.post("/holdem/0/fold").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["outcome"], "Fold");
+    assert_eq!(resp["status"]["Ok"]["gain"], 0);
+
+    let mut resp = client.get("/holdem/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert!(resp["status"]["Ok"].is_null());
+}
+
+#[test]
+fn test_baccarat_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.get("/baccarat/banker/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["bets"]["banker"], 100);
+    assert!(resp["status"]["Ok"]["coup"]["player"]["rule"].is_string());
+
+    let mut resp = client.get("/baccarat/dealer/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post("/baccarat/bets")
+        .header(key.clone())
+        .header(ContentType::JSON)
+        .body(r#"{"player": 100, "tie": 10, "banker_pair": 5}"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["bet"], 115);
+
+    // Wrapping around to a small total
+    let mut resp = client
+        .post("/baccarat/bets")
+        .header(key.clone())
+        .header(ContentType::JSON)
+        .body(r#"{"player": 18446744073709551615, "tie": 2}"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_roulette_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.get("/roulette/tables").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"][1]["wheel"], "American");
+
+    let mut resp = client
+        .post("/roulette/american/spin")
+        .header(key.clone())
+        .header(ContentType::JSON)
+        .body(r#"[{"bet": "straight 00", "amount": 10}, {"bet": "red", "amount": 20}]"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["bet"], 30);
+    assert_eq!(resp["status"]["Ok"]["bets"].as_array().unwrap().len(), 2);
+    let pocket = resp["status"]["Ok"]["pocket"].clone();
+
+    // Each bet is checked, not only the total they wrap around to
+    let mut resp = client
+        .post("/roulette/american/spin")
+        .header(key.clone())
+        .header(ContentType::JSON)
+        .body(r#"[{"bet": "red", "amount": 18446744073709551615}, {"bet": "black", "amount": 2}]"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post("/roulette/european/spin")
+        .header(key.clone())
+        .header(ContentType::JSON)
+        .body(r#"[{"bet": "straight 00", "amount": 10}]"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .get("/roulette/american/history")
+        .header(key.clone())
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["spins"][0], pocket);
+    assert_eq!(resp["status"]["Ok"]["counts"].as_array().unwrap().len(), 38);
+
+    let mut resp = client.get("/roulette/nowhere/history").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_craps_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.post("/craps/0/roll").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/craps/0/bet/come/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/craps/0/bet/pass/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -100);
+
+    let mut resp = client.post("/craps/0/place/7/50").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/craps/0/place/6/60").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["bets"].as_array().unwrap().len(), 2);
+
+    let mut resp = client.post("/craps/0/odds/pass/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/craps/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert!(resp["status"]["Ok"]["point"].is_null());
+    assert_eq!(resp["status"]["Ok"]["bets"][0]["kind"], "Pass");
+
+    let mut resp = client.post("/craps/0/roll").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+    assert!(resp["status"]["Ok"]["come_out"].as_bool().unwrap());
+}
+
+#[test]
+fn test_dice_routes() {
+    let client = create_client(false);
+
+    let mut resp = client.get("/dice/roll/4d6/42").dispatch();
+    let first = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(first["status"]["Ok"]["rolls"].as_array().unwrap().len(), 4);
+    let mut resp = client.get("/dice/roll/4d6/42").dispatch();
+    let second = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(first["status"]["Ok"]["rolls"], second["status"]["Ok"]["rolls"]);
+
+    let mut resp = client.get("/dice/roll/4x6").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client
+        .post("/dice/sic_bo")
+        .header(ContentType::JSON)
+        .body(r#"[{"bet": "big", "amount": 10}, {"bet": "total 10", "amount": 5}]"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["bet"], 15);
+    assert_eq!(resp["status"]["Ok"]["bets"].as_array().unwrap().len(), 2);
+
+    // Each bet is checked, not only the total they wrap around to
+    let mut resp = client
+        .post("/dice/sic_bo")
+        .header(ContentType::JSON)
+        .body(r#"[{"bet": "big", "amount": 18446744073709551615}, {"bet": "small", "amount": 2}]"#)
+        .dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/dice/over_under/over/7/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["rolls"].as_array().unwrap().len(), 2);
+
+    let mut resp = client.get("/dice/over_under/over/12/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_war_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.post("/war/0/surrender").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/war/0/deal/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+    let tied = resp["status"]["Ok"]["outcome"] == "Tie";
+
+    let mut resp = client.get("/war/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].is_null(), !tied);
+
+    if tied {
+        let mut resp = client.post("/war/0/deal/100").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status_code"], 501);
+
+        let mut resp = client.post("/war/0/surrender").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status"]["Ok"]["gain"], 50);
+    }
+}
+
+#[test]
+fn test_hi_lo_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.post("/hi_lo/0/guess/higher").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/hi_lo/0/start/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -100);
+    assert_eq!(resp["status"]["Ok"]["streak"]["cards_left"], 51);
+
+    let mut resp = client.post("/hi_lo/0/start/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/hi_lo/0/guess/sideways").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/hi_lo/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["pot"], 100);
+
+    let mut resp = client.post("/hi_lo/0/cash_out").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], 100);
+
+    let mut resp = client.post("/hi_lo/0/cash_out").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_keno_routes() {
+    let client = create_client(false);
+
+    let mut resp = client.get("/keno/paytable").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].as_array().unwrap().len(), 10);
+
+    let mut resp = client.get("/keno/3-17-42/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["drawn"].as_array().unwrap().len(), 20);
+    assert_eq!(resp["status"]["Ok"]["picks"].as_array().unwrap().len(), 3);
+
+    let mut resp = client.get("/keno/quick_pick/10/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["picks"].as_array().unwrap().len(), 10);
+
+    let mut resp = client.get("/keno/3-3/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_lottery_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.get("/lottery/current").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    let draw = resp["status"]["Ok"]["draw"].as_u64().unwrap();
+
+    let mut resp = client.post("/lottery/0/ticket/12-3-7").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -10);
+    assert_eq!(resp["status"]["Ok"]["ticket"]["numbers"][0], 3);
+    assert_eq!(resp["status"]["Ok"]["ticket"]["draw"].as_u64().unwrap(), draw);
+
+    let mut resp = client.post("/lottery/0/ticket/3-7").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/lottery/0/quick_pick").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 200);
+
+    let mut resp = client.get("/lottery/0/tickets").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].as_array().unwrap().len(), 2);
+
+    let mut resp = client.post("/lottery/0/claim").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], 0);
+
+    let mut resp = client.get(format!("/lottery/draws/{}", draw + 1)).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_crash_routes() {
+    let (client, key) = create_client_with_key();
+    // The scheduler isn't running in tests, rounds are advanced by hand
+    let pool = establish_test_connection_pool();
+    Rounds::advance(&pool, &Config::default().crash, crash::unix_millis()).unwrap();
+
+    let mut resp = client.get("/crash/current").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    let round = resp["status"]["Ok"]["round"].as_u64().unwrap();
+    let betting = resp["status"]["Ok"]["phase"] == "Betting";
+    assert_eq!(resp["status"]["Ok"]["commitment"].as_str().unwrap().len(), 64);
+    let crashed = resp["status"]["Ok"]["phase"] == "Crashed";
+    assert_eq!(resp["status"]["Ok"]["seed"].is_null(), !crashed);
+
+    let mut resp = client.get("/crash/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert!(resp["status"]["Ok"].is_null());
+
+    let mut resp = client.post("/crash/0/bet/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    if betting {
+        assert_eq!(resp["status"]["Ok"]["gain"], -100);
+        assert_eq!(resp["status"]["Ok"]["round"].as_u64().unwrap(), round);
+
+        let mut resp = client.post("/crash/0/bet/100").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status_code"], 501);
+
+        let mut resp = client.post("/crash/0/cash_out").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status_code"], 501);
+
+        let mut resp = client.get("/crash/0").header(key.clone()).dispatch();
+        let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+        assert_eq!(resp["status"]["Ok"]["bet"], 100);
+    } else {
+        assert_eq!(resp["status_code"], 501);
+    }
+
+    let mut resp = client.get(format!("/crash/rounds/{}", round)).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["round"].as_u64().unwrap(), round);
+}
+
+#[test]
+fn test_mines_routes() {
+    let (client, key) = create_client_with_key();
+
+    let mut resp = client.get("/mines/paytable/3").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].as_array().unwrap().len(), 22);
+
+    let mut resp = client.post("/mines/0/start/25/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.post("/mines/0/start/24/100").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["gain"], -100);
+
+    let mut resp = client.post("/mines/0/cash_out").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/mines/0").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["mines"], 24);
+
+    // With 24 mines the first reveal either hits one or clears the grid, both end the game
+    let mut resp = client.post("/mines/0/reveal/12").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert!(resp["status"]["Ok"]["game"].is_null());
+    assert_eq!(resp["status"]["Ok"]["mines"].as_array().unwrap().len(), 24);
+    let gain = if resp["status"]["Ok"]["mine"] == true { 0 } else { 2475 };
+    assert_eq!(resp["status"]["Ok"]["gain"], gain);
+
+    let mut resp = client.post("/mines/0/reveal/12").header(key.clone()).dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
+
+#[test]
+fn test_plinko_routes() {
+    let client = create_client(false);
+
+    let mut resp = client.get("/plinko/paytable/16/high").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"].as_array().unwrap().len(), 17);
+
+    let mut resp = client.get("/plinko/12/low/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status"]["Ok"]["path"].as_array().unwrap().len(), 12);
+
+    let mut resp = client.get("/plinko/12/extreme/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+
+    let mut resp = client.get("/plinko/40/low/100").dispatch();
+    let resp = serde_json::from_str::<Value>(&resp.body_string().unwrap()).unwrap();
+    assert_eq!(resp["status_code"], 501);
+}
@@ -0,0 +1,64 @@
+use auth::ApiClient;
+use games::video_poker::{parse_held, PaytableResponse, VideoPoker, VideoPokerError,
+                         VideoPokerResponse, VideoPokerStatusResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/paytable", rank = 1)]
+fn paytable(config: State<Config>) -> Json<PaytableResponse> {
+    Json(PaytableResponse::new(&config.video_poker))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>", rank = 2)]
+fn hand(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<VideoPokerStatusResponse> {
+    Json(VideoPokerStatusResponse::new(VideoPoker::restore(
+        &db_pool,
+        client.namespace,
+        user,
+        &config.video_poker,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/deal/<bet>")]
+fn deal(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    bet: u64,
+) -> Json<VideoPokerResponse> {
+    Json(VideoPokerResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(VideoPokerError::InvalidBet)
+            .and_then(|_| {
+                VideoPoker::deal(&db_pool, client.namespace, user, bet, &config.video_poker)
+            }),
+    ))
+}
+
+/// Draws every card not held, `held` looks like 11001
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/draw/<held>")]
+fn draw(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    held: String,
+) -> Json<VideoPokerResponse> {
+    Json(VideoPokerResponse::new(parse_held(&held).and_then(|held| {
+        VideoPoker::draw(&db_pool, client.namespace, user, &held, &config.video_poker)
+    })))
+}
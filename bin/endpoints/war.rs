@@ -0,0 +1,61 @@
+use auth::ApiClient;
+use games::war::{War, WarError, WarResponse, WarStatusResponse};
+use games_microservice::config::Config;
+use rocket::State;
+use rocket_contrib::Json;
+use ConnectionPool;
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[get("/<user>")]
+fn tie(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<WarStatusResponse> {
+    Json(WarStatusResponse::new(War::restore(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/deal/<bet>")]
+fn deal(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+    bet: u64,
+) -> Json<WarResponse> {
+    Json(WarResponse::new(
+        config
+            .bets
+            .check(bet)
+            .map_err(WarError::InvalidBet)
+            .and_then(|_| War::deal(&db_pool, client.namespace, user, bet, &config.war)),
+    ))
+}
+
+/// Matches the bet of the tie
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/war")]
+fn war(
+    db_pool: State<ConnectionPool>,
+    config: State<Config>,
+    client: ApiClient,
+    user: u64,
+) -> Json<WarResponse> {
+    Json(WarResponse::new(War::war(
+        &db_pool,
+        client.namespace,
+        user,
+        &config.war,
+    )))
+}
+
+#[cfg_attr(feature = "cargo-clippy", allow(needless_pass_by_value))]
+#[post("/<user>/surrender")]
+fn surrender(db_pool: State<ConnectionPool>, client: ApiClient, user: u64) -> Json<WarResponse> {
+    Json(WarResponse::new(War::surrender(
+        &db_pool,
+        client.namespace,
+        user,
+    )))
+}
@@ -9,22 +9,74 @@ extern crate rocket;
 #[cfg(feature = "web")]
 extern crate rocket_contrib;
 
-#[cfg(test)]
 #[macro_use]
 extern crate serde_derive;
 #[cfg(test)]
 extern crate serde_json;
 
+mod auth;
 mod endpoints;
+mod metrics;
+mod rate_limit;
 
-use games_microservice::{establish_connection_pool, games, ConnectionPool};
+use games_microservice::{connect_lazily, games, try_run_migrations, ConnectionPool};
+#[cfg(test)]
+use games_microservice::establish_test_connection_pool;
+use games_microservice::config::Config;
+use games_microservice::rate_limit::RateLimiter;
+use metrics::RequestMetrics;
+use rate_limit::RateLimit;
 use rocket::Rocket;
+use std::cmp;
+use std::process;
+use std::thread;
+use std::time::Duration;
+
+/// Longest wait between attempts to reach the DB on startup
+const MAX_BACKOFF_SECS: u64 = 30;
+
+/// Retries the migrations in the background with exponential backoff until they succeed,
+/// then starts the games running on their own
+/// Readiness reports the DB down in the meantime
+fn migrate_with_backoff(pool: ConnectionPool, config: &Config) {
+    let (lottery, crash) = (config.lottery, config.crash);
+
+    thread::spawn(move || {
+        let mut backoff = 1;
+        while let Err(why) = try_run_migrations(&pool) {
+            eprintln!("Failed to set up the DB: {}, retrying in {}s", why, backoff);
+            thread::sleep(Duration::from_secs(backoff));
+            backoff = cmp::min(backoff * 2, MAX_BACKOFF_SECS);
+        }
+
+        // Detached, draws are settled and rounds run for as long as the server runs
+        games::lottery::schedule(pool.clone(), lottery);
+        games::crash::schedule(pool, crash);
+    });
+}
 
 #[cfg(feature = "web")]
-pub fn create_rocket() -> Rocket {
-    endpoints::router(rocket::ignite().manage(establish_connection_pool()))
+pub fn create_rocket(config: Config) -> Rocket {
+    let pool = connect_lazily(&config.database);
+    let limiter = RateLimiter::new(config.rate_limits.clone());
+    migrate_with_backoff(pool.clone(), &config);
+
+    endpoints::router(rocket::ignite().manage(pool).manage(config))
+        .attach(RequestMetrics)
+        .attach(RateLimit(limiter))
 }
 
 fn main() {
-    create_rocket().launch();
+    games_microservice::load_dotenv();
+
+    // Config errors won't fix themselves, unlike an unreachable DB
+    let config = match Config::load() {
+        Ok(config) => config,
+        Err(why) => {
+            eprintln!("{}", why);
+            process::exit(1);
+        }
+    };
+
+    create_rocket(config).launch();
 }
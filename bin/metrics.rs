@@ -0,0 +1,50 @@
+use games_microservice::metrics::METRICS;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Header;
+use rocket::{Data, Request, Response};
+use std::time::Duration;
+
+/// Internal header carrying the time a request came in
+const REQUEST_START_HEADER: &str = "X-GameService-Request-Start";
+
+/// Counts and times requests per route
+pub struct RequestMetrics;
+
+impl Fairing for RequestMetrics {
+    fn info(&self) -> Info {
+        Info {
+            name: "Request Metrics",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    fn on_request(&self, request: &mut Request, _: &Data) {
+        request.replace_header(Header::new(
+            REQUEST_START_HEADER,
+            METRICS.clock().to_string(),
+        ));
+    }
+
+    fn on_response(&self, request: &Request, response: &mut Response) {
+        let started = request
+            .headers()
+            .get_one(REQUEST_START_HEADER)
+            .and_then(|start| start.parse::<u64>().ok())
+            .unwrap_or_else(|| METRICS.clock());
+        let nanos = METRICS.clock().saturating_sub(started);
+        let elapsed = Duration::new(nanos / 1_000_000_000, (nanos % 1_000_000_000) as u32);
+
+        // Label by route rather than path, so user ids don't blow up the label count
+        let route = request
+            .route()
+            .map(|route| route.uri.path().to_owned())
+            .unwrap_or_else(|| String::from("unmatched"));
+
+        METRICS.record_request(
+            request.method().as_str(),
+            &route,
+            response.status().code,
+            elapsed,
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use auth::ApiClient;
+use games_microservice::rate_limit::{route_user, RateLimiter};
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::Method;
+use rocket::{Data, Outcome, Request};
+
+/// Route rate limited requests are rewritten to, see `endpoints::rate_limited`
+pub const RATE_LIMITED_ROUTE: &str = "/rate_limited";
+
+/// Limits requests per route group, by API key and by user id
+///
+/// Rocket fairings can't answer a request themselves,
+/// so limited requests are rerouted to `RATE_LIMITED_ROUTE` before they reach a game.
+pub struct RateLimit(pub RateLimiter);
+
+impl Fairing for RateLimit {
+    fn info(&self) -> Info {
+        Info {
+            name: "Rate Limiter",
+            kind: Kind::Request,
+        }
+    }
+
+    fn on_request(&self, request: &mut Request, _: &Data) {
+        // Routes look like /<group>/..., only some of them take a user next
+        let (group, user) = {
+            let segments: Vec<&str> = request.uri().segments().collect();
+            (
+                segments.get(0).cloned().unwrap_or_default().to_owned(),
+                route_user(&segments),
+            )
+        };
+
+        // Only verified keys get their own buckets, anything else is limited by address,
+        // so made up keys can't be used to get fresh buckets
+        let client = match request.guard::<ApiClient>() {
+            Outcome::Success(client) => format!("key:{}", client.namespace),
+            _ => request
+                .remote()
+                .map(|addr| addr.ip().to_string())
+                .unwrap_or_default(),
+        };
+
+        if let Err(retry_after) = self.0.check(&group, &client, user) {
+            request.set_method(Method::Get);
+            request.set_uri(format!("{}/{}", RATE_LIMITED_ROUTE, retry_after));
+        }
+    }
+}
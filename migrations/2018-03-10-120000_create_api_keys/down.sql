@@ -0,0 +1,5 @@
+ALTER TABLE BlackJack DROP CONSTRAINT blackjack_pkey;
+DELETE FROM BlackJack WHERE namespace <> 0;
+ALTER TABLE BlackJack DROP COLUMN namespace;
+ALTER TABLE BlackJack ADD CONSTRAINT unq_ord_no PRIMARY KEY (id);
+DROP TABLE api_keys;
@@ -0,0 +1,13 @@
+CREATE TABLE api_keys (
+	id             BIGSERIAL PRIMARY KEY,
+	name           TEXT    NOT NULL UNIQUE,
+	-- sha256 of the raw key, the raw key is only shown once on creation
+	key_hash       TEXT    NOT NULL UNIQUE,
+	revoked        BOOLEAN NOT NULL DEFAULT FALSE
+);
+
+-- Sessions created before API keys existed live in namespace 0
+ALTER TABLE BlackJack ADD COLUMN namespace BIGINT NOT NULL DEFAULT 0;
+-- The original primary key on id took the name unq_ord_no
+ALTER TABLE BlackJack DROP CONSTRAINT unq_ord_no;
+ALTER TABLE BlackJack ADD PRIMARY KEY (namespace, id);
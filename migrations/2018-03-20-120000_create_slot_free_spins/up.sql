@@ -0,0 +1,10 @@
+-- Free spins won on the multi-line slot machine, played at the bet of the winning spin
+CREATE TABLE slot_free_spins (
+	namespace      BIGINT           NOT NULL,
+	id             BIGINT           NOT NULL,
+	remaining      INTEGER          NOT NULL,
+	line_bet       BIGINT           NOT NULL,
+	lines          INTEGER          NOT NULL,
+	multiplier     DOUBLE PRECISION NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
@@ -0,0 +1,5 @@
+-- Progressive jackpot pools, the row is created by the first wager
+CREATE TABLE jackpot (
+	name           TEXT   NOT NULL PRIMARY KEY,
+	amount         BIGINT NOT NULL
+);
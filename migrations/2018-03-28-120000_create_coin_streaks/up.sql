@@ -0,0 +1,9 @@
+-- Double or nothing coin toss streaks, removed once lost or cashed out
+CREATE TABLE coin_streaks (
+	namespace      BIGINT  NOT NULL,
+	id             BIGINT  NOT NULL,
+	bet            BIGINT  NOT NULL,
+	pot            BIGINT  NOT NULL,
+	wins           INTEGER NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
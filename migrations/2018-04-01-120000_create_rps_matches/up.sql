@@ -0,0 +1,12 @@
+-- Best of N rock paper scissors matches, removed once settled
+CREATE TABLE rps_matches (
+	namespace       BIGINT  NOT NULL,
+	id              BIGINT  NOT NULL,
+	bet             BIGINT  NOT NULL,
+	best_of         INTEGER NOT NULL,
+	player_score    INTEGER NOT NULL,
+	computer_score  INTEGER NOT NULL,
+	player_throws   TEXT[]  NOT NULL,
+	computer_throws TEXT[]  NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
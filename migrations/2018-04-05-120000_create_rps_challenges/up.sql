@@ -0,0 +1,15 @@
+-- Player vs player rock paper scissors, kept once settled
+CREATE TABLE rps_challenges (
+	id               BIGSERIAL PRIMARY KEY,
+	namespace        BIGINT  NOT NULL,
+	challenger       BIGINT  NOT NULL,
+	opponent         BIGINT  NOT NULL,
+	stake            BIGINT  NOT NULL,
+	variant          TEXT    NOT NULL,
+	commitment       TEXT    NOT NULL,
+	opponent_throw   TEXT,
+	challenger_throw TEXT,
+	settled          BOOLEAN NOT NULL DEFAULT FALSE,
+	-- Unix time, the accept deadline until accepted, then the reveal deadline
+	expires_at       BIGINT  NOT NULL
+);
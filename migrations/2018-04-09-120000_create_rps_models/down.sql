@@ -0,0 +1 @@
+DROP TABLE rps_models;
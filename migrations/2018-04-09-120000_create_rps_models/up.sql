@@ -0,0 +1,12 @@
+-- Throw history of each user per RPS variant, used by the adaptive opponents
+CREATE TABLE rps_models (
+	namespace      BIGINT    NOT NULL,
+	id             BIGINT    NOT NULL,
+	variant        TEXT      NOT NULL,
+	counts         INTEGER[] NOT NULL,
+	-- Flattened weapons x weapons matrix
+	transitions    INTEGER[] NOT NULL,
+	last_throw     INTEGER,
+	throws         INTEGER   NOT NULL,
+	PRIMARY KEY (namespace, id, variant)
+);
@@ -0,0 +1,9 @@
+-- Video poker hands waiting for the draw, removed once drawn
+CREATE TABLE video_poker (
+	namespace      BIGINT  NOT NULL,
+	id             BIGINT  NOT NULL,
+	bet            BIGINT  NOT NULL,
+	hand           TEXT[]  NOT NULL,
+	deck           TEXT[]  NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
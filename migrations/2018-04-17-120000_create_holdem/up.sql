@@ -0,0 +1,11 @@
+-- Casino Hold'em hands waiting for a call or fold, removed once played
+CREATE TABLE holdem (
+	namespace      BIGINT  NOT NULL,
+	id             BIGINT  NOT NULL,
+	ante           BIGINT  NOT NULL,
+	player_cards   TEXT[]  NOT NULL,
+	dealer_cards   TEXT[]  NOT NULL,
+	board          TEXT[]  NOT NULL,
+	deck           TEXT[]  NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
@@ -0,0 +1,10 @@
+-- Every roulette spin, per API key and table, for the hot and cold numbers
+CREATE TABLE roulette_spins (
+	id             BIGSERIAL PRIMARY KEY,
+	namespace      BIGINT  NOT NULL,
+	table_name     TEXT    NOT NULL,
+	-- 0 to 36, 37 for 00
+	pocket         INT     NOT NULL
+);
+
+CREATE INDEX roulette_spins_table ON roulette_spins (namespace, table_name, id);
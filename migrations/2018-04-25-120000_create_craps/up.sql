@@ -0,0 +1,9 @@
+-- Craps tables between rolls, one per user
+CREATE TABLE craps (
+	namespace      BIGINT  NOT NULL,
+	id             BIGINT  NOT NULL,
+	point          INT,
+	bets           TEXT[]  NOT NULL,
+	version        INT     NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
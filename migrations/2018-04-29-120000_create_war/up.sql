@@ -0,0 +1,10 @@
+-- Casino War ties waiting to go to war or surrender, removed once played
+CREATE TABLE war (
+	namespace      BIGINT  NOT NULL,
+	id             BIGINT  NOT NULL,
+	bet            BIGINT  NOT NULL,
+	player_card    TEXT    NOT NULL,
+	dealer_card    TEXT    NOT NULL,
+	shoe           TEXT[]  NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
@@ -0,0 +1 @@
+DROP TABLE hi_lo;
@@ -0,0 +1,11 @@
+-- Hi-Lo streaks, removed once lost or cashed out
+CREATE TABLE hi_lo (
+	namespace      BIGINT            NOT NULL,
+	id             BIGINT            NOT NULL,
+	bet            BIGINT            NOT NULL,
+	multiplier     DOUBLE PRECISION  NOT NULL,
+	card           TEXT              NOT NULL,
+	deck           TEXT[]            NOT NULL,
+	guesses        INT               NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
@@ -0,0 +1,2 @@
+DROP TABLE lottery_draws;
+DROP TABLE lottery_tickets;
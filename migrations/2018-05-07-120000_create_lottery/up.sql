@@ -0,0 +1,26 @@
+-- Lottery tickets, won is set once their draw is settled
+CREATE TABLE lottery_tickets (
+	id             BIGSERIAL  PRIMARY KEY,
+	draw           BIGINT     NOT NULL,
+	namespace      BIGINT     NOT NULL,
+	user_id        BIGINT     NOT NULL,
+	numbers        INT[]      NOT NULL,
+	price          BIGINT     NOT NULL,
+	won            BIGINT,
+	claimed        BOOLEAN    NOT NULL  DEFAULT FALSE
+);
+
+CREATE INDEX lottery_tickets_draw ON lottery_tickets (draw);
+CREATE INDEX lottery_tickets_user ON lottery_tickets (namespace, user_id);
+
+-- Settled draws, one row per draw that had tickets
+CREATE TABLE lottery_draws (
+	id             BIGINT     PRIMARY KEY,
+	numbers        INT[]      NOT NULL,
+	tickets        INT        NOT NULL,
+	pool           BIGINT     NOT NULL,
+	winners        INT        NOT NULL,
+	payout         BIGINT     NOT NULL,
+	rollover       BIGINT     NOT NULL,
+	drawn_at       BIGINT     NOT NULL
+);
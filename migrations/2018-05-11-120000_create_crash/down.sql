@@ -0,0 +1,2 @@
+DROP TABLE crash_bets;
+DROP TABLE crash_rounds;
@@ -0,0 +1,22 @@
+-- Crash rounds, scheduled back to back by the server
+CREATE TABLE crash_rounds (
+	id             BIGINT            PRIMARY KEY,
+	seed           TEXT              NOT NULL,
+	commitment     TEXT              NOT NULL,
+	crash_point    DOUBLE PRECISION  NOT NULL,
+	starts_at      BIGINT            NOT NULL,
+	crashes_at     BIGINT            NOT NULL,
+	settled        BOOLEAN           NOT NULL  DEFAULT FALSE
+);
+
+-- One bet per user and round, cashed_out is set by a cash out
+CREATE TABLE crash_bets (
+	round_id       BIGINT            NOT NULL,
+	namespace      BIGINT            NOT NULL,
+	user_id        BIGINT            NOT NULL,
+	bet            BIGINT            NOT NULL,
+	cashed_out     DOUBLE PRECISION,
+	PRIMARY KEY (round_id, namespace, user_id)
+);
+
+CREATE INDEX crash_bets_user ON crash_bets (namespace, user_id, round_id);
@@ -0,0 +1,9 @@
+-- Mines games, removed once a mine is hit or they are cashed out
+CREATE TABLE mines (
+	namespace      BIGINT  NOT NULL,
+	id             BIGINT  NOT NULL,
+	bet            BIGINT  NOT NULL,
+	mine_tiles     INT[]   NOT NULL,
+	revealed       INT[]   NOT NULL,
+	PRIMARY KEY (namespace, id)
+);
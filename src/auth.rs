@@ -0,0 +1,132 @@
+use diesel;
+use diesel::prelude::*;
+use diesel::result::Error as DieselResultError;
+use models::NewApiKey;
+use r2d2::Error as R2d2Error;
+use rand::{thread_rng, Rng};
+use sha2::{Digest, Sha256};
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub use models::ApiKey;
+
+/// Length of a raw API key in bytes (before hex encoding)
+const KEY_LEN: usize = 32;
+
+#[derive(Debug)]
+pub enum AuthError {
+    DieselResult(DieselResultError),
+    KeyRevoked,
+    /// The server has no connection pool to check keys against
+    NoPool,
+    R2d2(R2d2Error),
+    UnknownKey,
+}
+
+impl Display for AuthError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.description())
+    }
+}
+
+impl StdError for AuthError {
+    fn description(&self) -> &str {
+        use self::AuthError::*;
+        match *self {
+            DieselResult(ref inner) => inner.description(),
+            KeyRevoked => "API key has been revoked",
+            NoPool => "API keys can't be checked without a database",
+            R2d2(ref inner) => inner.description(),
+            UnknownKey => "Unknown API key",
+        }
+    }
+}
+
+impl From<DieselResultError> for AuthError {
+    fn from(err: DieselResultError) -> Self {
+        AuthError::DieselResult(err)
+    }
+}
+
+impl From<R2d2Error> for AuthError {
+    fn from(err: R2d2Error) -> Self {
+        AuthError::R2d2(err)
+    }
+}
+
+/// Hashes a raw key the same way it is stored in the DB
+pub fn hash_key(raw_key: &str) -> String {
+    let digest = Sha256::digest(raw_key.as_bytes());
+    c![format!("{:02x}", byte), for byte in digest.iter()].concat()
+}
+
+/// Generates a new random key, hex encoded
+fn generate_key() -> String {
+    let mut rng = thread_rng();
+    (0..KEY_LEN).map(|_| format!("{:02x}", rng.gen::<u8>())).collect()
+}
+
+impl ApiKey {
+    /// Creates a new key called `key_name`
+    /// Returns the stored key and the raw key, which is not recoverable afterwards
+    pub fn create(conn: &PgConnection, key_name: &str) -> Result<(Self, String), AuthError> {
+        use schema::api_keys;
+
+        let raw_key = generate_key();
+        let hashed = hash_key(&raw_key);
+
+        let key: Self = diesel::insert_into(api_keys::table)
+            .values(&NewApiKey {
+                name: key_name,
+                key_hash: &hashed,
+            })
+            .get_result(conn)?;
+
+        Ok((key, raw_key))
+    }
+
+    /// Looks up the key matching `raw_key`
+    pub fn verify(conn: &PgConnection, raw_key: &str) -> Result<Self, AuthError> {
+        use schema::api_keys::dsl::*;
+
+        let key = api_keys
+            .filter(key_hash.eq(hash_key(raw_key)))
+            .first::<Self>(conn)
+            .optional()?
+            .ok_or(AuthError::UnknownKey)?;
+
+        if key.revoked {
+            return Err(AuthError::KeyRevoked);
+        }
+
+        Ok(key)
+    }
+
+    /// Lists every key, including revoked ones
+    pub fn list(conn: &PgConnection) -> Result<Vec<Self>, AuthError> {
+        use schema::api_keys::dsl::*;
+
+        Ok(api_keys.order(id.asc()).load::<Self>(conn)?)
+    }
+
+    /// Revokes the key called `key_name`
+    /// Its sessions are kept, so the namespace can't be reused by a new key
+    pub fn revoke(conn: &PgConnection, key_name: &str) -> Result<(), AuthError> {
+        use schema::api_keys::dsl::*;
+
+        let updated = diesel::update(api_keys.filter(name.eq(key_name)))
+            .set(revoked.eq(true))
+            .execute(conn)?;
+
+        if updated == 0 {
+            return Err(AuthError::UnknownKey);
+        }
+
+        Ok(())
+    }
+
+    /// Namespace that scopes this client's user ids
+    pub fn namespace(&self) -> i64 {
+        self.id
+    }
+}
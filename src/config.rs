@@ -0,0 +1,328 @@
+use games::baccarat::Rules as BaccaratRules;
+use games::coin_toss::Rules as CoinTossRules;
+use games::crash::Rules as CrashRules;
+use games::craps::Rules as CrapsRules;
+use games::dice::{OverUnderRules, SicBoRules};
+use games::hi_lo::Rules as HiLoRules;
+use games::holdem::Rules as HoldemRules;
+use games::keno::Rules as KenoRules;
+use games::lottery::Rules as LotteryRules;
+use games::mines::Rules as MinesRules;
+use games::plinko::Rules as PlinkoRules;
+use games::roulette::Rules as RouletteRules;
+use games::rps::Rules as RpsRules;
+use games::slot_machine::{Combination, SlotConfig};
+use games::video_poker::Paytable as VideoPokerPaytable;
+use games::war::Rules as WarRules;
+use jackpot::JackpotConfig;
+use rate_limit::{GroupLimits, QuotaParseError, ROUTE_GROUPS};
+use std::collections::HashMap;
+use std::env;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+use std::str::FromStr;
+use toml;
+
+/// Config file used when `GAMESERVICE_CONFIG` isn't set, it is optional
+pub const DEFAULT_CONFIG_PATH: &str = "GameService.toml";
+
+cfg_if! {
+    if #[cfg(test)] {
+        const DATABASE_URL_VAR: &str = "GAMESERVICE_TEST_DATABASE_URL";
+    } else {
+        const DATABASE_URL_VAR: &str = "GAMESERVICE_DATABASE_URL";
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(String, io::Error),
+    Parse(String, toml::de::Error),
+    InvalidEnv(&'static str, String),
+    InvalidQuota(String, QuotaParseError),
+    /// Every problem found while validating
+    Invalid(Vec<String>),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::ConfigError::*;
+        match *self {
+            Io(ref path, ref inner) => write!(f, "Failed to read {}: {}", path, inner),
+            Parse(ref path, ref inner) => write!(f, "Failed to parse {}: {}", path, inner),
+            InvalidEnv(var, ref value) => write!(f, "{} has an invalid value: {:?}", var, value),
+            InvalidQuota(ref var, ref inner) => write!(f, "{}: {}", var, inner),
+            Invalid(ref problems) => write!(f, "Invalid config: {}", problems.join(", ")),
+        }
+    }
+}
+
+impl StdError for ConfigError {
+    fn description(&self) -> &str {
+        use self::ConfigError::*;
+        match *self {
+            Io(_, _) => "Failed to read the config file",
+            Parse(_, _) => "Failed to parse the config file",
+            InvalidEnv(_, _) => "Invalid environment variable",
+            InvalidQuota(_, ref inner) => inner.description(),
+            Invalid(_) => "Invalid config",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    pub url: String,
+    /// None uses r2d2's default
+    pub pool_size: Option<u32>,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            pool_size: None,
+        }
+    }
+}
+
+/// Bets outside of `min..=max` are refused
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BetLimits {
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Default for BetLimits {
+    fn default() -> Self {
+        Self {
+            min: 0,
+            max: u64::from(u32::max_value()),
+        }
+    }
+}
+
+impl BetLimits {
+    pub fn check(&self, bet: u64) -> Result<(), String> {
+        if bet < self.min || bet > self.max {
+            Err(format!(
+                "Bet must be between {} and {}",
+                self.min, self.max
+            ))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Config {
+    pub database: DatabaseConfig,
+    pub bets: BetLimits,
+    pub coin_toss: CoinTossRules,
+    pub rps: RpsRules,
+    pub slot_machine: SlotConfig,
+    pub video_poker: VideoPokerPaytable,
+    pub holdem: HoldemRules,
+    pub baccarat: BaccaratRules,
+    pub roulette: RouletteRules,
+    pub craps: CrapsRules,
+    pub sic_bo: SicBoRules,
+    pub over_under: OverUnderRules,
+    pub war: WarRules,
+    pub hi_lo: HiLoRules,
+    pub keno: KenoRules,
+    pub lottery: LotteryRules,
+    pub crash: CrashRules,
+    pub mines: MinesRules,
+    pub plinko: PlinkoRules,
+    pub jackpot: JackpotConfig,
+    /// Keyed by route group, see `rate_limit::ROUTE_GROUPS`
+    pub rate_limits: HashMap<String, GroupLimits>,
+}
+
+fn env_override<T: FromStr>(var: &'static str, field: &mut T) -> Result<(), ConfigError> {
+    if let Ok(value) = env::var(var) {
+        *field = value
+            .parse()
+            .map_err(|_| ConfigError::InvalidEnv(var, value.clone()))?;
+    }
+    Ok(())
+}
+
+impl Config {
+    /// Loads the config file at `GAMESERVICE_CONFIG` (or `DEFAULT_CONFIG_PATH` if present),
+    /// applies environment overrides and validates the result
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match env::var("GAMESERVICE_CONFIG") {
+            Ok(path) => Self::from_file(&path)?,
+            Err(_) if Path::new(DEFAULT_CONFIG_PATH).exists() => {
+                Self::from_file(DEFAULT_CONFIG_PATH)?
+            }
+            Err(_) => Self::default(),
+        };
+
+        config.apply_env()?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    pub fn from_file(path: &str) -> Result<Self, ConfigError> {
+        let mut contents = String::new();
+        File::open(path)
+            .and_then(|mut file| file.read_to_string(&mut contents))
+            .map_err(|e| ConfigError::Io(path.to_owned(), e))?;
+
+        Self::from_toml(&contents).map_err(|e| ConfigError::Parse(path.to_owned(), e))
+    }
+
+    /// Parses a config without applying environment overrides
+    pub fn from_toml(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    /// Environment variables take precedence over the config file
+    /// Route groups missing from the config get the default limits
+    pub fn apply_env(&mut self) -> Result<(), ConfigError> {
+        if let Ok(url) = env::var(DATABASE_URL_VAR) {
+            self.database.url = url;
+        }
+
+        if let Ok(size) = env::var("MAX_POOL") {
+            let size = size.parse()
+                .map_err(|_| ConfigError::InvalidEnv("MAX_POOL", size.clone()))?;
+            self.database.pool_size = Some(size);
+        }
+
+        env_override("GAMESERVICE_MIN_BET", &mut self.bets.min)?;
+        env_override("GAMESERVICE_MAX_BET", &mut self.bets.max)?;
+
+        for group in &ROUTE_GROUPS {
+            let prefix = format!("GAMESERVICE_RATE_LIMIT_{}", group.to_uppercase());
+            let limits = self.rate_limits
+                .entry(group.to_string())
+                .or_insert_with(GroupLimits::default);
+
+            for &(suffix, is_user) in &[("KEY", false), ("USER", true)] {
+                let var = format!("{}_{}", prefix, suffix);
+                if let Ok(quota) = env::var(&var) {
+                    let quota = quota
+                        .parse()
+                        .map_err(|e| ConfigError::InvalidQuota(var.clone(), e))?;
+                    if is_user {
+                        limits.per_user = quota;
+                    } else {
+                        limits.per_key = quota;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Checks every value, reporting all problems at once
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        if cfg!(feature = "auto_save") && self.database.url.is_empty() {
+            problems.push(format!(
+                "database.url must be set (or {})",
+                DATABASE_URL_VAR
+            ));
+        }
+        if self.database.pool_size == Some(0) {
+            problems.push(String::from("database.pool_size must be above 0"));
+        }
+        if self.bets.min > self.bets.max {
+            problems.push(format!(
+                "bets.min ({}) is above bets.max ({})",
+                self.bets.min, self.bets.max
+            ));
+        }
+        if let Err(coin_toss_problems) = self.coin_toss.validate() {
+            problems.extend(coin_toss_problems);
+        }
+        if let Err(rps_problems) = self.rps.validate() {
+            problems.extend(rps_problems);
+        }
+        if let Err(holdem_problems) = self.holdem.validate() {
+            problems.extend(holdem_problems);
+        }
+        if let Err(baccarat_problems) = self.baccarat.validate() {
+            problems.extend(baccarat_problems);
+        }
+        if let Err(roulette_problems) = self.roulette.validate() {
+            problems.extend(roulette_problems);
+        }
+        if let Err(craps_problems) = self.craps.validate() {
+            problems.extend(craps_problems);
+        }
+        if let Err(sic_bo_problems) = self.sic_bo.validate() {
+            problems.extend(sic_bo_problems);
+        }
+        if let Err(over_under_problems) = self.over_under.validate() {
+            problems.extend(over_under_problems);
+        }
+        if let Err(war_problems) = self.war.validate() {
+            problems.extend(war_problems);
+        }
+        if let Err(hi_lo_problems) = self.hi_lo.validate() {
+            problems.extend(hi_lo_problems);
+        }
+        if let Err(keno_problems) = self.keno.validate() {
+            problems.extend(keno_problems);
+        }
+        if let Err(lottery_problems) = self.lottery.validate() {
+            problems.extend(lottery_problems);
+        }
+        if let Err(crash_problems) = self.crash.validate() {
+            problems.extend(crash_problems);
+        }
+        if let Err(mines_problems) = self.mines.validate() {
+            problems.extend(mines_problems);
+        }
+        if let Err(plinko_problems) = self.plinko.validate() {
+            problems.extend(plinko_problems);
+        }
+        if let Err(slot_problems) = self.slot_machine.validate() {
+            problems.extend(slot_problems);
+        }
+        if let Err(jackpot_problems) = self.jackpot.validate() {
+            problems.extend(jackpot_problems);
+        }
+        let jackpot_symbols = match self.jackpot.combination {
+            Combination::OfAKind(n) => n,
+            Combination::Symbols(ref symbols) => symbols.len(),
+        };
+        if jackpot_symbols == 0 || jackpot_symbols > self.slot_machine.reels.len() {
+            problems.push(format!(
+                "jackpot.combination needs between 1 and {} symbols",
+                self.slot_machine.reels.len()
+            ));
+        }
+        for (group, limits) in &self.rate_limits {
+            for quota in &[limits.per_key, limits.per_user] {
+                if quota.requests == 0 || quota.seconds == 0 {
+                    problems.push(format!(
+                        "rate_limits.{} requests and seconds must be above 0",
+                        group
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Invalid(problems))
+        }
+    }
+}
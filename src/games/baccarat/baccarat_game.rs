@@ -0,0 +1,337 @@
+use games::poker::{rank_of, ACE};
+use games::{checked_sum, net_gain, StandardCard, StandardDeck};
+use metrics::{payout, METRICS};
+use super::BaccaratError;
+
+/// Punto Banco rules
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Decks in the shoe
+    pub decks: usize,
+    /// Kept from banker wins, 0.05 pays 0.95 to 1
+    pub banker_commission: f64,
+    /// Gain on a tie as a multiple of the bet, player and banker bets push on a tie
+    pub tie_pays: u64,
+    /// Gain on a pair side bet as a multiple of the bet
+    pub pair_pays: u64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            decks: 8,
+            banker_commission: 0.05,
+            tie_pays: 8,
+            pair_pays: 11,
+        }
+    }
+}
+
+impl Rules {
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.decks == 0 {
+            problems.push(String::from("baccarat.decks must be above 0"));
+        }
+        if self.banker_commission < 0.0 || self.banker_commission >= 1.0 {
+            problems.push(String::from(
+                "baccarat.banker_commission must be at least 0 and below 1",
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Baccarat value of a card: aces are 1, tens and faces 0
+pub fn value_of(card: &StandardCard) -> u8 {
+    match rank_of(card) {
+        ACE => 1,
+        rank if rank >= 10 => 0,
+        rank => rank,
+    }
+}
+
+fn total(cards: &[StandardCard]) -> u8 {
+    cards.iter().map(value_of).sum::<u8>() % 10
+}
+
+/// Whether the banker draws on `banker` once the player drew `third`, and the rule deciding it
+fn banker_draws(banker: u8, third: u8) -> (bool, String) {
+    // Player third cards the banker draws against, by banker total
+    let (low, high) = match banker {
+        4 => (2, 7),
+        5 => (4, 7),
+        6 => (6, 7),
+        _ => (0, 9),
+    };
+
+    if banker <= 2 {
+        (true, String::from("Banker draws on 0-2"))
+    } else if banker == 3 && third == 8 {
+        (
+            false,
+            String::from("Banker stands on 3, the player's third card is 8"),
+        )
+    } else if banker == 3 {
+        (
+            true,
+            String::from("Banker draws on 3 unless the player's third card is 8"),
+        )
+    } else if banker >= 7 {
+        (false, String::from("Banker stands on 7"))
+    } else if third >= low && third <= high {
+        (
+            true,
+            format!(
+                "Banker draws on {}, the player's third card is {}-{}",
+                banker, low, high
+            ),
+        )
+    } else {
+        (
+            false,
+            format!(
+                "Banker stands on {}, the player's third card isn't {}-{}",
+                banker, low, high
+            ),
+        )
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Winner {
+    Player,
+    Banker,
+    Tie,
+}
+
+/// A hand and the tableau rule that decided whether it drew a third card
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BaccaratHand {
+    pub cards: Vec<String>,
+    pub total: u8,
+    pub rule: String,
+    /// First 2 cards of the same rank
+    pub pair: bool,
+}
+
+impl BaccaratHand {
+    fn new(cards: &[StandardCard], rule: String) -> Self {
+        Self {
+            cards: c![card.to_string(), for card in cards],
+            total: total(cards),
+            rule,
+            pair: rank_of(&cards[0]) == rank_of(&cards[1]),
+        }
+    }
+}
+
+/// Both hands of one round
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Coup {
+    pub player: BaccaratHand,
+    pub banker: BaccaratHand,
+    pub winner: Winner,
+}
+
+/// Deals a round from `shoe` by the third card tableau
+pub fn deal(shoe: &mut StandardDeck) -> Result<Coup, BaccaratError> {
+    let (p1, b1, p2, b2) = (shoe.draw()?, shoe.draw()?, shoe.draw()?, shoe.draw()?);
+    let mut player = vec![p1, p2];
+    let mut banker = vec![b1, b2];
+    let (player_total, banker_total) = (total(&player), total(&banker));
+
+    let (player_rule, banker_rule) = if player_total >= 8 || banker_total >= 8 {
+        let natural = |hand_total: u8, name: &str| {
+            if hand_total >= 8 {
+                format!("{} has a natural {}", name, hand_total)
+            } else {
+                format!("{} stands, a natural was dealt", name)
+            }
+        };
+        (
+            natural(player_total, "Player"),
+            natural(banker_total, "Banker"),
+        )
+    } else if player_total <= 5 {
+        let third = shoe.draw()?;
+        player.push(third);
+        let (draws, banker_rule) = banker_draws(banker_total, value_of(&third));
+        if draws {
+            banker.push(shoe.draw()?);
+        }
+        (String::from("Player draws on 0-5"), banker_rule)
+    } else if banker_total <= 5 {
+        banker.push(shoe.draw()?);
+        (
+            String::from("Player stands on 6-7"),
+            String::from("Banker draws on 0-5 when the player stands"),
+        )
+    } else {
+        (
+            String::from("Player stands on 6-7"),
+            String::from("Banker stands on 6-7 when the player stands"),
+        )
+    };
+
+    let (player_total, banker_total) = (total(&player), total(&banker));
+    let winner = if player_total > banker_total {
+        Winner::Player
+    } else if banker_total > player_total {
+        Winner::Banker
+    } else {
+        Winner::Tie
+    };
+
+    Ok(Coup {
+        player: BaccaratHand::new(&player, player_rule),
+        banker: BaccaratHand::new(&banker, banker_rule),
+        winner,
+    })
+}
+
+/// Amounts bet on each spot, any of them can be left at 0
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BaccaratBets {
+    pub player: u64,
+    pub banker: u64,
+    pub tie: u64,
+    pub player_pair: u64,
+    pub banker_pair: u64,
+}
+
+impl BaccaratBets {
+    /// `bet` on the spot named `spot`
+    pub fn single(spot: &str, bet: u64) -> Result<Self, BaccaratError> {
+        let mut bets = Self::default();
+        match spot.to_lowercase().as_str() {
+            "player" | "punto" => bets.player = bet,
+            "banker" | "banco" => bets.banker = bet,
+            "tie" => bets.tie = bet,
+            "player_pair" => bets.player_pair = bet,
+            "banker_pair" => bets.banker_pair = bet,
+            _ => return Err(BaccaratError::UnknownBet(spot.to_string())),
+        }
+        Ok(bets)
+    }
+
+    /// Amounts on player, banker, tie, player pair and banker pair
+    pub fn spots(&self) -> [u64; 5] {
+        [
+            self.player,
+            self.banker,
+            self.tie,
+            self.player_pair,
+            self.banker_pair,
+        ]
+    }
+
+    pub fn total(&self) -> Result<u64, BaccaratError> {
+        checked_sum(&self.spots()).ok_or(BaccaratError::BetTooLarge)
+    }
+
+    /// Net gain of every bet on `coup`
+    pub fn gain(&self, coup: &Coup, rules: &Rules) -> Result<i64, BaccaratError> {
+        let pays = |bet: u64, pays: u64| bet.checked_mul(pays).ok_or(BaccaratError::BetTooLarge);
+        // Won and lost by each spot, player and banker bets push on a tie
+        let main = match coup.winner {
+            Winner::Player => [(self.player, 0), (0, self.banker), (0, self.tie)],
+            Winner::Banker => [
+                (
+                    (self.banker as f64 * (1.0 - rules.banker_commission)) as u64,
+                    0,
+                ),
+                (0, self.player),
+                (0, self.tie),
+            ],
+            Winner::Tie => [(pays(self.tie, rules.tie_pays)?, 0), (0, 0), (0, 0)],
+        };
+        let pairs = [
+            if coup.player.pair {
+                (pays(self.player_pair, rules.pair_pays)?, 0)
+            } else {
+                (0, self.player_pair)
+            },
+            if coup.banker.pair {
+                (pays(self.banker_pair, rules.pair_pays)?, 0)
+            } else {
+                (0, self.banker_pair)
+            },
+        ];
+
+        let won: Vec<u64> = main.iter().chain(pairs.iter()).map(|&(won, _)| won).collect();
+        let lost: Vec<u64> = main.iter().chain(pairs.iter()).map(|&(_, lost)| lost).collect();
+        match (checked_sum(&won), checked_sum(&lost)) {
+            (Some(won), Some(lost)) => net_gain(won, lost).ok_or(BaccaratError::BetTooLarge),
+            _ => Err(BaccaratError::BetTooLarge),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BaccaratGame {
+    pub coup: Coup,
+    pub bets: BaccaratBets,
+    /// Everything bet
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Plays `bets` on a round dealt from `shoe`
+pub fn play(
+    bets: BaccaratBets,
+    shoe: &mut StandardDeck,
+    rules: &Rules,
+) -> Result<BaccaratGame, BaccaratError> {
+    let bet = bets.total()?;
+    if bet == 0 {
+        return Err(BaccaratError::NoBet);
+    }
+
+    let coup = deal(shoe)?;
+    let gain = bets.gain(&coup, rules)?;
+
+    Ok(BaccaratGame {
+        coup,
+        bets,
+        bet,
+        gain,
+    })
+}
+
+/// Plays `bets` on a round dealt from a fresh shoe
+pub fn baccarat(bets: BaccaratBets, rules: &Rules) -> Result<BaccaratGame, BaccaratError> {
+    let game = play(bets, &mut StandardDeck::shoe(rules.decks), rules)?;
+    METRICS.record_game("baccarat", game.bet, payout(game.bet, game.gain));
+    Ok(game)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BaccaratResponse {
+    pub status_code: u16,
+    pub status: Result<BaccaratGame, String>,
+}
+
+impl BaccaratResponse {
+    pub fn new(result: Result<BaccaratGame, BaccaratError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use games::StandardDeckError;
+
+#[derive(Debug)]
+pub enum BaccaratError {
+    BetTooLarge,
+    InvalidBet(String),
+    NoBet,
+    NoCard,
+    UnknownBet(String),
+}
+
+impl Display for BaccaratError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            BaccaratError::InvalidBet(ref why) => f.write_str(why),
+            BaccaratError::UnknownBet(ref bet) => write!(
+                f,
+                "Unknown bet {}, bet on player/banker/tie/player_pair/banker_pair",
+                bet
+            ),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for BaccaratError {
+    fn description(&self) -> &str {
+        use self::BaccaratError::*;
+        match *self {
+            BetTooLarge => "Bet is too large",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            NoBet => "Nothing was bet",
+            NoCard => "No card was able to be drawn",
+            UnknownBet(_) => "Unknown bet",
+        }
+    }
+}
+
+impl From<StandardDeckError> for BaccaratError {
+    fn from(_: StandardDeckError) -> Self {
+        BaccaratError::NoCard
+    }
+}
+
+impl BaccaratError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::BaccaratError::*;
+        match *self {
+            BetTooLarge | InvalidBet(_) | NoBet | UnknownBet(_) => 501,
+            NoCard => 500,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+mod baccarat_game;
+mod error;
+
+pub use self::baccarat_game::{baccarat, deal, play, value_of, BaccaratBets, BaccaratGame,
+                              BaccaratHand, BaccaratResponse, Coup, Rules, Winner};
+pub use self::error::BaccaratError;
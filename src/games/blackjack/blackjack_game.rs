@@ -8,6 +8,7 @@ use diesel::result::Error as DieselResultError;
 use diesel;
 #[cfg(feature = "auto_save")]
 use r2d2::Error as R2d2Error;
+use metrics::{payout, METRICS};
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use super::{CardParseError, Deck, DeckError, Hand};
@@ -39,6 +40,7 @@ pub enum BlackJackError {
     #[cfg(feature = "auto_save")]
     DieselResult(DieselResultError),
     GameOver,
+    InvalidBet,
     InvalidResultCount(usize),
     NoCard,
     PlayerAlreadyLost,
@@ -69,6 +71,7 @@ impl StdError for BlackJackError {
             #[cfg(feature = "auto_save")]
             DieselResult(ref inner) => inner.description(),
             GameOver => "The game is over",
+            InvalidBet => "Bet is outside of the allowed limits",
             InvalidResultCount(_) => "More than or less than 1 game result found",
             NoCard => "No card was able to be drawn",
             PlayerAlreadyLost => "You already lost",
@@ -122,6 +125,7 @@ impl BlackJackError {
             PlayerAlreadyLost => 501,
             PlayerAlreadyPressedStay => 500,
             GameOver => 501,
+            InvalidBet => 501,
             InvalidResultCount(_) => 500,
             PlayerAlreadyWon => 501,
             PlayerNotDoneYet => 501,
@@ -142,6 +146,9 @@ pub struct BlackJack {
     pub player: Hand,
     #[cfg(feature = "auto_save")]
     pub player_id: u64,
+    // Namespace of the API key that owns player_id
+    #[cfg(feature = "auto_save")]
+    pub namespace: i64,
     pub dealer: Hand,
     deck: Deck,
     pub bet: u64,
@@ -159,6 +166,7 @@ pub struct BlackJack {
 impl BlackJack {
     #[cfg(feature = "auto_save")]
     pub fn new(
+        player_namespace: i64,
         player_id: u64,
         new_bet: u64,
         db_pool: ConnectionPool,
@@ -169,6 +177,7 @@ impl BlackJack {
         // TODO: Make this safer (low)
         let conn = db_pool.get().unwrap();
         let num: i64 = blackjack
+            .filter(namespace.eq(player_namespace))
             .filter(id.eq(player_id as i64))
             .count()
             .get_result(&*conn)
@@ -188,6 +197,7 @@ impl BlackJack {
         dealer.add_card(new_deck.draw()?);
 
         let num: i64 = blackjack
+            .filter(namespace.eq(player_namespace))
             .filter(id.eq(player_id as i64))
             .count()
             .get_result(&*conn)
@@ -205,6 +215,7 @@ impl BlackJack {
                 player_hand: player.export().1,
                 player_stay: false,
                 status: None,
+                namespace: player_namespace,
             };
 
             let _: Session = diesel::insert_into(blackjack_schema::table)
@@ -215,6 +226,7 @@ impl BlackJack {
 
         Ok(Self {
             player_id: player_id,
+            namespace: player_namespace,
             player: player,
             dealer: dealer,
             deck: new_deck,
@@ -248,13 +260,39 @@ impl BlackJack {
         })
     }
 
+    /// Counts sessions whose game isn't over yet
+    /// across every namespace when `player_namespace` is None
+    #[cfg(feature = "auto_save")]
+    pub fn active_sessions(
+        db_pool: &ConnectionPool,
+        player_namespace: Option<i64>,
+    ) -> Result<u64, BlackJackError> {
+        use schema::blackjack::dsl::*;
+
+        let conn = db_pool.get()?;
+        let mut query = blackjack.filter(status.is_null()).into_boxed();
+
+        if let Some(player_namespace) = player_namespace {
+            query = query.filter(namespace.eq(player_namespace));
+        }
+
+        let count: i64 = query.count().get_result(&*conn)?;
+
+        Ok(count as u64)
+    }
+
     #[cfg(feature = "auto_save")]
-    pub fn restore(db_pool: &ConnectionPool, player: u64) -> Result<Self, BlackJackError> {
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Self, BlackJackError> {
         use schema::blackjack::dsl::*;
 
         // TODO: Make this safer (low)
         let conn = db_pool.get()?;
         let results = blackjack
+            .filter(namespace.eq(player_namespace))
             .filter(id.eq(player as i64))
             .limit(1)
             .load::<Session>(&*conn)
@@ -285,6 +323,7 @@ impl BlackJack {
                 cards: c![card.parse()?, for card in &session.player_hand],
             },
             player_id: session.id as u64,
+            namespace: session.namespace,
             dealer: Hand {
                 cards: c![card.parse()?, for card in &session.dealer_hand],
             },
@@ -424,6 +463,7 @@ impl BlackJack {
             player_hand: self.player.export().1,
             player_stay: self.player_stay_status,
             status: game_status,
+            namespace: self.namespace,
         };
 
         let _: Session = sess.save_changes(&*conn)?;
@@ -440,7 +480,11 @@ impl BlackJack {
 
         let conn = self.db_pool.get()?;
 
-        diesel::delete(blackjack.filter(id.eq(self.player_id as i64))).execute(&*conn)?;
+        diesel::delete(
+            blackjack
+                .filter(namespace.eq(self.namespace))
+                .filter(id.eq(self.player_id as i64)),
+        ).execute(&*conn)?;
 
         Ok(())
     }
@@ -455,6 +499,7 @@ impl BlackJack {
                     self.claimed = true;
                 }
                 self.gain = -(self.bet as i64);
+                METRICS.record_game("blackjack", self.bet, payout(self.bet, self.gain));
 
                 Ok(self.gain)
             }
@@ -464,6 +509,7 @@ impl BlackJack {
                     self.claimed = true;
                 }
                 self.gain = self.bet as i64;
+                METRICS.record_game("blackjack", self.bet, payout(self.bet, self.gain));
 
                 Ok(self.gain)
             }
@@ -1,5 +1,6 @@
 use regex::Regex;
 use std::char::ParseCharError;
+use std::cmp::Ordering;
 use std::error::Error as StdError;
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::str::FromStr;
@@ -152,6 +153,16 @@ impl StandardCard {
     pub fn face_as_string(&self) -> String {
         self.face().to_string()
     }
+
+    /// Rank of the card's face, see `StandardCardFace::rank`
+    pub fn rank(&self) -> u8 {
+        self.face().rank()
+    }
+
+    /// Orders cards by rank alone, cards of different suits can be equal
+    pub fn cmp_rank(&self, other: &StandardCard) -> Ordering {
+        self.face().cmp(&other.face())
+    }
 }
 
 impl StandardCardFace {
@@ -160,6 +171,49 @@ impl StandardCardFace {
     pub fn value(&self) -> u8 {
         self.clone().into()
     }
+
+    /// Rank of the face, 2 through 14 with aces high
+    /// Jokers are 0, below every other face
+    pub fn rank(&self) -> u8 {
+        use self::StandardCardFace::*;
+        match *self {
+            Two => 2,
+            Three => 3,
+            Four => 4,
+            Five => 5,
+            Six => 6,
+            Seven => 7,
+            Eight => 8,
+            Nine => 9,
+            Ten => 10,
+            Jack => 11,
+            Queen => 12,
+            King => 13,
+            Ace => 14,
+            Joker => 0,
+        }
+    }
+}
+
+/// Faces are ordered by rank, aces high
+impl PartialEq for StandardCardFace {
+    fn eq(&self, other: &Self) -> bool {
+        self.rank() == other.rank()
+    }
+}
+
+impl Eq for StandardCardFace {}
+
+impl PartialOrd for StandardCardFace {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for StandardCardFace {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
 }
 
 impl From<StandardCard> for StandardCardFace {
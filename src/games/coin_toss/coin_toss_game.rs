@@ -0,0 +1,178 @@
+use metrics::{payout, METRICS};
+use rand::{thread_rng, Rng};
+use std::str::FromStr;
+use super::CoinTossError;
+
+/// Configurable coin toss rules
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Chance of a coin landing heads
+    pub heads_chance: f64,
+    /// Kept from every payout, 0.02 pays 98% of the fair odds
+    pub house_edge: f64,
+    /// Most coins tossed in one bet
+    pub max_coins: u32,
+    /// Streaks have to be cashed out after this many wins
+    pub max_streak: u32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            heads_chance: 0.5,
+            house_edge: 0.02,
+            max_coins: 10,
+            max_streak: 10,
+        }
+    }
+}
+
+impl Rules {
+    /// Chance of a coin landing on `side`
+    pub fn chance(&self, side: &Coin) -> f64 {
+        match *side {
+            Coin::Heads => self.heads_chance,
+            Coin::Tails => 1.0 - self.heads_chance,
+        }
+    }
+
+    /// Returned amount, as a multiple of the bet, for an outcome with `probability`
+    pub fn pays(&self, probability: f64) -> f64 {
+        (1.0 - self.house_edge) / probability
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.heads_chance <= 0.0 || self.heads_chance >= 1.0 {
+            problems.push(String::from(
+                "coin_toss.heads_chance must be above 0 and below 1",
+            ));
+        }
+        if self.house_edge < 0.0 || self.house_edge >= 1.0 {
+            problems.push(String::from(
+                "coin_toss.house_edge must be at least 0 and below 1",
+            ));
+        }
+        if self.max_coins == 0 || self.max_coins > 64 {
+            problems.push(String::from("coin_toss.max_coins must be between 1 and 64"));
+        }
+        if self.max_streak == 0 {
+            problems.push(String::from("coin_toss.max_streak must be above 0"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, PartialOrd, Ord, Serialize)]
+pub enum Coin {
+    Heads,
+    Tails,
+}
+
+impl Coin {
+    /// Flips a fair coin, returning a Coin with its current Side up
+    pub fn flip() -> Coin {
+        Self::flip_with_chance(&mut thread_rng(), 0.5)
+    }
+
+    /// Flips a coin landing heads with `heads_chance`
+    pub fn flip_with_chance<R: Rng>(rng: &mut R, heads_chance: f64) -> Coin {
+        if rng.next_f64() < heads_chance {
+            Coin::Heads
+        } else {
+            Coin::Tails
+        }
+    }
+}
+
+impl FromStr for Coin {
+    type Err = CoinTossError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match &s.to_lowercase()[..] {
+            "heads" | "h" => Ok(Coin::Heads),
+            "tails" | "t" => Ok(Coin::Tails),
+            _ => Err(CoinTossError::InvalidSide),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CoinTossResult {
+    player: Coin,
+    computer: Coin,
+    bet: u64,
+    gain: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CoinTossResponse {
+    status_code: u16,
+    status: Result<CoinTossResult, String>,
+}
+
+impl CoinTossResponse {
+    pub fn win(bet: u64, gain: i64, side: Coin, guess: Coin) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(CoinTossResult {
+                player: guess,
+                computer: side,
+                bet,
+                gain,
+            }),
+        }
+    }
+
+    pub fn lose(bet: u64, side: Coin, guess: Coin) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(CoinTossResult {
+                player: guess,
+                computer: side,
+                bet,
+                gain: -(bet as i64),
+            }),
+        }
+    }
+
+    pub fn err(err: String) -> Self {
+        Self {
+            status_code: 501,
+            status: Err(err),
+        }
+    }
+}
+
+/// Guess a coin side
+pub fn guess_side(bet: u64, side: &str) -> CoinTossResponse {
+    guess_side_with_rules(bet, side, &Rules::default())
+}
+
+/// Guess a coin side, paying out by `rules`
+pub fn guess_side_with_rules(bet: u64, side: &str, rules: &Rules) -> CoinTossResponse {
+    let guessed_side: Coin = match side.parse() {
+        Ok(side) => side,
+        Err(err) => return CoinTossResponse::err(err.to_string()),
+    };
+
+    let side = Coin::flip_with_chance(&mut thread_rng(), rules.heads_chance);
+
+    if guessed_side == side {
+        let pays = rules.pays(rules.chance(&guessed_side));
+        let gain = (bet as f64 * (pays - 1.0)) as i64;
+        METRICS.record_game("coin_toss", bet, payout(bet, gain));
+        CoinTossResponse::win(bet, gain, side, guessed_side)
+    } else {
+        METRICS.record_game("coin_toss", bet, 0);
+        CoinTossResponse::lose(bet, side, guessed_side)
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum CoinTossError {
+    /// Someone else changed the streak during this request
+    Conflict,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    InvalidBet(String),
+    /// Most coins that can be tossed at once
+    InvalidCoins(u32),
+    /// Coins tossed
+    InvalidHeads(u32),
+    InvalidSide,
+    NoStreak,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+    StreakInProgress,
+    /// Longest streak allowed
+    StreakLimit(u32),
+}
+
+impl Display for CoinTossError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::CoinTossError::*;
+        match *self {
+            InvalidBet(ref why) => f.write_str(why),
+            InvalidCoins(max) => write!(f, "Coins must be between 1 and {}", max),
+            InvalidHeads(coins) => write!(f, "Heads must be between 0 and {}", coins),
+            StreakLimit(max) => write!(f, "Streaks stop at {} wins, please cash out", max),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for CoinTossError {
+    fn description(&self) -> &str {
+        use self::CoinTossError::*;
+        match *self {
+            Conflict => "The streak changed during the request, please retry",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidCoins(_) => "Invalid number of coins",
+            InvalidHeads(_) => "Invalid number of heads",
+            InvalidSide => "Not a valid side, heads/tails.",
+            NoStreak => "No streak in progress",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+            StreakInProgress => "A streak is already in progress, double it or cash out",
+            StreakLimit(_) => "Streak limit reached",
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for CoinTossError {
+    fn from(err: DieselResultError) -> Self {
+        CoinTossError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for CoinTossError {
+    fn from(err: R2d2Error) -> Self {
+        CoinTossError::R2d2(err)
+    }
+}
+
+impl CoinTossError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::CoinTossError::*;
+        match *self {
+            Conflict => 409,
+            InvalidBet(_) | InvalidCoins(_) | InvalidHeads(_) | InvalidSide | NoStreak
+            | StreakInProgress | StreakLimit(_) => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
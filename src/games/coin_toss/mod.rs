@@ -0,0 +1,13 @@
+mod coin_toss_game;
+mod error;
+mod multi;
+#[cfg(feature = "auto_save")]
+mod streak;
+
+pub use self::coin_toss_game::{guess_side, guess_side_with_rules, Coin, CoinTossResponse,
+                               CoinTossResult, Rules};
+pub use self::error::CoinTossError;
+pub use self::multi::{paytable, toss_coins, CoinBet, MultiCoinResponse, MultiCoinResult,
+                      PaytableEntry, PaytableResponse};
+#[cfg(feature = "auto_save")]
+pub use self::streak::{Streak, StreakResponse, StreakResult, StreakStatusResponse};
@@ -0,0 +1,164 @@
+use metrics::{payout, METRICS};
+use rand::thread_rng;
+use super::{Coin, CoinTossError, Rules};
+
+/// What a multi-coin bet is on
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum CoinBet {
+    /// Exactly this many heads
+    Heads(u32),
+    /// Every coin on the same side
+    AllSame,
+}
+
+/// Ways of picking `k` of `n`
+fn binomial(n: u32, k: u32) -> f64 {
+    (0..k).fold(1.0, |ways, i| ways * f64::from(n - i) / f64::from(i + 1))
+}
+
+impl CoinBet {
+    /// True chance of winning with `coins` coins
+    pub fn probability(&self, coins: u32, heads_chance: f64) -> f64 {
+        let tails_chance = 1.0 - heads_chance;
+        match *self {
+            CoinBet::Heads(heads) if heads <= coins => {
+                binomial(coins, heads) * heads_chance.powi(heads as i32)
+                    * tails_chance.powi((coins - heads) as i32)
+            }
+            CoinBet::Heads(_) => 0.0,
+            CoinBet::AllSame => {
+                heads_chance.powi(coins as i32) + tails_chance.powi(coins as i32)
+            }
+        }
+    }
+
+    pub fn wins(&self, tossed: &[Coin]) -> bool {
+        match *self {
+            CoinBet::Heads(heads) => count_heads(tossed) == heads,
+            CoinBet::AllSame => tossed.windows(2).all(|pair| pair[0] == pair[1]),
+        }
+    }
+
+    fn check(&self, coins: u32, rules: &Rules) -> Result<(), CoinTossError> {
+        if coins == 0 || coins > rules.max_coins {
+            return Err(CoinTossError::InvalidCoins(rules.max_coins));
+        }
+        match *self {
+            CoinBet::Heads(heads) if heads > coins => Err(CoinTossError::InvalidHeads(coins)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn count_heads(tossed: &[Coin]) -> u32 {
+    tossed.iter().filter(|coin| **coin == Coin::Heads).count() as u32
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableEntry {
+    pub bet: CoinBet,
+    pub probability: f64,
+    /// Returned amount as a multiple of the bet
+    pub pays: f64,
+}
+
+/// Every bet on `coins` coins, paying the true odds minus the house edge
+pub fn paytable(coins: u32, rules: &Rules) -> Result<Vec<PaytableEntry>, CoinTossError> {
+    CoinBet::AllSame.check(coins, rules)?;
+
+    let bets = (0..coins + 1)
+        .map(CoinBet::Heads)
+        .chain(Some(CoinBet::AllSame));
+
+    Ok(c![PaytableEntry {
+        bet,
+        probability: bet.probability(coins, rules.heads_chance),
+        pays: rules.pays(bet.probability(coins, rules.heads_chance)),
+    }, for bet in bets])
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MultiCoinResult {
+    pub coins: Vec<Coin>,
+    pub heads: u32,
+    pub bet_on: CoinBet,
+    /// Returned amount on a win as a multiple of the bet
+    pub pays: f64,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Tosses `coins` coins, `bet` is on `on`
+pub fn toss_coins(
+    bet: u64,
+    coins: u32,
+    on: CoinBet,
+    rules: &Rules,
+) -> Result<MultiCoinResult, CoinTossError> {
+    on.check(coins, rules)?;
+
+    let mut rng = thread_rng();
+    let tossed: Vec<Coin> = (0..coins)
+        .map(|_| Coin::flip_with_chance(&mut rng, rules.heads_chance))
+        .collect();
+    let pays = rules.pays(on.probability(coins, rules.heads_chance));
+
+    let gain = if on.wins(&tossed) {
+        (bet as f64 * pays) as i64 - bet as i64
+    } else {
+        -(bet as i64)
+    };
+
+    METRICS.record_game("coin_toss", bet, payout(bet, gain));
+
+    Ok(MultiCoinResult {
+        heads: count_heads(&tossed),
+        coins: tossed,
+        bet_on: on,
+        pays,
+        bet,
+        gain,
+    })
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MultiCoinResponse {
+    pub status_code: u16,
+    pub status: Result<MultiCoinResult, String>,
+}
+
+impl MultiCoinResponse {
+    pub fn new(result: Result<MultiCoinResult, CoinTossError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<PaytableEntry>, String>,
+}
+
+impl PaytableResponse {
+    pub fn new(coins: u32, rules: &Rules) -> Self {
+        match paytable(coins, rules) {
+            Ok(entries) => Self {
+                status_code: 200,
+                status: Ok(entries),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use diesel;
+use diesel::prelude::*;
+use metrics::METRICS;
+use models::CoinStreakSession;
+use rand::thread_rng;
+use super::{Coin, CoinTossError, Rules};
+use ConnectionPool;
+
+/// A double or nothing streak, stored between requests
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Streak {
+    /// Amount that started the streak
+    pub bet: u64,
+    /// Paid on cash out, doubled (minus the house edge) by every win
+    pub pot: u64,
+    pub wins: u32,
+}
+
+impl From<CoinStreakSession> for Streak {
+    fn from(session: CoinStreakSession) -> Self {
+        Self {
+            bet: session.bet as u64,
+            pot: session.pot as u64,
+            wins: session.wins as u32,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StreakResult {
+    /// None when cashing out
+    pub guess: Option<Coin>,
+    pub side: Option<Coin>,
+    /// The streak after this request, None once it is lost or cashed out
+    pub streak: Option<Streak>,
+    /// Amount taken by this request, only starting a streak takes a bet
+    pub bet: u64,
+    pub gain: i64,
+}
+
+fn flip(guess: Coin, pot: u64, rules: &Rules) -> (Coin, Option<u64>) {
+    let side = Coin::flip_with_chance(&mut thread_rng(), rules.heads_chance);
+
+    if side == guess {
+        (side, Some((pot as f64 * rules.pays(rules.chance(&guess))) as u64))
+    } else {
+        (side, None)
+    }
+}
+
+impl Streak {
+    /// Streak of `player`, if they have one going
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Option<Self>, CoinTossError> {
+        use schema::coin_streaks::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = coin_streaks
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<CoinStreakSession>(&*conn)
+            .optional()?;
+
+        Ok(session.map(Self::from))
+    }
+
+    /// Wagers `new_bet` on `guess`, a win starts a streak
+    pub fn start(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        guess: Coin,
+        new_bet: u64,
+        rules: &Rules,
+    ) -> Result<StreakResult, CoinTossError> {
+        use schema::coin_streaks;
+        use schema::coin_streaks::dsl::*;
+
+        let conn = db_pool.get()?;
+        let count: i64 = coin_streaks
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .count()
+            .get_result(&*conn)?;
+
+        if count != 0 {
+            return Err(CoinTossError::StreakInProgress);
+        }
+
+        let (side, won) = flip(guess, new_bet, rules);
+        let streak = match won {
+            Some(new_pot) => {
+                let session = CoinStreakSession {
+                    namespace: player_namespace,
+                    id: player as i64,
+                    bet: new_bet as i64,
+                    pot: new_pot as i64,
+                    wins: 1,
+                };
+                // Another start may have won since the count above
+                let inserted = diesel::insert_into(coin_streaks::table)
+                    .values(&session)
+                    .on_conflict_do_nothing()
+                    .execute(&*conn)?;
+                if inserted != 1 {
+                    return Err(CoinTossError::StreakInProgress);
+                }
+
+                Some(Self::from(session))
+            }
+            None => {
+                METRICS.record_game("coin_toss_streak", new_bet, 0);
+                None
+            }
+        };
+
+        Ok(StreakResult {
+            guess: Some(guess),
+            side: Some(side),
+            streak,
+            bet: new_bet,
+            gain: -(new_bet as i64),
+        })
+    }
+
+    /// Double or nothing on the pot of `player`'s streak
+    pub fn double(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        guess: Coin,
+        rules: &Rules,
+    ) -> Result<StreakResult, CoinTossError> {
+        use schema::coin_streaks::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = coin_streaks
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<CoinStreakSession>(&*conn)
+            .optional()?
+            .ok_or(CoinTossError::NoStreak)?;
+
+        if session.wins as u32 >= rules.max_streak {
+            return Err(CoinTossError::StreakLimit(rules.max_streak));
+        }
+
+        // Only applied if nobody else played the streak since it was loaded
+        let unchanged = coin_streaks
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .filter(wins.eq(session.wins));
+
+        let (side, won) = flip(guess, session.pot as u64, rules);
+        let updated = match won {
+            Some(new_pot) => diesel::update(unchanged)
+                .set((pot.eq(new_pot as i64), wins.eq(session.wins + 1)))
+                .execute(&*conn)?,
+            None => diesel::delete(unchanged).execute(&*conn)?,
+        };
+
+        if updated != 1 {
+            return Err(CoinTossError::Conflict);
+        }
+
+        let streak = match won {
+            Some(new_pot) => Some(Self {
+                bet: session.bet as u64,
+                pot: new_pot,
+                wins: session.wins as u32 + 1,
+            }),
+            None => {
+                METRICS.record_game("coin_toss_streak", session.bet as u64, 0);
+                None
+            }
+        };
+
+        Ok(StreakResult {
+            guess: Some(guess),
+            side: Some(side),
+            streak,
+            bet: 0,
+            gain: 0,
+        })
+    }
+
+    /// Ends `player`'s streak, paying its pot
+    pub fn cash_out(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<StreakResult, CoinTossError> {
+        use schema::coin_streaks::dsl::*;
+
+        let conn = db_pool.get()?;
+        // Deleting and reading in one statement keeps a streak from being paid twice
+        let session = diesel::delete(
+            coin_streaks
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64)),
+        ).get_result::<CoinStreakSession>(&*conn)
+            .optional()?
+            .ok_or(CoinTossError::NoStreak)?;
+
+        METRICS.record_game("coin_toss_streak", session.bet as u64, session.pot as u64);
+
+        Ok(StreakResult {
+            guess: None,
+            side: None,
+            streak: None,
+            bet: 0,
+            gain: session.pot,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StreakResponse {
+    pub status_code: u16,
+    pub status: Result<StreakResult, String>,
+}
+
+impl StreakResponse {
+    pub fn new(result: Result<StreakResult, CoinTossError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct StreakStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when there is no streak going
+    pub status: Result<Option<Streak>, String>,
+}
+
+impl StreakStatusResponse {
+    pub fn new(result: Result<Option<Streak>, CoinTossError>) -> Self {
+        match result {
+            Ok(streak) => Self {
+                status_code: 200,
+                status: Ok(streak),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
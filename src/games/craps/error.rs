@@ -0,0 +1,100 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum CrapsError {
+    Conflict,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    InvalidBet(String),
+    /// Bets stored in a format that can't be read
+    InvalidStoredBet(String),
+    /// Place bets are on 4, 5, 6, 8, 9 or 10
+    InvalidNumber(u8),
+    NoBets,
+    /// No bet to take odds on
+    NoOddsBet,
+    /// Come bets and odds wait for a point
+    NoPoint,
+    /// Most odds allowed on the bet
+    OddsLimit(u64),
+    /// Line bets are made on the come-out roll
+    PointEstablished,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+    UnknownBet(String),
+}
+
+impl Display for CrapsError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            CrapsError::InvalidBet(ref why) => f.write_str(why),
+            CrapsError::InvalidStoredBet(ref bet) => write!(f, "Stored bet {} is invalid", bet),
+            CrapsError::InvalidNumber(number) => write!(
+                f,
+                "{} can't be placed, place bets are on 4, 5, 6, 8, 9 or 10",
+                number
+            ),
+            CrapsError::OddsLimit(max) => write!(f, "At most {} can be taken in odds", max),
+            CrapsError::UnknownBet(ref bet) => write!(
+                f,
+                "Unknown bet {}, bet on pass/dont_pass/come/dont_come",
+                bet
+            ),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for CrapsError {
+    fn description(&self) -> &str {
+        use self::CrapsError::*;
+        match *self {
+            Conflict => "The table changed during the request, please retry",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidStoredBet(_) => "Stored bet is invalid",
+            InvalidNumber(_) => "Invalid place number",
+            NoBets => "No bets are on the table",
+            NoOddsBet => "There is no bet with a point to take odds on",
+            NoPoint => "No point is established yet, bet on pass or don't pass",
+            OddsLimit(_) => "Odds are over the limit",
+            PointEstablished => "The point is established, bet on come or don't come",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+            UnknownBet(_) => "Unknown bet",
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for CrapsError {
+    fn from(err: DieselResultError) -> Self {
+        CrapsError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for CrapsError {
+    fn from(err: R2d2Error) -> Self {
+        CrapsError::R2d2(err)
+    }
+}
+
+impl CrapsError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::CrapsError::*;
+        match *self {
+            Conflict => 409,
+            InvalidBet(_) | InvalidNumber(_) | NoBets | NoOddsBet | NoPoint | OddsLimit(_)
+            | PointEstablished | UnknownBet(_) => 501,
+            _ => 500,
+        }
+    }
+}
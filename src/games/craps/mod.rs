@@ -0,0 +1,10 @@
+mod error;
+#[cfg(feature = "auto_save")]
+mod session;
+mod table;
+
+pub use self::error::CrapsError;
+#[cfg(feature = "auto_save")]
+pub use self::session::{CrapsSession, CrapsStatusResponse, RollResponse, WagerResponse};
+pub use self::table::{roll_dice, BetKind, CrapsBet, CrapsTable, Outcome, ResolvedBet, Roll,
+                      Rules, Wager, POINTS};
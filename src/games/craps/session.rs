@@ -0,0 +1,214 @@
+use diesel;
+use diesel::prelude::*;
+use metrics::METRICS;
+use models::CrapsTableSession;
+use super::{roll_dice, BetKind, CrapsBet, CrapsError, CrapsTable, Roll, Rules, Wager};
+use {ConnectionPool, PgConnection};
+
+/// Craps tables are stored per user between rolls, like blackjack hands
+pub struct CrapsSession;
+
+impl CrapsSession {
+    /// Table of `player` and the version it was stored at, None if never stored
+    fn load(
+        conn: &PgConnection,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<(CrapsTable, Option<i32>), CrapsError> {
+        use schema::craps::dsl::*;
+
+        let session = craps
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<CrapsTableSession>(conn)
+            .optional()?;
+
+        match session {
+            Some(session) => {
+                let mut stored = Vec::with_capacity(session.bets.len());
+                for bet in &session.bets {
+                    stored.push(bet.parse::<CrapsBet>()?);
+                }
+                Ok((
+                    CrapsTable {
+                        point: session.point.map(|stored_point| stored_point as u8),
+                        bets: stored,
+                    },
+                    Some(session.version),
+                ))
+            }
+            None => Ok((CrapsTable::new(), None)),
+        }
+    }
+
+    /// Stores `table`, only if nothing else stored it since it was loaded at `previous`
+    fn save(
+        conn: &PgConnection,
+        player_namespace: i64,
+        player: u64,
+        table: &CrapsTable,
+        previous: Option<i32>,
+    ) -> Result<(), CrapsError> {
+        use schema::craps;
+        use schema::craps::dsl::*;
+
+        let new_point = table.point.map(i32::from);
+        let new_bets = c![bet.to_string(), for bet in &table.bets];
+        let updated = match previous {
+            None => diesel::insert_into(craps::table)
+                .values(&CrapsTableSession {
+                    namespace: player_namespace,
+                    id: player as i64,
+                    point: new_point,
+                    bets: new_bets,
+                    version: 1,
+                })
+                .on_conflict_do_nothing()
+                .execute(conn)?,
+            Some(previous) => diesel::update(
+                craps
+                    .filter(namespace.eq(player_namespace))
+                    .filter(id.eq(player as i64))
+                    .filter(version.eq(previous)),
+            ).set((
+                point.eq(new_point),
+                bets.eq(new_bets),
+                version.eq(previous + 1),
+            ))
+                .execute(conn)?,
+        };
+
+        if updated == 1 {
+            Ok(())
+        } else {
+            Err(CrapsError::Conflict)
+        }
+    }
+
+    /// Point and bets of `player`'s table
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<CrapsTable, CrapsError> {
+        let conn = db_pool.get()?;
+        Ok(Self::load(&*conn, player_namespace, player)?.0)
+    }
+
+    /// Puts `amount` on a bet of `player`'s table
+    pub fn bet(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        kind: BetKind,
+        number: Option<u8>,
+        amount: u64,
+    ) -> Result<Wager, CrapsError> {
+        let conn = db_pool.get()?;
+        let (mut table, version) = Self::load(&*conn, player_namespace, player)?;
+
+        let wager = table.bet(kind, number, amount)?;
+        Self::save(&*conn, player_namespace, player, &table, version)?;
+
+        Ok(wager)
+    }
+
+    /// Takes `amount` in odds behind a bet of `player`'s table
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn odds(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        kind: BetKind,
+        point: Option<u8>,
+        amount: u64,
+        rules: &Rules,
+    ) -> Result<Wager, CrapsError> {
+        let conn = db_pool.get()?;
+        let (mut table, version) = Self::load(&*conn, player_namespace, player)?;
+
+        let wager = table.odds(kind, point, amount, rules)?;
+        Self::save(&*conn, player_namespace, player, &table, version)?;
+
+        Ok(wager)
+    }
+
+    /// Rolls the dice on `player`'s table
+    pub fn roll(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Roll, CrapsError> {
+        let conn = db_pool.get()?;
+        let (mut table, version) = Self::load(&*conn, player_namespace, player)?;
+
+        let roll = table.roll(roll_dice())?;
+        Self::save(&*conn, player_namespace, player, &table, version)?;
+        METRICS.record_game("craps", roll.settled, roll.gain as u64);
+
+        Ok(roll)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WagerResponse {
+    pub status_code: u16,
+    pub status: Result<Wager, String>,
+}
+
+impl WagerResponse {
+    pub fn new(result: Result<Wager, CrapsError>) -> Self {
+        match result {
+            Ok(wager) => Self {
+                status_code: 200,
+                status: Ok(wager),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RollResponse {
+    pub status_code: u16,
+    pub status: Result<Roll, String>,
+}
+
+impl RollResponse {
+    pub fn new(result: Result<Roll, CrapsError>) -> Self {
+        match result {
+            Ok(roll) => Self {
+                status_code: 200,
+                status: Ok(roll),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CrapsStatusResponse {
+    pub status_code: u16,
+    pub status: Result<CrapsTable, String>,
+}
+
+impl CrapsStatusResponse {
+    pub fn new(result: Result<CrapsTable, CrapsError>) -> Self {
+        match result {
+            Ok(table) => Self {
+                status_code: 200,
+                status: Ok(table),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use rand::{thread_rng, Rng};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use super::CrapsError;
+
+/// Numbers that can be the point, and the numbers place bets are made on
+pub const POINTS: [u8; 6] = [4, 5, 6, 8, 9, 10];
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Odds can be taken up to this many times the bet they back
+    pub max_odds: u64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self { max_odds: 3 }
+    }
+}
+
+impl Rules {
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        if self.max_odds == 0 || self.max_odds > 100 {
+            Err(vec![String::from("craps.max_odds must be between 1 and 100")])
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BetKind {
+    Pass,
+    DontPass,
+    Come,
+    DontCome,
+    Place,
+}
+
+impl Display for BetKind {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(match *self {
+            BetKind::Pass => "PASS",
+            BetKind::DontPass => "DONT_PASS",
+            BetKind::Come => "COME",
+            BetKind::DontCome => "DONT_COME",
+            BetKind::Place => "PLACE",
+        })
+    }
+}
+
+impl FromStr for BetKind {
+    type Err = CrapsError;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind.to_lowercase().as_str() {
+            "pass" => Ok(BetKind::Pass),
+            "dont_pass" | "dontpass" => Ok(BetKind::DontPass),
+            "come" => Ok(BetKind::Come),
+            "dont_come" | "dontcome" => Ok(BetKind::DontCome),
+            "place" => Ok(BetKind::Place),
+            _ => Err(CrapsError::UnknownBet(kind.to_string())),
+        }
+    }
+}
+
+impl BetKind {
+    /// Pass and come bets win on the point, the don'ts win on a 7
+    pub fn is_do(&self) -> bool {
+        *self == BetKind::Pass || *self == BetKind::Come
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CrapsBet {
+    pub kind: BetKind,
+    /// Point of a line or come bet once it has one, number of a place bet
+    pub number: Option<u8>,
+    pub amount: u64,
+    /// Odds backing the bet once it has a point
+    pub odds: u64,
+}
+
+impl Display for CrapsBet {
+    /// Stored as KIND:NUMBER:AMOUNT:ODDS, 0 for no number
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(
+            f,
+            "{}:{}:{}:{}",
+            self.kind,
+            self.number.unwrap_or(0),
+            self.amount,
+            self.odds
+        )
+    }
+}
+
+impl FromStr for CrapsBet {
+    type Err = CrapsError;
+
+    fn from_str(bet: &str) -> Result<Self, Self::Err> {
+        let invalid = || CrapsError::InvalidStoredBet(bet.to_string());
+        let parts: Vec<&str> = bet.split(':').collect();
+        if parts.len() != 4 {
+            return Err(invalid());
+        }
+
+        let number = parts[1].parse::<u8>().map_err(|_| invalid())?;
+        Ok(Self {
+            kind: parts[0].parse().map_err(|_| invalid())?,
+            number: if number == 0 { None } else { Some(number) },
+            amount: parts[2].parse().map_err(|_| invalid())?,
+            odds: parts[3].parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+/// Odds pay the true odds of the point coming before a 7, returned with the odds
+fn odds_return(kind: BetKind, point: u8, odds: u64) -> u64 {
+    let winnings = match (kind.is_do(), point) {
+        (true, 4) | (true, 10) => odds * 2,
+        (true, 5) | (true, 9) => odds * 3 / 2,
+        (true, _) => odds * 6 / 5,
+        (false, 4) | (false, 10) => odds / 2,
+        (false, 5) | (false, 9) => odds * 2 / 3,
+        (false, _) => odds * 5 / 6,
+    };
+    odds + winnings
+}
+
+/// Winnings of a place bet, the bet itself stays up
+fn place_winnings(number: u8, amount: u64) -> u64 {
+    match number {
+        4 | 10 => amount * 9 / 5,
+        5 | 9 => amount * 7 / 5,
+        _ => amount * 7 / 6,
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Outcome {
+    Win,
+    Lose,
+    Push,
+}
+
+/// A bet the roll decided
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ResolvedBet {
+    pub bet: CrapsBet,
+    pub outcome: Outcome,
+    /// Paid back to the player, stakes included
+    /// Place bets only return their winnings and stay up
+    pub returned: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Roll {
+    pub dice: [u8; 2],
+    pub total: u8,
+    /// True if it was a come-out roll
+    pub come_out: bool,
+    /// Point after the roll, None once it is made or on a seven-out
+    pub point: Option<u8>,
+    pub resolved: Vec<ResolvedBet>,
+    /// Bets still on the table
+    pub bets: Vec<CrapsBet>,
+    /// Stakes of the bets settled by the roll
+    pub settled: u64,
+    /// Bets are taken when they are made, so the roll only pays
+    pub gain: i64,
+}
+
+/// A wager put on the table
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Wager {
+    pub point: Option<u8>,
+    pub bets: Vec<CrapsBet>,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+pub fn roll_dice() -> [u8; 2] {
+    let mut rng = thread_rng();
+    [rng.gen_range(1, 7), rng.gen_range(1, 7)]
+}
+
+/// The state between rolls: the point and every bet still up
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct CrapsTable {
+    pub point: Option<u8>,
+    pub bets: Vec<CrapsBet>,
+}
+
+impl CrapsTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, kind: BetKind, number: Option<u8>, amount: u64) {
+        let existing = self.bets
+            .iter()
+            .position(|bet| bet.kind == kind && bet.number == number);
+
+        match existing {
+            Some(i) => self.bets[i].amount += amount,
+            None => self.bets.push(CrapsBet {
+                kind,
+                number,
+                amount,
+                odds: 0,
+            }),
+        }
+    }
+
+    /// Puts `amount` on a bet, `number` is only used by place bets
+    pub fn bet(
+        &mut self,
+        kind: BetKind,
+        number: Option<u8>,
+        amount: u64,
+    ) -> Result<Wager, CrapsError> {
+        match kind {
+            BetKind::Pass | BetKind::DontPass if self.point.is_some() => {
+                return Err(CrapsError::PointEstablished)
+            }
+            BetKind::Come | BetKind::DontCome if self.point.is_none() => {
+                return Err(CrapsError::NoPoint)
+            }
+            BetKind::Place => match number {
+                Some(number) if POINTS.contains(&number) => self.add(kind, Some(number), amount),
+                _ => return Err(CrapsError::InvalidNumber(number.unwrap_or(0))),
+            },
+            // Line and come bets get their point from a roll
+            _ => self.add(kind, None, amount),
+        }
+
+        Ok(self.wager(amount))
+    }
+
+    /// Backs the line or come bet on `point` with `amount` in odds
+    /// The line bets back the table point, which is used when `point` is None
+    pub fn odds(
+        &mut self,
+        kind: BetKind,
+        point: Option<u8>,
+        amount: u64,
+        rules: &Rules,
+    ) -> Result<Wager, CrapsError> {
+        let point = match kind {
+            BetKind::Pass | BetKind::DontPass => self.point.ok_or(CrapsError::NoPoint)?,
+            BetKind::Come | BetKind::DontCome => point.ok_or(CrapsError::NoOddsBet)?,
+            BetKind::Place => return Err(CrapsError::NoOddsBet),
+        };
+
+        {
+            let bet = self.bets
+                .iter_mut()
+                .find(|bet| bet.kind == kind && bet.number == Some(point))
+                .ok_or(CrapsError::NoOddsBet)?;
+            let max = bet.amount.saturating_mul(rules.max_odds);
+            if bet.odds + amount > max {
+                return Err(CrapsError::OddsLimit(max));
+            }
+            bet.odds += amount;
+        }
+
+        Ok(self.wager(amount))
+    }
+
+    fn wager(&self, amount: u64) -> Wager {
+        Wager {
+            point: self.point,
+            bets: self.bets.clone(),
+            bet: amount,
+            gain: -(amount as i64),
+        }
+    }
+
+    /// Settles the bets `dice` decide and moves the point
+    pub fn roll(&mut self, dice: [u8; 2]) -> Result<Roll, CrapsError> {
+        if self.bets.is_empty() {
+            return Err(CrapsError::NoBets);
+        }
+
+        let total = dice[0] + dice[1];
+        let come_out = self.point.is_none();
+        let mut resolved = Vec::new();
+        let mut remaining = Vec::new();
+        let settle = |bet, outcome, returned| ResolvedBet {
+            bet,
+            outcome,
+            returned,
+        };
+
+        for mut bet in self.bets.drain(..) {
+            match (bet.kind, bet.number) {
+                // Place bets are off on the come-out roll
+                (BetKind::Place, _) if come_out => remaining.push(bet),
+                (BetKind::Place, _) if total == 7 => resolved.push(settle(bet, Outcome::Lose, 0)),
+                (BetKind::Place, Some(number)) if total == number => {
+                    resolved.push(settle(bet, Outcome::Win, place_winnings(number, bet.amount)));
+                    remaining.push(bet);
+                }
+                (BetKind::Place, _) => remaining.push(bet),
+                // A line or come bet waiting for its own come-out
+                (kind, None) => match (kind.is_do(), total) {
+                    (true, 7) | (true, 11) | (false, 2) | (false, 3) => {
+                        resolved.push(settle(bet, Outcome::Win, bet.amount * 2))
+                    }
+                    (false, 12) => resolved.push(settle(bet, Outcome::Push, bet.amount)),
+                    (_, 2) | (_, 3) | (_, 12) | (_, 7) | (_, 11) => {
+                        resolved.push(settle(bet, Outcome::Lose, 0))
+                    }
+                    _ => {
+                        bet.number = Some(total);
+                        remaining.push(bet);
+                    }
+                },
+                (kind, Some(point)) if total == point || total == 7 => {
+                    let won = (total == point) == kind.is_do();
+                    // Come bet odds are off on the come-out roll, they are returned unpaid
+                    let odds_off = kind == BetKind::Come && come_out;
+                    if won {
+                        let odds = if odds_off {
+                            bet.odds
+                        } else {
+                            odds_return(kind, point, bet.odds)
+                        };
+                        resolved.push(settle(bet, Outcome::Win, bet.amount * 2 + odds));
+                    } else if odds_off {
+                        resolved.push(settle(bet, Outcome::Lose, bet.odds));
+                    } else {
+                        resolved.push(settle(bet, Outcome::Lose, 0));
+                    }
+                }
+                _ => remaining.push(bet),
+            }
+        }
+        self.bets = remaining;
+
+        self.point = match self.point {
+            None if POINTS.contains(&total) => Some(total),
+            Some(point) if total == point || total == 7 => None,
+            point => point,
+        };
+
+        // Place bets that won are still up, only the others were settled
+        let settled = resolved
+            .iter()
+            .filter(|resolved| {
+                resolved.bet.kind != BetKind::Place || resolved.outcome == Outcome::Lose
+            })
+            .map(|resolved| resolved.bet.amount + resolved.bet.odds)
+            .sum();
+        let returned = resolved.iter().map(|resolved| resolved.returned).sum::<u64>();
+
+        Ok(Roll {
+            dice,
+            total,
+            come_out,
+            point: self.point,
+            resolved,
+            bets: self.bets.clone(),
+            settled,
+            gain: returned as i64,
+        })
+    }
+}
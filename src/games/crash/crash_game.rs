@@ -0,0 +1,114 @@
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Random bytes of a seed
+const SEED_BYTES: usize = 32;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Bets are taken for this long before a round starts
+    pub betting_secs: u64,
+    /// The multiplier is e^(growth * seconds) while a round runs
+    pub growth: f64,
+    /// Kept from the fair odds, a round crashes at or above x with a chance of (1 - edge) / x
+    pub house_edge: f64,
+    /// Rounds crash at this multiplier at the latest
+    pub max_multiplier: f64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            betting_secs: 10,
+            growth: 0.06,
+            house_edge: 0.01,
+            max_multiplier: 1000.0,
+        }
+    }
+}
+
+impl Rules {
+    /// Multiplier `elapsed` milliseconds into a round, rounded down to cents
+    pub fn multiplier_at(&self, elapsed: u64) -> f64 {
+        floor_cents((self.growth * elapsed as f64 / 1000.0).exp())
+    }
+
+    /// Milliseconds a round crashing at `crash_point` runs for
+    pub fn duration(&self, crash_point: f64) -> u64 {
+        (crash_point.ln() / self.growth * 1000.0).floor() as u64
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.betting_secs == 0 {
+            problems.push(String::from("crash.betting_secs must be above 0"));
+        }
+        if self.growth <= 0.0 {
+            problems.push(String::from("crash.growth must be above 0"));
+        }
+        if self.house_edge < 0.0 || self.house_edge >= 1.0 {
+            problems.push(String::from(
+                "crash.house_edge must be at least 0 and below 1",
+            ));
+        }
+        if self.max_multiplier <= 1.0 {
+            problems.push(String::from("crash.max_multiplier must be above 1"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+fn floor_cents(multiplier: f64) -> f64 {
+    (multiplier * 100.0).floor() / 100.0
+}
+
+fn hex(bytes: &[u8]) -> String {
+    c![format!("{:02x}", byte), for byte in bytes].concat()
+}
+
+/// Secret of a round, revealed once it crashed
+pub fn new_seed<R: Rng>(rng: &mut R) -> String {
+    hex(&(0..SEED_BYTES).map(|_| rng.gen::<u8>()).collect::<Vec<_>>())
+}
+
+/// Published before a round takes bets, sha256 of the seed hex encoded
+pub fn commitment(seed: &str) -> String {
+    hex(&Sha256::digest(seed.as_bytes()))
+}
+
+/// Multiplier the round of `seed` crashes at, from the first 52 bits of sha256 of `crash:<seed>`
+/// taken as a number `u` in [0, 1): (1 - house_edge) / (1 - u) rounded down to cents,
+/// between 1 and `max_multiplier`
+pub fn crash_point(seed: &str, rules: &Rules) -> f64 {
+    let digest = Sha256::digest(format!("crash:{}", seed).as_bytes());
+    let bits = digest[..7]
+        .iter()
+        .fold(0u64, |bits, &byte| (bits << 8) | u64::from(byte)) >> 4;
+    let u = bits as f64 / (1u64 << 52) as f64;
+
+    floor_cents((1.0 - rules.house_edge) / (1.0 - u))
+        .max(1.0)
+        .min(rules.max_multiplier)
+}
+
+/// Returned amount of `bet` cashed out at `multiplier`
+pub fn payout(bet: u64, multiplier: f64) -> u64 {
+    (bet as f64 * multiplier).floor() as u64
+}
+
+/// Milliseconds since the unix epoch
+pub fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() * 1000 + u64::from(elapsed.subsec_nanos() / 1_000_000))
+        .unwrap_or(0)
+}
@@ -0,0 +1,92 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum CrashError {
+    /// One bet per user and round
+    AlreadyBet(u64),
+    /// The round started, bets go to the next one
+    BettingClosed(u64),
+    /// The round crashed at this multiplier before cashing out
+    Crashed(f64),
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    InvalidBet(String),
+    /// Nothing to cash out in the round
+    NoBet(u64),
+    /// No such round, or none was scheduled yet
+    NoRound(Option<u64>),
+    /// The round is still taking bets
+    NotStarted(u64),
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+}
+
+impl Display for CrashError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            CrashError::AlreadyBet(round) => write!(f, "Already bet on round {}", round),
+            CrashError::BettingClosed(round) => {
+                write!(f, "Round {} started, bet on the next one", round)
+            }
+            CrashError::Crashed(multiplier) => write!(f, "Crashed at {:.2}x", multiplier),
+            CrashError::InvalidBet(ref why) => f.write_str(why),
+            CrashError::NoBet(round) => write!(f, "No bet to cash out on round {}", round),
+            CrashError::NoRound(Some(round)) => write!(f, "Round {} doesn't exist", round),
+            CrashError::NoRound(None) => f.write_str("No round was scheduled yet"),
+            CrashError::NotStarted(round) => write!(f, "Round {} hasn't started", round),
+            #[cfg(feature = "auto_save")]
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for CrashError {
+    fn description(&self) -> &str {
+        use self::CrashError::*;
+        match *self {
+            AlreadyBet(_) => "Already bet on the round",
+            BettingClosed(_) => "Betting is closed",
+            Crashed(_) => "The round crashed",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            NoBet(_) => "No bet to cash out",
+            NoRound(_) => "No such round",
+            NotStarted(_) => "The round hasn't started",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for CrashError {
+    fn from(err: DieselResultError) -> Self {
+        CrashError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for CrashError {
+    fn from(err: R2d2Error) -> Self {
+        CrashError::R2d2(err)
+    }
+}
+
+impl CrashError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::CrashError::*;
+        match *self {
+            AlreadyBet(_) | BettingClosed(_) | Crashed(_) | InvalidBet(_) | NoBet(_)
+            | NoRound(_) | NotStarted(_) => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
@@ -0,0 +1,10 @@
+mod crash_game;
+mod error;
+#[cfg(feature = "auto_save")]
+mod round;
+
+pub use self::crash_game::{commitment, crash_point, new_seed, payout, unix_millis, Rules};
+pub use self::error::CrashError;
+#[cfg(feature = "auto_save")]
+pub use self::round::{schedule, BetResponse, BetStatus, BetStatusResponse, CrashBetResult,
+                      CrashRound, Phase, RoundResponse, Rounds};
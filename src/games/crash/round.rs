@@ -0,0 +1,419 @@
+use diesel;
+use diesel::prelude::*;
+use metrics::METRICS;
+use models::{CrashBetRecord, CrashRoundRecord};
+use rand::thread_rng;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use super::{commitment, crash_point, new_seed, payout, unix_millis, CrashError, Rules};
+use ConnectionPool;
+use PgConnection;
+
+/// Wait before retrying to schedule a round after a failure
+const RETRY_MILLIS: u64 = 1000;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Phase {
+    /// Taking bets
+    Betting,
+    /// The multiplier is growing, bets can be cashed out
+    Running,
+    Crashed,
+}
+
+impl Phase {
+    fn of(round: &CrashRoundRecord, now: u64) -> Self {
+        if now < round.starts_at as u64 {
+            Phase::Betting
+        } else if now < round.crashes_at as u64 {
+            Phase::Running
+        } else {
+            Phase::Crashed
+        }
+    }
+}
+
+/// A round as players see it, the seed and crash point are only shown once it crashed
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CrashRound {
+    pub round: u64,
+    pub phase: Phase,
+    /// sha256 of the seed, published before any bet
+    pub commitment: String,
+    /// Unix time in milliseconds, bets are taken until then
+    pub starts_at: u64,
+    /// 1 while betting, the crash point once crashed
+    pub multiplier: f64,
+    pub crash_point: Option<f64>,
+    pub crashes_at: Option<u64>,
+    pub seed: Option<String>,
+    pub players: u64,
+    /// Unix time in milliseconds the multiplier was taken at
+    pub now: u64,
+}
+
+impl CrashRound {
+    fn new(round: CrashRoundRecord, players: u64, rules: &Rules, now: u64) -> Self {
+        let phase = Phase::of(&round, now);
+        let crashed = phase == Phase::Crashed;
+        let multiplier = match phase {
+            Phase::Betting => 1.0,
+            Phase::Running => rules.multiplier_at(now - round.starts_at as u64),
+            Phase::Crashed => round.crash_point,
+        };
+
+        Self {
+            round: round.id as u64,
+            phase,
+            commitment: round.commitment,
+            starts_at: round.starts_at as u64,
+            multiplier,
+            crash_point: if crashed { Some(round.crash_point) } else { None },
+            crashes_at: if crashed {
+                Some(round.crashes_at as u64)
+            } else {
+                None
+            },
+            seed: if crashed { Some(round.seed) } else { None },
+            players,
+            now,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CrashBetResult {
+    pub round: u64,
+    pub bet: u64,
+    /// Multiplier the bet was cashed out at
+    pub cashed_out: Option<f64>,
+    pub gain: i64,
+}
+
+/// Latest bet of a user
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BetStatus {
+    pub round: u64,
+    pub phase: Phase,
+    pub bet: u64,
+    pub cashed_out: Option<f64>,
+    /// Paid by the cash out, 0 until then
+    pub returned: u64,
+}
+
+/// Rounds and their bets are stored, new rounds are scheduled by `schedule`
+pub struct Rounds;
+
+impl Rounds {
+    fn latest(conn: &PgConnection) -> QueryResult<Option<CrashRoundRecord>> {
+        use schema::crash_rounds;
+
+        crash_rounds::table
+            .order(crash_rounds::id.desc())
+            .first::<CrashRoundRecord>(conn)
+            .optional()
+    }
+
+    fn view(
+        conn: &PgConnection,
+        round: CrashRoundRecord,
+        rules: &Rules,
+        now: u64,
+    ) -> QueryResult<CrashRound> {
+        use schema::crash_bets;
+
+        let players: i64 = crash_bets::table
+            .filter(crash_bets::round_id.eq(round.id))
+            .count()
+            .get_result(conn)?;
+        Ok(CrashRound::new(round, players as u64, rules, now))
+    }
+
+    /// Latest round at unix `now` in milliseconds
+    pub fn current(
+        db_pool: &ConnectionPool,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<CrashRound, CrashError> {
+        let conn = db_pool.get()?;
+        match Self::latest(&*conn)? {
+            Some(round) => Ok(Self::view(&*conn, round, rules, now)?),
+            None => Err(CrashError::NoRound(None)),
+        }
+    }
+
+    pub fn round(
+        db_pool: &ConnectionPool,
+        id: u64,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<CrashRound, CrashError> {
+        use schema::crash_rounds;
+
+        let conn = db_pool.get()?;
+        let round = crash_rounds::table
+            .find(id as i64)
+            .first::<CrashRoundRecord>(&*conn)
+            .optional()?;
+        match round {
+            Some(round) => Ok(Self::view(&*conn, round, rules, now)?),
+            None => Err(CrashError::NoRound(Some(id))),
+        }
+    }
+
+    /// Bets on the round taking bets at `now`
+    pub fn bet(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        bet: u64,
+        now: u64,
+    ) -> Result<CrashBetResult, CrashError> {
+        use schema::crash_bets;
+
+        let conn = db_pool.get()?;
+        let round = Self::latest(&*conn)?.ok_or(CrashError::NoRound(None))?;
+        if now >= round.starts_at as u64 {
+            return Err(CrashError::BettingClosed(round.id as u64));
+        }
+
+        let placed = diesel::insert_into(crash_bets::table)
+            .values(&CrashBetRecord {
+                round_id: round.id,
+                namespace: player_namespace,
+                user_id: player as i64,
+                bet: bet as i64,
+                cashed_out: None,
+            })
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+        if placed != 1 {
+            return Err(CrashError::AlreadyBet(round.id as u64));
+        }
+
+        Ok(CrashBetResult {
+            round: round.id as u64,
+            bet,
+            cashed_out: None,
+            gain: -(bet as i64),
+        })
+    }
+
+    /// Cashes out the bet on the running round at the multiplier of `now`
+    pub fn cash_out(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<CrashBetResult, CrashError> {
+        use schema::crash_bets;
+
+        let conn = db_pool.get()?;
+        let round = Self::latest(&*conn)?.ok_or(CrashError::NoRound(None))?;
+        match Phase::of(&round, now) {
+            Phase::Betting => return Err(CrashError::NotStarted(round.id as u64)),
+            Phase::Crashed => return Err(CrashError::Crashed(round.crash_point)),
+            Phase::Running => {}
+        }
+
+        let multiplier = rules.multiplier_at(now - round.starts_at as u64);
+        // Only a bet that is still riding is cashed out, so it can't be paid twice
+        let bet = diesel::update(
+            crash_bets::table
+                .filter(crash_bets::round_id.eq(round.id))
+                .filter(crash_bets::namespace.eq(player_namespace))
+                .filter(crash_bets::user_id.eq(player as i64))
+                .filter(crash_bets::cashed_out.is_null()),
+        ).set(crash_bets::cashed_out.eq(multiplier))
+            .returning(crash_bets::bet)
+            .get_result::<i64>(&*conn)
+            .optional()?
+            .ok_or(CrashError::NoBet(round.id as u64))? as u64;
+
+        Ok(CrashBetResult {
+            round: round.id as u64,
+            bet,
+            cashed_out: Some(multiplier),
+            gain: payout(bet, multiplier) as i64,
+        })
+    }
+
+    /// Latest bet of `player`
+    pub fn status(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        now: u64,
+    ) -> Result<Option<BetStatus>, CrashError> {
+        use schema::{crash_bets, crash_rounds};
+
+        let conn = db_pool.get()?;
+        let bet = crash_bets::table
+            .filter(crash_bets::namespace.eq(player_namespace))
+            .filter(crash_bets::user_id.eq(player as i64))
+            .order(crash_bets::round_id.desc())
+            .first::<CrashBetRecord>(&*conn)
+            .optional()?;
+        let bet = match bet {
+            Some(bet) => bet,
+            None => return Ok(None),
+        };
+        let round = crash_rounds::table
+            .find(bet.round_id)
+            .first::<CrashRoundRecord>(&*conn)?;
+
+        Ok(Some(BetStatus {
+            round: bet.round_id as u64,
+            phase: Phase::of(&round, now),
+            bet: bet.bet as u64,
+            cashed_out: bet.cashed_out,
+            returned: bet.cashed_out
+                .map_or(0, |multiplier| payout(bet.bet as u64, multiplier)),
+        }))
+    }
+
+    /// Records the bets of a crashed round in the metrics, once
+    fn settle(conn: &PgConnection, round: &CrashRoundRecord) -> QueryResult<()> {
+        use schema::{crash_bets, crash_rounds};
+
+        let settled = diesel::update(
+            crash_rounds::table
+                .filter(crash_rounds::id.eq(round.id))
+                .filter(crash_rounds::settled.eq(false)),
+        ).set(crash_rounds::settled.eq(true))
+            .execute(conn)?;
+        if settled != 1 {
+            return Ok(());
+        }
+
+        let bets = crash_bets::table
+            .filter(crash_bets::round_id.eq(round.id))
+            .load::<CrashBetRecord>(conn)?;
+        for bet in bets {
+            let returned = bet.cashed_out
+                .map_or(0, |multiplier| payout(bet.bet as u64, multiplier));
+            METRICS.record_game("crash", bet.bet as u64, returned);
+        }
+        Ok(())
+    }
+
+    /// Schedules the next round once the latest crashed, returns the milliseconds until
+    /// the latest round crashes
+    pub fn advance(db_pool: &ConnectionPool, rules: &Rules, now: u64) -> Result<u64, CrashError> {
+        use schema::crash_rounds;
+
+        let conn = db_pool.get()?;
+        let latest = Self::latest(&*conn)?;
+        if let Some(ref round) = latest {
+            if round.crashes_at as u64 > now {
+                return Ok(round.crashes_at as u64 - now);
+            }
+        }
+
+        let id = match latest {
+            Some(round) => {
+                Self::settle(&*conn, &round)?;
+                round.id + 1
+            }
+            None => 1,
+        };
+        let seed = new_seed(&mut thread_rng());
+        let crash = crash_point(&seed, rules);
+        let starts_at = now + rules.betting_secs * 1000;
+        let round = CrashRoundRecord {
+            id,
+            commitment: commitment(&seed),
+            seed,
+            crash_point: crash,
+            starts_at: starts_at as i64,
+            crashes_at: (starts_at + rules.duration(crash)) as i64,
+            settled: false,
+        };
+        // Another server may have scheduled the round first, its round is waited on then
+        diesel::insert_into(crash_rounds::table)
+            .values(&round)
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+
+        let crashes_at = Self::latest(&*conn)?.map_or(round.crashes_at, |latest| latest.crashes_at);
+        Ok((crashes_at as u64).saturating_sub(now))
+    }
+}
+
+/// Runs rounds back to back in the background, each taking bets for `betting_secs` first
+pub fn schedule(db_pool: ConnectionPool, rules: Rules) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        let wait = match Rounds::advance(&db_pool, &rules, unix_millis()) {
+            Ok(wait) => wait,
+            Err(why) => {
+                error!("Failed to schedule a crash round: {}", why);
+                RETRY_MILLIS
+            }
+        };
+        thread::sleep(Duration::from_millis(wait));
+    })
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RoundResponse {
+    pub status_code: u16,
+    pub status: Result<CrashRound, String>,
+}
+
+impl RoundResponse {
+    pub fn new(result: Result<CrashRound, CrashError>) -> Self {
+        match result {
+            Ok(round) => Self {
+                status_code: 200,
+                status: Ok(round),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BetResponse {
+    pub status_code: u16,
+    pub status: Result<CrashBetResult, String>,
+}
+
+impl BetResponse {
+    pub fn new(result: Result<CrashBetResult, CrashError>) -> Self {
+        match result {
+            Ok(bet) => Self {
+                status_code: 200,
+                status: Ok(bet),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct BetStatusResponse {
+    pub status_code: u16,
+    pub status: Result<Option<BetStatus>, String>,
+}
+
+impl BetStatusResponse {
+    pub fn new(result: Result<Option<BetStatus>, CrashError>) -> Self {
+        match result {
+            Ok(status) => Self {
+                status_code: 200,
+                status: Ok(status),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
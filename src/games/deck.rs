@@ -35,6 +35,17 @@ impl StandardDeck {
         Self { cards }
     }
 
+    /// Shuffled shoe of `decks` decks, the way baccarat and blackjack tables deal
+    pub fn shoe(decks: usize) -> Self {
+        let mut cards: Vec<StandardCard> = Vec::with_capacity(decks * STANDARD_DECK_OF_CARDS.len());
+        for _ in 0..decks {
+            cards.extend_from_slice(&STANDARD_DECK_OF_CARDS);
+        }
+        use rand::{thread_rng, Rng};
+        thread_rng().shuffle(&mut cards);
+        Self { cards }
+    }
+
     pub fn draw(&mut self) -> Result<StandardCard, StandardDeckError> {
         // Game should never get to the point where the deck is empty
         match self.cards.pop() {
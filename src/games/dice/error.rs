@@ -0,0 +1,69 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum DiceError {
+    BetTooLarge,
+    InvalidBet(String),
+    /// The dice as they were given
+    InvalidDice(String),
+    /// The bet as it was given
+    InvalidSpot(String),
+    /// Target no roll can win on, or every roll wins on
+    InvalidTarget(u32),
+    NoBet,
+    /// Most bets allowed on one roll
+    TooManyBets(usize),
+    UnknownDirection(String),
+}
+
+impl Display for DiceError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            DiceError::InvalidBet(ref why) => f.write_str(why),
+            DiceError::InvalidDice(ref dice) => write!(
+                f,
+                "{} isn't a roll like `3d6`, up to {} dice of 2 to 255 sides",
+                dice,
+                super::MAX_DICE
+            ),
+            DiceError::InvalidSpot(ref spot) => write!(
+                f,
+                "{} isn't a Sic Bo bet, bet like `small`, `big`, `odd`, `even`, `total 10`, \
+                 `triple 4`, `any_triple`, `double 3`, `combination 1-2` or `single 5`",
+                spot
+            ),
+            DiceError::InvalidTarget(target) => {
+                write!(f, "A bet on {} either can't win or can't lose", target)
+            }
+            DiceError::TooManyBets(max) => write!(f, "At most {} bets per roll", max),
+            DiceError::UnknownDirection(ref direction) => {
+                write!(f, "{} isn't `over` or `under`", direction)
+            }
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for DiceError {
+    fn description(&self) -> &str {
+        use self::DiceError::*;
+        match *self {
+            BetTooLarge => "Bet is too large",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidDice(_) => "Invalid dice",
+            InvalidSpot(_) => "Invalid bet",
+            InvalidTarget(_) => "Invalid target",
+            NoBet => "Nothing was bet",
+            TooManyBets(_) => "Too many bets",
+            UnknownDirection(_) => "Unknown direction",
+        }
+    }
+}
+
+impl DiceError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        501
+    }
+}
@@ -0,0 +1,11 @@
+mod error;
+mod over_under;
+mod roller;
+mod sic_bo;
+
+pub use self::error::DiceError;
+pub use self::over_under::{over_under, play_over_under, Direction, OverUnderGame,
+                           OverUnderResponse, Rules as OverUnderRules};
+pub use self::roller::{Dice, DiceRoll, DiceRollResponse, DiceRoller, MAX_DICE};
+pub use self::sic_bo::{play_sic_bo, sic_bo, total_bet, BetKind, Rules as SicBoRules, SettledBet,
+                       SicBoBet, SicBoGame, SicBoResponse};
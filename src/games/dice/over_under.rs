@@ -0,0 +1,185 @@
+use metrics::{payout, METRICS};
+use std::str::FromStr;
+use super::{Dice, DiceError, DiceRoller};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Dice rolled, their total is bet over or under the target
+    pub dice: u8,
+    pub sides: u8,
+    /// Kept from the payout, 0.02 pays 98% of the fair odds
+    pub house_edge: f64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            dice: 2,
+            sides: 6,
+            house_edge: 0.02,
+        }
+    }
+}
+
+impl Rules {
+    pub fn dice(&self) -> Result<Dice, DiceError> {
+        Dice::new(self.dice, self.sides)
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        match self.dice() {
+            Ok(dice) => if dice.outcomes().is_none() {
+                problems.push(format!("over_under rolls {}, too many outcomes to count", dice));
+            },
+            Err(_) => problems.push(format!(
+                "over_under needs 1 to {} dice of at least 2 sides",
+                super::MAX_DICE
+            )),
+        }
+        if self.house_edge < 0.0 || self.house_edge >= 1.0 {
+            problems.push(String::from(
+                "over_under.house_edge must be at least 0 and below 1",
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Exact chance of winning `direction` on `target`, and the returned amount on a win as a
+    /// multiple of the bet
+    pub fn odds(&self, direction: Direction, target: u32) -> Result<(f64, f64), DiceError> {
+        let dice = self.dice()?;
+        let ways = dice.distribution()
+            .ok_or_else(|| DiceError::InvalidDice(dice.to_string()))?;
+        let outcomes = ways.iter().sum::<u64>();
+        let wins = ways.iter()
+            .enumerate()
+            .filter(|&(total, _)| direction.wins(total as u32, target))
+            .map(|(_, &ways)| ways)
+            .sum::<u64>();
+
+        let probability = wins as f64 / outcomes as f64;
+        let pays = (1.0 - self.house_edge) / probability;
+        // A win has to pay more than the bet
+        if wins == 0 || pays <= 1.0 {
+            return Err(DiceError::InvalidTarget(target));
+        }
+        Ok((probability, pays))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Direction {
+    Over,
+    Under,
+}
+
+impl Direction {
+    /// True if `total` is strictly over or under `target`
+    pub fn wins(&self, total: u32, target: u32) -> bool {
+        match *self {
+            Direction::Over => total > target,
+            Direction::Under => total < target,
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = DiceError;
+
+    fn from_str(direction: &str) -> Result<Self, Self::Err> {
+        match direction.to_lowercase().as_str() {
+            "over" => Ok(Direction::Over),
+            "under" => Ok(Direction::Under),
+            _ => Err(DiceError::UnknownDirection(direction.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OverUnderGame {
+    pub dice: Dice,
+    pub rolls: Vec<u8>,
+    pub total: u32,
+    pub direction: Direction,
+    pub target: u32,
+    /// Exact chance of the bet winning
+    pub probability: f64,
+    /// Returned amount on a win as a multiple of the bet
+    pub pays: f64,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Settles `bet` on the total of `rolls` being over or under `target`
+pub fn play_over_under(
+    bet: u64,
+    direction: Direction,
+    target: u32,
+    rolls: Vec<u8>,
+    rules: &Rules,
+) -> Result<OverUnderGame, DiceError> {
+    let (probability, pays) = rules.odds(direction, target)?;
+    let total = rolls.iter().map(|&roll| u32::from(roll)).sum();
+
+    let gain = if direction.wins(total, target) {
+        (bet as f64 * pays) as i64 - bet as i64
+    } else {
+        -(bet as i64)
+    };
+
+    Ok(OverUnderGame {
+        dice: rules.dice()?,
+        rolls,
+        total,
+        direction,
+        target,
+        probability,
+        pays,
+        bet,
+        gain,
+    })
+}
+
+/// Rolls the dice of `rules` with `bet` on their total being over or under `target`
+pub fn over_under(
+    bet: u64,
+    direction: Direction,
+    target: u32,
+    roller: &mut DiceRoller,
+    rules: &Rules,
+) -> Result<OverUnderGame, DiceError> {
+    let rolls = roller.roll(rules.dice()?);
+    let game = play_over_under(bet, direction, target, rolls, rules)?;
+    METRICS.record_game("over_under", bet, payout(bet, game.gain));
+    Ok(game)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OverUnderResponse {
+    pub status_code: u16,
+    pub status: Result<OverUnderGame, String>,
+}
+
+impl OverUnderResponse {
+    pub fn new(result: Result<OverUnderGame, DiceError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
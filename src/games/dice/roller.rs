@@ -0,0 +1,164 @@
+use rand::{thread_rng, Isaac64Rng, Rng, SeedableRng};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use super::DiceError;
+
+/// Most dice rolled at once
+pub const MAX_DICE: u8 = 100;
+
+/// `count` dice of `sides` sides, written NdM like `3d6`
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Dice {
+    pub count: u8,
+    pub sides: u8,
+}
+
+impl Dice {
+    pub fn new(count: u8, sides: u8) -> Result<Self, DiceError> {
+        if count == 0 || count > MAX_DICE || sides < 2 {
+            return Err(DiceError::InvalidDice(format!("{}d{}", count, sides)));
+        }
+        Ok(Self { count, sides })
+    }
+
+    pub fn min(&self) -> u32 {
+        u32::from(self.count)
+    }
+
+    pub fn max(&self) -> u32 {
+        u32::from(self.count) * u32::from(self.sides)
+    }
+
+    /// Every way the dice can land, None if there are more than fit a u64
+    pub fn outcomes(&self) -> Option<u64> {
+        (0..self.count).fold(Some(1u64), |outcomes, _| {
+            outcomes.and_then(|outcomes| outcomes.checked_mul(u64::from(self.sides)))
+        })
+    }
+
+    /// Ways of rolling each total, indexed by the total
+    /// None if the outcomes can't be counted in a u64
+    pub fn distribution(&self) -> Option<Vec<u64>> {
+        self.outcomes()?;
+
+        let sides = self.sides as usize;
+        let mut ways = vec![1u64];
+        for _ in 0..self.count {
+            let mut next = vec![0u64; ways.len() + sides];
+            for (total, &count) in ways.iter().enumerate() {
+                for face in 1..sides + 1 {
+                    next[total + face] += count;
+                }
+            }
+            ways = next;
+        }
+        Some(ways)
+    }
+}
+
+impl Display for Dice {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "{}d{}", self.count, self.sides)
+    }
+}
+
+impl FromStr for Dice {
+    type Err = DiceError;
+
+    /// Parses `3d6`, `d20` is a single die
+    fn from_str(dice: &str) -> Result<Self, Self::Err> {
+        let invalid = || DiceError::InvalidDice(dice.to_string());
+        let lower = dice.trim().to_lowercase();
+        let mut parts = lower.splitn(2, 'd');
+        let count = match parts.next() {
+            Some("") => 1,
+            Some(count) => count.parse().map_err(|_| invalid())?,
+            None => return Err(invalid()),
+        };
+        let sides = parts
+            .next()
+            .and_then(|sides| sides.parse().ok())
+            .ok_or_else(invalid)?;
+
+        Self::new(count, sides).map_err(|_| invalid())
+    }
+}
+
+/// Rolls dice from a seed, the same seed always gives the same rolls
+pub struct DiceRoller {
+    seed: u64,
+    rng: Isaac64Rng,
+}
+
+impl Default for DiceRoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DiceRoller {
+    /// Roller with a random seed
+    pub fn new() -> Self {
+        Self::seeded(thread_rng().gen())
+    }
+
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Isaac64Rng::from_seed(&[seed]),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn roll(&mut self, dice: Dice) -> Vec<u8> {
+        let rng = &mut self.rng;
+        (0..dice.count)
+            .map(|_| rng.gen_range(1, u16::from(dice.sides) + 1) as u8)
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DiceRoll {
+    pub dice: Dice,
+    /// Rolling the same dice with this seed rolls the same
+    pub seed: u64,
+    pub rolls: Vec<u8>,
+    pub total: u32,
+}
+
+impl DiceRoll {
+    pub fn new(dice: Dice, roller: &mut DiceRoller) -> Self {
+        let rolls = roller.roll(dice);
+        Self {
+            dice,
+            seed: roller.seed(),
+            total: rolls.iter().map(|&roll| u32::from(roll)).sum(),
+            rolls,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DiceRollResponse {
+    pub status_code: u16,
+    pub status: Result<DiceRoll, String>,
+}
+
+impl DiceRollResponse {
+    pub fn new(result: Result<DiceRoll, DiceError>) -> Self {
+        match result {
+            Ok(roll) => Self {
+                status_code: 200,
+                status: Ok(roll),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
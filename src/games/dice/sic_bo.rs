@@ -0,0 +1,247 @@
+use games::{checked_sum, net_gain};
+use metrics::{payout, METRICS};
+use super::{Dice, DiceError, DiceRoller};
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Most bets settled on one roll
+    pub max_bets: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self { max_bets: 50 }
+    }
+}
+
+impl Rules {
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        if self.max_bets == 0 {
+            Err(vec![String::from("sic_bo.max_bets must be above 0")])
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BetKind {
+    /// Totals of 4 to 10, loses on a triple
+    Small,
+    /// Totals of 11 to 17, loses on a triple
+    Big,
+    /// Loses on a triple
+    Odd,
+    /// Loses on a triple
+    Even,
+    /// A total of 4 to 17
+    Total(u8),
+    /// Three of the face
+    Triple(u8),
+    AnyTriple,
+    /// At least two of the face
+    Double(u8),
+    /// Two different faces, lowest first
+    Combination(u8, u8),
+    /// The face on any die, pays for every die showing it
+    Single(u8),
+}
+
+/// Gain on a total as a multiple of the bet
+fn total_pays(total: u8) -> u64 {
+    match total {
+        4 | 17 => 60,
+        5 | 16 => 30,
+        6 | 15 => 17,
+        7 | 14 => 12,
+        8 | 13 => 8,
+        _ => 6,
+    }
+}
+
+impl BetKind {
+    /// Parses bets like `big`, `total 10`, `triple 4`, `any_triple`, `combination 1-2`
+    pub fn parse(input: &str) -> Result<Self, DiceError> {
+        let lower = input.trim().to_lowercase();
+        let mut words = lower.split_whitespace();
+        let kind = words.next().unwrap_or("");
+        let args: Vec<&str> = words
+            .next()
+            .map_or_else(Vec::new, |args| args.split('-').collect());
+        let number = |i: usize| args.get(i).and_then(|arg| arg.parse::<u8>().ok());
+        let face = |i: usize| match number(i) {
+            Some(n) if n >= 1 && n <= 6 => Some(n),
+            _ => None,
+        };
+
+        if words.next().is_some() {
+            return Err(DiceError::InvalidSpot(input.to_string()));
+        }
+
+        let bet = match (kind, args.len()) {
+            ("small", 0) => Some(BetKind::Small),
+            ("big", 0) => Some(BetKind::Big),
+            ("odd", 0) => Some(BetKind::Odd),
+            ("even", 0) => Some(BetKind::Even),
+            ("total", 1) => match number(0) {
+                Some(n) if n >= 4 && n <= 17 => Some(BetKind::Total(n)),
+                _ => None,
+            },
+            ("triple", 1) => face(0).map(BetKind::Triple),
+            ("any_triple", 0) | ("anytriple", 0) => Some(BetKind::AnyTriple),
+            ("double", 1) => face(0).map(BetKind::Double),
+            ("combination", 2) => match (face(0), face(1)) {
+                (Some(a), Some(b)) if a < b => Some(BetKind::Combination(a, b)),
+                (Some(a), Some(b)) if a > b => Some(BetKind::Combination(b, a)),
+                _ => None,
+            },
+            ("single", 1) => face(0).map(BetKind::Single),
+            _ => None,
+        };
+
+        bet.ok_or_else(|| DiceError::InvalidSpot(input.to_string()))
+    }
+
+    /// Gain as a multiple of the bet on `dice`, None if the bet lost
+    pub fn pays(&self, dice: &[u8; 3]) -> Option<u64> {
+        let total = dice.iter().sum::<u8>();
+        let triple = dice[0] == dice[1] && dice[1] == dice[2];
+        let showing = |face: u8| dice.iter().filter(|&&die| die == face).count() as u64;
+        let pays_if = |won: bool, pays: u64| if won { Some(pays) } else { None };
+
+        match *self {
+            BetKind::Small => pays_if(!triple && total <= 10, 1),
+            BetKind::Big => pays_if(!triple && total >= 11, 1),
+            BetKind::Odd => pays_if(!triple && total % 2 == 1, 1),
+            BetKind::Even => pays_if(!triple && total % 2 == 0, 1),
+            BetKind::Total(n) => pays_if(total == n, total_pays(n)),
+            BetKind::Triple(face) => pays_if(showing(face) == 3, 180),
+            BetKind::AnyTriple => pays_if(triple, 30),
+            BetKind::Double(face) => pays_if(showing(face) >= 2, 10),
+            BetKind::Combination(a, b) => pays_if(showing(a) > 0 && showing(b) > 0, 5),
+            BetKind::Single(face) => pays_if(showing(face) > 0, showing(face)),
+        }
+    }
+}
+
+/// A bet as it is sent
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SicBoBet {
+    /// Like `small`, `big`, `odd`, `even`, `total 10`, `triple 4`, `any_triple`, `double 3`,
+    /// `combination 1-2` or `single 5`
+    pub bet: String,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SettledBet {
+    pub bet: String,
+    pub amount: u64,
+    pub won: bool,
+    pub gain: i64,
+}
+
+impl SettledBet {
+    pub fn new(bet: &SicBoBet, kind: BetKind, dice: &[u8; 3]) -> Result<Self, DiceError> {
+        let pays = kind.pays(dice);
+        let gain = match pays {
+            Some(pays) => bet.amount
+                .checked_mul(pays)
+                .and_then(|winnings| net_gain(winnings, 0)),
+            None => net_gain(0, bet.amount),
+        };
+
+        Ok(Self {
+            bet: bet.bet.clone(),
+            amount: bet.amount,
+            won: pays.is_some(),
+            gain: gain.ok_or(DiceError::BetTooLarge)?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SicBoGame {
+    pub dice: [u8; 3],
+    pub total: u8,
+    pub bets: Vec<SettledBet>,
+    /// Everything bet
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Everything bet in `bets`
+pub fn total_bet(bets: &[SicBoBet]) -> Result<u64, DiceError> {
+    checked_sum(&c![bet.amount, for bet in bets]).ok_or(DiceError::BetTooLarge)
+}
+
+/// Settles `bets` against `dice`, nothing is settled if any bet is invalid
+pub fn play_sic_bo(
+    bets: &[SicBoBet],
+    dice: [u8; 3],
+    rules: &Rules,
+) -> Result<SicBoGame, DiceError> {
+    if bets.len() > rules.max_bets {
+        return Err(DiceError::TooManyBets(rules.max_bets));
+    }
+    let total = total_bet(bets)?;
+    if total == 0 {
+        return Err(DiceError::NoBet);
+    }
+
+    let mut kinds = Vec::with_capacity(bets.len());
+    for bet in bets {
+        kinds.push(BetKind::parse(&bet.bet)?);
+    }
+    let settled = bets.iter()
+        .zip(&kinds)
+        .map(|(bet, &kind)| SettledBet::new(bet, kind, &dice))
+        .collect::<Result<Vec<_>, _>>()?;
+    let gain = settled
+        .iter()
+        .fold(Some(0i64), |sum, bet| sum.and_then(|sum| sum.checked_add(bet.gain)))
+        .ok_or(DiceError::BetTooLarge)?;
+
+    Ok(SicBoGame {
+        dice,
+        total: dice.iter().sum(),
+        gain,
+        bets: settled,
+        bet: total,
+    })
+}
+
+/// Rolls 3 dice and settles `bets` against them
+pub fn sic_bo(
+    bets: &[SicBoBet],
+    roller: &mut DiceRoller,
+    rules: &Rules,
+) -> Result<SicBoGame, DiceError> {
+    let rolls = roller.roll(Dice { count: 3, sides: 6 });
+    let game = play_sic_bo(bets, [rolls[0], rolls[1], rolls[2]], rules)?;
+    METRICS.record_game("sic_bo", game.bet, payout(game.bet, game.gain));
+    Ok(game)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SicBoResponse {
+    pub status_code: u16,
+    pub status: Result<SicBoGame, String>,
+}
+
+impl SicBoResponse {
+    pub fn new(result: Result<SicBoGame, DiceError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,107 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use games::{StandardCardParseError, StandardDeckError};
+
+#[derive(Debug)]
+pub enum HiLoError {
+    CardParse(StandardCardParseError),
+    /// The streak was played by another request
+    Conflict,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    /// Most guesses allowed in one streak
+    GuessLimit(u32),
+    InvalidBet(String),
+    /// The card guessed on
+    InvalidGuess(String),
+    NoCard,
+    NoStreak,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+    StreakInProgress,
+    UnknownGuess(String),
+}
+
+impl Display for HiLoError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            HiLoError::GuessLimit(max) => {
+                write!(f, "Streaks have to be cashed out after {} guesses", max)
+            }
+            HiLoError::InvalidBet(ref why) => f.write_str(why),
+            HiLoError::InvalidGuess(ref card) => write!(
+                f,
+                "That guess can't pay more than it risks on {}, guess the other way",
+                card
+            ),
+            HiLoError::UnknownGuess(ref guess) => {
+                write!(f, "{} isn't `higher` or `lower`", guess)
+            }
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for HiLoError {
+    fn description(&self) -> &str {
+        use self::HiLoError::*;
+        match *self {
+            CardParse(ref inner) => inner.description(),
+            Conflict => "The streak was played by another request, please try again",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            GuessLimit(_) => "Too many guesses",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidGuess(_) => "Invalid guess",
+            NoCard => "No card was able to be drawn",
+            NoStreak => "No streak was started",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+            StreakInProgress => "A streak is already going, please cash out first",
+            UnknownGuess(_) => "Unknown guess",
+        }
+    }
+}
+
+impl From<StandardCardParseError> for HiLoError {
+    fn from(err: StandardCardParseError) -> Self {
+        HiLoError::CardParse(err)
+    }
+}
+
+impl From<StandardDeckError> for HiLoError {
+    fn from(_: StandardDeckError) -> Self {
+        HiLoError::NoCard
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for HiLoError {
+    fn from(err: DieselResultError) -> Self {
+        HiLoError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for HiLoError {
+    fn from(err: R2d2Error) -> Self {
+        HiLoError::R2d2(err)
+    }
+}
+
+impl HiLoError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::HiLoError::*;
+        match *self {
+            Conflict => 409,
+            GuessLimit(_) | InvalidBet(_) | InvalidGuess(_) | NoStreak | StreakInProgress
+            | UnknownGuess(_) => 501,
+            _ => 500,
+        }
+    }
+}
@@ -0,0 +1,431 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+use games::{StandardCard, StandardDeck};
+#[cfg(feature = "auto_save")]
+use metrics::METRICS;
+#[cfg(feature = "auto_save")]
+use models::HiLoSession;
+use std::cmp::Ordering;
+use std::str::FromStr;
+use super::HiLoError;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Kept from every win, 0.02 raises the multiplier by 98% of the fair odds
+    pub house_edge: f64,
+    /// Streaks have to be cashed out after this many guesses
+    pub max_guesses: u32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            house_edge: 0.02,
+            max_guesses: 20,
+        }
+    }
+}
+
+impl Rules {
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.house_edge < 0.0 || self.house_edge >= 1.0 {
+            problems.push(String::from(
+                "hi_lo.house_edge must be at least 0 and below 1",
+            ));
+        }
+        // The first card and every guess come from one deck
+        if self.max_guesses == 0 || self.max_guesses > 51 {
+            problems.push(String::from("hi_lo.max_guesses must be between 1 and 51"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Guess {
+    Higher,
+    Lower,
+}
+
+impl FromStr for Guess {
+    type Err = HiLoError;
+
+    fn from_str(guess: &str) -> Result<Self, Self::Err> {
+        match guess.to_lowercase().as_str() {
+            "higher" | "hi" | "h" => Ok(Guess::Higher),
+            "lower" | "lo" | "l" => Ok(Guess::Lower),
+            _ => Err(HiLoError::UnknownGuess(guess.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HiLoOutcome {
+    Win,
+    Lose,
+    /// Same rank, the streak goes on from the new card
+    Push,
+}
+
+/// Chance of `guess` winning on the next card of `deck` after `card`
+/// Cards of the same rank push, so they are left out
+pub fn probability(card: &StandardCard, deck: &[StandardCard], guess: Guess) -> f64 {
+    let higher = deck.iter()
+        .filter(|next| next.cmp_rank(card) == Ordering::Greater)
+        .count();
+    let lower = deck.iter()
+        .filter(|next| next.cmp_rank(card) == Ordering::Less)
+        .count();
+
+    match (guess, higher + lower) {
+        (_, 0) => 0.0,
+        (Guess::Higher, decided) => higher as f64 / decided as f64,
+        (Guess::Lower, decided) => lower as f64 / decided as f64,
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct GuessResult {
+    pub drawn: StandardCard,
+    pub outcome: HiLoOutcome,
+    /// Exact chance the guess had of winning
+    pub probability: f64,
+    /// Multiplier of the streak after the guess, 0 once lost
+    pub multiplier: f64,
+}
+
+/// Draws the card after `card` from `deck` on `guess`
+/// A win raises `multiplier` by the odds of the guess minus the house edge
+pub fn guess_next(
+    card: &StandardCard,
+    deck: &mut StandardDeck,
+    guess: Guess,
+    multiplier: f64,
+    rules: &Rules,
+) -> Result<GuessResult, HiLoError> {
+    let chance = probability(card, &deck.cards, guess);
+    let pays = (1.0 - rules.house_edge) / chance;
+    if chance == 0.0 || pays <= 1.0 {
+        return Err(HiLoError::InvalidGuess(card.to_string()));
+    }
+
+    let drawn = deck.draw()?;
+    let (outcome, multiplier) = match (guess, drawn.cmp_rank(card)) {
+        (_, Ordering::Equal) => (HiLoOutcome::Push, multiplier),
+        (Guess::Higher, Ordering::Greater) | (Guess::Lower, Ordering::Less) => {
+            (HiLoOutcome::Win, multiplier * pays)
+        }
+        _ => (HiLoOutcome::Lose, 0.0),
+    };
+
+    Ok(GuessResult {
+        drawn,
+        outcome,
+        probability: chance,
+        multiplier,
+    })
+}
+
+/// A streak between guesses
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HiLoStreak {
+    /// Amount that started the streak
+    pub bet: u64,
+    /// The next guess is on this card
+    pub card: String,
+    pub multiplier: f64,
+    /// Paid on cash out, the bet times the multiplier
+    pub pot: u64,
+    pub guesses: u32,
+    pub cards_left: usize,
+}
+
+impl HiLoStreak {
+    pub fn new(
+        bet: u64,
+        card: &StandardCard,
+        multiplier: f64,
+        guesses: u32,
+        deck: &[String],
+    ) -> Self {
+        Self {
+            bet,
+            card: card.to_string(),
+            multiplier,
+            pot: (bet as f64 * multiplier) as u64,
+            guesses,
+            cards_left: deck.len(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HiLoResult {
+    /// None when starting or cashing out
+    pub guess: Option<Guess>,
+    /// Card the guess was settled on
+    pub drawn: Option<String>,
+    pub outcome: Option<HiLoOutcome>,
+    /// Chance the guess had of winning
+    pub probability: Option<f64>,
+    /// The streak after this request, None once it is lost or cashed out
+    pub streak: Option<HiLoStreak>,
+    /// Amount taken by this request, only starting a streak takes a bet
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Streaks are stored from the first card until lost or cashed out
+#[cfg(feature = "auto_save")]
+pub struct HiLo;
+
+#[cfg(feature = "auto_save")]
+impl HiLo {
+    fn streak(session: &HiLoSession) -> Result<HiLoStreak, HiLoError> {
+        Ok(HiLoStreak::new(
+            session.bet as u64,
+            &session.card.parse::<StandardCard>()?,
+            session.multiplier,
+            session.guesses as u32,
+            &session.deck,
+        ))
+    }
+
+    /// Streak of `player`, if they have one going
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Option<HiLoStreak>, HiLoError> {
+        use schema::hi_lo::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = hi_lo
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<HiLoSession>(&*conn)
+            .optional()?;
+
+        match session {
+            Some(session) => Ok(Some(Self::streak(&session)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Takes `new_bet` and shows the first card
+    pub fn start(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        new_bet: u64,
+    ) -> Result<HiLoResult, HiLoError> {
+        use schema::hi_lo;
+
+        let conn = db_pool.get()?;
+        let mut new_deck = StandardDeck::new();
+        let first = new_deck.draw()?;
+        let session = HiLoSession {
+            namespace: player_namespace,
+            id: player as i64,
+            bet: new_bet as i64,
+            multiplier: 1.0,
+            card: first.to_string(),
+            deck: new_deck.export(),
+            guesses: 0,
+        };
+        let inserted = diesel::insert_into(hi_lo::table)
+            .values(&session)
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+
+        // Nothing is inserted while the player already has a streak
+        if inserted != 1 {
+            return Err(HiLoError::StreakInProgress);
+        }
+
+        Ok(HiLoResult {
+            guess: None,
+            drawn: None,
+            outcome: None,
+            probability: None,
+            streak: Some(Self::streak(&session)?),
+            bet: new_bet,
+            gain: -(new_bet as i64),
+        })
+    }
+
+    /// Guesses the next card of `player`'s streak
+    pub fn guess(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        next: Guess,
+        rules: &Rules,
+    ) -> Result<HiLoResult, HiLoError> {
+        use schema::hi_lo::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = hi_lo
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<HiLoSession>(&*conn)
+            .optional()?
+            .ok_or(HiLoError::NoStreak)?;
+
+        if session.guesses as u32 >= rules.max_guesses {
+            return Err(HiLoError::GuessLimit(rules.max_guesses));
+        }
+
+        let mut cards = Vec::with_capacity(session.deck.len());
+        for stored in &session.deck {
+            cards.push(stored.parse::<StandardCard>()?);
+        }
+        let mut remaining = StandardDeck { cards };
+        let result = guess_next(
+            &session.card.parse::<StandardCard>()?,
+            &mut remaining,
+            next,
+            session.multiplier,
+            rules,
+        )?;
+
+        // Only applied if nobody else played the streak since it was loaded
+        let unchanged = hi_lo
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .filter(guesses.eq(session.guesses));
+
+        let new_deck = remaining.export();
+        let updated = match result.outcome {
+            HiLoOutcome::Lose => diesel::delete(unchanged).execute(&*conn)?,
+            _ => diesel::update(unchanged)
+                .set((
+                    multiplier.eq(result.multiplier),
+                    card.eq(result.drawn.to_string()),
+                    deck.eq(&new_deck),
+                    guesses.eq(session.guesses + 1),
+                ))
+                .execute(&*conn)?,
+        };
+
+        if updated != 1 {
+            return Err(HiLoError::Conflict);
+        }
+
+        let streak = match result.outcome {
+            HiLoOutcome::Lose => {
+                METRICS.record_game("hi_lo", session.bet as u64, 0);
+                None
+            }
+            _ => Some(HiLoStreak::new(
+                session.bet as u64,
+                &result.drawn,
+                result.multiplier,
+                session.guesses as u32 + 1,
+                &new_deck,
+            )),
+        };
+
+        Ok(HiLoResult {
+            guess: Some(next),
+            drawn: Some(result.drawn.to_string()),
+            outcome: Some(result.outcome),
+            probability: Some(result.probability),
+            streak,
+            bet: 0,
+            gain: 0,
+        })
+    }
+
+    /// Ends `player`'s streak, paying its pot
+    pub fn cash_out(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<HiLoResult, HiLoError> {
+        use schema::hi_lo::dsl::*;
+
+        let conn = db_pool.get()?;
+        // Deleting and reading in one statement keeps a streak from being paid twice
+        let session = diesel::delete(
+            hi_lo
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64)),
+        ).get_result::<HiLoSession>(&*conn)
+            .optional()?
+            .ok_or(HiLoError::NoStreak)?;
+
+        let pot = Self::streak(&session)?.pot;
+        METRICS.record_game("hi_lo", session.bet as u64, pot);
+
+        Ok(HiLoResult {
+            guess: None,
+            drawn: None,
+            outcome: None,
+            probability: None,
+            streak: None,
+            bet: 0,
+            gain: pot as i64,
+        })
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HiLoResponse {
+    pub status_code: u16,
+    pub status: Result<HiLoResult, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl HiLoResponse {
+    pub fn new(result: Result<HiLoResult, HiLoError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HiLoStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when there is no streak going
+    pub status: Result<Option<HiLoStreak>, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl HiLoStatusResponse {
+    pub fn new(result: Result<Option<HiLoStreak>, HiLoError>) -> Self {
+        match result {
+            Ok(streak) => Self {
+                status_code: 200,
+                status: Ok(streak),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,8 @@
+mod error;
+mod hi_lo_game;
+
+pub use self::error::HiLoError;
+pub use self::hi_lo_game::{guess_next, probability, Guess, GuessResult, HiLoOutcome, HiLoResult,
+                           HiLoStreak, Rules};
+#[cfg(feature = "auto_save")]
+pub use self::hi_lo_game::{HiLo, HiLoResponse, HiLoStatusResponse};
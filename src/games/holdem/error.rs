@@ -0,0 +1,83 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use games::{StandardCardParseError, StandardDeckError};
+
+#[derive(Debug)]
+pub enum HoldemError {
+    CardParse(StandardCardParseError),
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    HandInProgress,
+    InvalidBet(String),
+    NoCard,
+    NoHand,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+}
+
+impl Display for HoldemError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            HoldemError::InvalidBet(ref why) => f.write_str(why),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for HoldemError {
+    fn description(&self) -> &str {
+        use self::HoldemError::*;
+        match *self {
+            CardParse(ref inner) => inner.description(),
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            HandInProgress => "A hand is already dealt, please call or fold first",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            NoCard => "No card was able to be drawn",
+            NoHand => "No hand was dealt",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+impl From<StandardCardParseError> for HoldemError {
+    fn from(err: StandardCardParseError) -> Self {
+        HoldemError::CardParse(err)
+    }
+}
+
+impl From<StandardDeckError> for HoldemError {
+    fn from(_: StandardDeckError) -> Self {
+        HoldemError::NoCard
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for HoldemError {
+    fn from(err: DieselResultError) -> Self {
+        HoldemError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for HoldemError {
+    fn from(err: R2d2Error) -> Self {
+        HoldemError::R2d2(err)
+    }
+}
+
+impl HoldemError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::HoldemError::*;
+        match *self {
+            HandInProgress | InvalidBet(_) | NoHand => 501,
+            _ => 500,
+        }
+    }
+}
@@ -0,0 +1,408 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+use games::poker::{best_hand, HandCategory, HandRank, ACE};
+use games::StandardCard;
+#[cfg(feature = "auto_save")]
+use games::StandardDeck;
+#[cfg(feature = "auto_save")]
+use metrics::METRICS;
+#[cfg(feature = "auto_save")]
+use models::HoldemSession;
+use super::HoldemError;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+/// Casino Hold'em rules, the ante pays by the player's final hand when it isn't lost
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Lowest pair the dealer qualifies with, 4 for a pair of fours
+    pub dealer_qualifier: u8,
+    /// Calling costs this many antes
+    pub call_multiplier: u64,
+    pub royal_flush: u64,
+    pub straight_flush: u64,
+    pub four_of_a_kind: u64,
+    pub full_house: u64,
+    pub flush: u64,
+    /// Anything below a flush
+    pub other: u64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            dealer_qualifier: 4,
+            call_multiplier: 2,
+            royal_flush: 100,
+            straight_flush: 20,
+            four_of_a_kind: 10,
+            full_house: 3,
+            flush: 2,
+            other: 1,
+        }
+    }
+}
+
+impl Rules {
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.dealer_qualifier < 2 || self.dealer_qualifier > ACE {
+            problems.push(format!(
+                "holdem.dealer_qualifier ({}) must be a rank from 2 to {}",
+                self.dealer_qualifier, ACE
+            ));
+        }
+        if self.call_multiplier == 0 {
+            problems.push(String::from("holdem.call_multiplier must be above 0"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// Ante winnings per unit bet for the player's final hand
+    pub fn ante_pays(&self, rank: &HandRank) -> u64 {
+        match rank.category {
+            HandCategory::StraightFlush if rank.is_royal() => self.royal_flush,
+            HandCategory::StraightFlush => self.straight_flush,
+            HandCategory::FourOfAKind => self.four_of_a_kind,
+            HandCategory::FullHouse => self.full_house,
+            HandCategory::Flush => self.flush,
+            _ => self.other,
+        }
+    }
+
+    /// The dealer needs `dealer_qualifier` pair or better to play
+    pub fn qualifies(&self, rank: &HandRank) -> bool {
+        rank.category > HandCategory::OnePair
+            || (rank.category == HandCategory::OnePair && rank.ranks[0] >= self.dealer_qualifier)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum HoldemOutcome {
+    /// The ante is lost, the call was never made
+    Fold,
+    /// The ante pays by the paytable and the call is returned
+    DealerDoesNotQualify,
+    /// The ante pays by the paytable and the call pays 1 to 1
+    Win,
+    Lose,
+    /// Ante and call are returned
+    Push,
+}
+
+/// Outcome of a called hand and everything returned to the player, stakes included
+pub fn settle(
+    player: &[StandardCard],
+    dealer: &[StandardCard],
+    board: &[StandardCard],
+    ante: u64,
+    rules: &Rules,
+) -> Option<(HoldemOutcome, u64)> {
+    let call = ante * rules.call_multiplier;
+    let player_rank = best_hand(&[player, board].concat())?.rank;
+    let dealer_rank = best_hand(&[dealer, board].concat())?.rank;
+    let ante_win = ante + ante * rules.ante_pays(&player_rank);
+
+    Some(if !rules.qualifies(&dealer_rank) {
+        (HoldemOutcome::DealerDoesNotQualify, ante_win + call)
+    } else if player_rank > dealer_rank {
+        (HoldemOutcome::Win, ante_win + call * 2)
+    } else if player_rank < dealer_rank {
+        (HoldemOutcome::Lose, 0)
+    } else {
+        (HoldemOutcome::Push, ante + call)
+    })
+}
+
+/// A hand after the ante, or once called or folded
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HoldemHand {
+    /// Hole cards of the player
+    pub player: Vec<String>,
+    /// The flop, then all 5 cards once called
+    pub board: Vec<String>,
+    /// Hole cards of the dealer, hidden until the hand is over
+    pub dealer: Option<Vec<String>>,
+    /// Best hand of the player with the board dealt so far
+    pub player_hand: String,
+    pub dealer_hand: Option<String>,
+    pub dealer_qualifies: Option<bool>,
+    /// None until called or folded
+    pub outcome: Option<HoldemOutcome>,
+    pub ante: u64,
+    /// 0 unless called
+    pub call: u64,
+    /// The ante takes the bet, the call takes the call and pays everything returned
+    pub gain: i64,
+}
+
+impl HoldemHand {
+    /// The hand right after the ante, with the flop dealt
+    pub fn dealt(
+        player: &[StandardCard],
+        flop: &[StandardCard],
+        ante: u64,
+    ) -> Result<Self, HoldemError> {
+        Ok(Self {
+            player: c![card.to_string(), for card in player],
+            board: c![card.to_string(), for card in flop],
+            dealer: None,
+            player_hand: best_hand(&[player, flop].concat())
+                .ok_or(HoldemError::NoCard)?
+                .rank
+                .description(),
+            dealer_hand: None,
+            dealer_qualifies: None,
+            outcome: None,
+            ante,
+            call: 0,
+            gain: -(ante as i64),
+        })
+    }
+
+    /// The hand once folded, the dealer's cards are shown but nothing else is dealt
+    pub fn folded(
+        player: &[StandardCard],
+        dealer: &[StandardCard],
+        flop: &[StandardCard],
+        ante: u64,
+    ) -> Result<Self, HoldemError> {
+        let mut hand = Self::dealt(player, flop, ante)?;
+        hand.dealer = Some(c![card.to_string(), for card in dealer]);
+        hand.outcome = Some(HoldemOutcome::Fold);
+        hand.gain = 0;
+        Ok(hand)
+    }
+
+    /// The hand once called, `board` holds all 5 cards
+    pub fn called(
+        player: &[StandardCard],
+        dealer: &[StandardCard],
+        board: &[StandardCard],
+        ante: u64,
+        rules: &Rules,
+    ) -> Result<Self, HoldemError> {
+        let call = ante * rules.call_multiplier;
+        let (outcome, returned) =
+            settle(player, dealer, board, ante, rules).ok_or(HoldemError::NoCard)?;
+        let dealer_rank = best_hand(&[dealer, board].concat())
+            .ok_or(HoldemError::NoCard)?
+            .rank;
+
+        let mut hand = Self::dealt(player, board, ante)?;
+        hand.dealer = Some(c![card.to_string(), for card in dealer]);
+        hand.dealer_hand = Some(dealer_rank.description());
+        hand.dealer_qualifies = Some(rules.qualifies(&dealer_rank));
+        hand.outcome = Some(outcome);
+        hand.call = call;
+        hand.gain = returned as i64 - call as i64;
+        Ok(hand)
+    }
+}
+
+/// Hands are stored from the ante until called or folded
+#[cfg(feature = "auto_save")]
+pub struct Holdem;
+
+#[cfg(feature = "auto_save")]
+impl Holdem {
+    fn cards(cards: &[String]) -> Result<Vec<StandardCard>, HoldemError> {
+        let mut parsed = Vec::with_capacity(cards.len());
+        for card in cards {
+            parsed.push(card.parse::<StandardCard>()?);
+        }
+        Ok(parsed)
+    }
+
+    fn deal(deck: &mut StandardDeck, count: usize) -> Result<Vec<StandardCard>, HoldemError> {
+        let mut dealt = Vec::with_capacity(count);
+        for _ in 0..count {
+            dealt.push(deck.draw()?);
+        }
+        Ok(dealt)
+    }
+
+    /// Removes the stored hand of `player`, so it can only be played once
+    fn take(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<HoldemSession, HoldemError> {
+        use schema::holdem::dsl::*;
+
+        let conn = db_pool.get()?;
+        diesel::delete(
+            holdem
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64)),
+        ).get_result::<HoldemSession>(&*conn)
+            .optional()?
+            .ok_or(HoldemError::NoHand)
+    }
+
+    /// Hand `player` anted and hasn't called or folded yet
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Option<HoldemHand>, HoldemError> {
+        use schema::holdem::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = holdem
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<HoldemSession>(&*conn)
+            .optional()?;
+
+        match session {
+            Some(session) => Ok(Some(HoldemHand::dealt(
+                &Self::cards(&session.player_cards)?,
+                &Self::cards(&session.board)?,
+                session.ante as u64,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Takes `new_ante` and deals the hole cards and the flop
+    pub fn ante(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        new_ante: u64,
+    ) -> Result<HoldemHand, HoldemError> {
+        use schema::holdem;
+
+        let conn = db_pool.get()?;
+        let mut new_deck = StandardDeck::new();
+        let player_hole = Self::deal(&mut new_deck, 2)?;
+        let dealer_hole = Self::deal(&mut new_deck, 2)?;
+        let flop = Self::deal(&mut new_deck, 3)?;
+
+        // Only one hand per player, an ante racing another one inserts nothing
+        let inserted = diesel::insert_into(holdem::table)
+            .values(&HoldemSession {
+                namespace: player_namespace,
+                id: player as i64,
+                ante: new_ante as i64,
+                player_cards: c![card.to_string(), for card in &player_hole],
+                dealer_cards: c![card.to_string(), for card in &dealer_hole],
+                board: c![card.to_string(), for card in &flop],
+                deck: new_deck.export(),
+            })
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+
+        if inserted != 1 {
+            return Err(HoldemError::HandInProgress);
+        }
+
+        HoldemHand::dealt(&player_hole, &flop, new_ante)
+    }
+
+    /// Gives up the ante
+    pub fn fold(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<HoldemHand, HoldemError> {
+        let session = Self::take(db_pool, player_namespace, player)?;
+
+        let result = HoldemHand::folded(
+            &Self::cards(&session.player_cards)?,
+            &Self::cards(&session.dealer_cards)?,
+            &Self::cards(&session.board)?,
+            session.ante as u64,
+        )?;
+        METRICS.record_game("holdem", result.ante, 0);
+
+        Ok(result)
+    }
+
+    /// Takes the call, deals the turn and river and settles against the dealer
+    pub fn call(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        rules: &Rules,
+    ) -> Result<HoldemHand, HoldemError> {
+        let session = Self::take(db_pool, player_namespace, player)?;
+
+        let mut board = Self::cards(&session.board)?;
+        let mut remaining = StandardDeck {
+            cards: Self::cards(&session.deck)?,
+        };
+        board.extend(Self::deal(&mut remaining, 2)?);
+
+        let result = HoldemHand::called(
+            &Self::cards(&session.player_cards)?,
+            &Self::cards(&session.dealer_cards)?,
+            &board,
+            session.ante as u64,
+            rules,
+        )?;
+        METRICS.record_game(
+            "holdem",
+            result.ante + result.call,
+            (result.gain + result.call as i64) as u64,
+        );
+
+        Ok(result)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HoldemResponse {
+    pub status_code: u16,
+    pub status: Result<HoldemHand, String>,
+}
+
+impl HoldemResponse {
+    pub fn new(result: Result<HoldemHand, HoldemError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HoldemStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when no hand is waiting for a call or fold
+    pub status: Result<Option<HoldemHand>, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl HoldemStatusResponse {
+    pub fn new(result: Result<Option<HoldemHand>, HoldemError>) -> Self {
+        match result {
+            Ok(hand) => Self {
+                status_code: 200,
+                status: Ok(hand),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,7 @@
+mod error;
+mod holdem_game;
+
+pub use self::error::HoldemError;
+pub use self::holdem_game::{settle, HoldemHand, HoldemOutcome, HoldemResponse, Rules};
+#[cfg(feature = "auto_save")]
+pub use self::holdem_game::{Holdem, HoldemStatusResponse};
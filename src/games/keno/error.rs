@@ -0,0 +1,52 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum KenoError {
+    DuplicateNumber(u8),
+    InvalidBet(String),
+    /// The number as it was given
+    InvalidNumber(String),
+    /// How many numbers were picked
+    InvalidPicks(usize),
+}
+
+impl Display for KenoError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            KenoError::DuplicateNumber(number) => write!(f, "{} was picked twice", number),
+            KenoError::InvalidBet(ref why) => f.write_str(why),
+            KenoError::InvalidNumber(ref number) => write!(
+                f,
+                "{} isn't a number from 1 to {}, pick like `3-17-42`",
+                number,
+                super::NUMBERS
+            ),
+            KenoError::InvalidPicks(picks) => write!(
+                f,
+                "Pick 1 to {} numbers, not {}",
+                super::MAX_PICKS,
+                picks
+            ),
+        }
+    }
+}
+
+impl StdError for KenoError {
+    fn description(&self) -> &str {
+        use self::KenoError::*;
+        match *self {
+            DuplicateNumber(_) => "A number was picked twice",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidNumber(_) => "Invalid number",
+            InvalidPicks(_) => "Invalid number of picks",
+        }
+    }
+}
+
+impl KenoError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        501
+    }
+}
@@ -0,0 +1,256 @@
+use metrics::{payout, METRICS};
+use rand::{thread_rng, Rng};
+use super::KenoError;
+
+/// Numbers on the board, 1 through 80
+pub const NUMBERS: u8 = 80;
+/// Numbers drawn every game
+pub const DRAWN: usize = 20;
+pub const MAX_PICKS: usize = 10;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Returned amount as a multiple of the bet, by picks and then hits
+    /// `paytable[2][3]` is 3 of 3 picks hit, every row has an entry for 0 hits
+    pub paytable: Vec<Vec<u64>>,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            paytable: vec![
+                vec![0, 3],
+                vec![0, 1, 9],
+                vec![0, 0, 2, 44],
+                vec![0, 0, 1, 6, 150],
+                vec![0, 0, 0, 3, 14, 700],
+                vec![0, 0, 0, 2, 5, 80, 1800],
+                vec![0, 0, 0, 1, 2, 20, 400, 7000],
+                vec![0, 0, 0, 0, 2, 10, 80, 1500, 25000],
+                vec![0, 0, 0, 0, 1, 5, 50, 350, 5000, 50000],
+                vec![3, 0, 0, 0, 0, 3, 20, 150, 1000, 5000, 100000],
+            ],
+        }
+    }
+}
+
+impl Rules {
+    /// Returned amount as a multiple of the bet when `hits` of `picks` numbers are drawn
+    pub fn pays(&self, picks: usize, hits: usize) -> u64 {
+        self.paytable
+            .get(picks.wrapping_sub(1))
+            .and_then(|row| row.get(hits))
+            .cloned()
+            .unwrap_or(0)
+    }
+
+    /// Share of every bet on `picks` numbers paid back in the long run
+    pub fn return_to_player(&self, picks: usize) -> f64 {
+        (0..picks + 1)
+            .map(|hits| probability(picks, hits) * self.pays(picks, hits) as f64)
+            .sum()
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.paytable.len() != MAX_PICKS {
+            problems.push(format!(
+                "keno.paytable needs a row for each of 1 to {} picks",
+                MAX_PICKS
+            ));
+        }
+        for (i, row) in self.paytable.iter().enumerate() {
+            if row.len() != i + 2 {
+                problems.push(format!(
+                    "keno.paytable row {} needs {} entries, for 0 to {} hits",
+                    i + 1,
+                    i + 2,
+                    i + 1
+                ));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Ways of picking `k` of `n`
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |ways, i| ways * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// Exact chance of `hits` of `picks` numbers being drawn
+pub fn probability(picks: usize, hits: usize) -> f64 {
+    let numbers = NUMBERS as usize;
+    if hits > picks || hits > DRAWN || picks - hits > numbers - DRAWN {
+        return 0.0;
+    }
+
+    binomial(DRAWN, hits) * binomial(numbers - DRAWN, picks - hits) / binomial(numbers, picks)
+}
+
+/// Numbers a player picked, sorted
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Picks(pub Vec<u8>);
+
+impl Picks {
+    /// Parses picks like `3-17-42`
+    pub fn parse(input: &str) -> Result<Self, KenoError> {
+        let mut numbers = Vec::new();
+        for number in input.split('-') {
+            match number.trim().parse::<u8>() {
+                Ok(n) if n >= 1 && n <= NUMBERS => numbers.push(n),
+                _ => return Err(KenoError::InvalidNumber(number.to_string())),
+            }
+        }
+        Self::new(numbers)
+    }
+
+    pub fn new(mut numbers: Vec<u8>) -> Result<Self, KenoError> {
+        if numbers.is_empty() || numbers.len() > MAX_PICKS {
+            return Err(KenoError::InvalidPicks(numbers.len()));
+        }
+        if let Some(&number) = numbers.iter().find(|&&n| n == 0 || n > NUMBERS) {
+            return Err(KenoError::InvalidNumber(number.to_string()));
+        }
+
+        numbers.sort();
+        if let Some(pair) = numbers.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(KenoError::DuplicateNumber(pair[0]));
+        }
+        Ok(Picks(numbers))
+    }
+
+    /// Quick pick of `count` numbers
+    pub fn random(count: usize) -> Result<Self, KenoError> {
+        quick_pick(&mut thread_rng(), count)
+    }
+}
+
+/// `count` different numbers of the board, sorted
+fn sample<R: Rng>(rng: &mut R, count: usize) -> Vec<u8> {
+    let mut numbers: Vec<u8> = (1..NUMBERS + 1).collect();
+    rng.shuffle(&mut numbers);
+    numbers.truncate(count);
+    numbers.sort();
+    numbers
+}
+
+/// Draws the 20 numbers of a game
+pub fn draw<R: Rng>(rng: &mut R) -> Vec<u8> {
+    sample(rng, DRAWN)
+}
+
+/// Picks `count` random numbers for the player
+pub fn quick_pick<R: Rng>(rng: &mut R, count: usize) -> Result<Picks, KenoError> {
+    if count == 0 || count > MAX_PICKS {
+        return Err(KenoError::InvalidPicks(count));
+    }
+    Ok(Picks(sample(rng, count)))
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KenoGame {
+    pub picks: Vec<u8>,
+    pub drawn: Vec<u8>,
+    /// Picks that were drawn
+    pub hits: Vec<u8>,
+    /// Returned amount as a multiple of the bet
+    pub pays: u64,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Settles `bet` on `picks` against the `drawn` numbers
+pub fn play(bet: u64, picks: &Picks, drawn: Vec<u8>, rules: &Rules) -> KenoGame {
+    let hits = c![*pick, for pick in &picks.0, if drawn.contains(pick)];
+    let pays = rules.pays(picks.0.len(), hits.len());
+
+    KenoGame {
+        picks: picks.0.clone(),
+        drawn,
+        hits,
+        pays,
+        bet,
+        gain: (bet * pays) as i64 - bet as i64,
+    }
+}
+
+/// Draws a game for `bet` on `picks`
+pub fn keno(bet: u64, picks: &Picks, rules: &Rules) -> KenoGame {
+    let game = play(bet, picks, draw(&mut thread_rng()), rules);
+    METRICS.record_game("keno", bet, payout(bet, game.gain));
+    game
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableEntry {
+    pub hits: usize,
+    pub probability: f64,
+    /// Returned amount as a multiple of the bet
+    pub pays: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableRow {
+    pub picks: usize,
+    pub entries: Vec<PaytableEntry>,
+    /// Share of the bets paid back in the long run
+    pub return_to_player: f64,
+}
+
+/// Every row of the paytable with the odds of each entry
+pub fn paytable(rules: &Rules) -> Vec<PaytableRow> {
+    c![PaytableRow {
+        picks,
+        entries: c![PaytableEntry {
+            hits,
+            probability: probability(picks, hits),
+            pays: rules.pays(picks, hits),
+        }, for hits in 0..picks + 1],
+        return_to_player: rules.return_to_player(picks),
+    }, for picks in 1..MAX_PICKS + 1]
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct KenoResponse {
+    pub status_code: u16,
+    pub status: Result<KenoGame, String>,
+}
+
+impl KenoResponse {
+    pub fn new(result: Result<KenoGame, KenoError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<PaytableRow>, String>,
+}
+
+impl PaytableResponse {
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(paytable(rules)),
+        }
+    }
+}
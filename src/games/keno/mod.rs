@@ -0,0 +1,7 @@
+mod error;
+mod keno_game;
+
+pub use self::error::KenoError;
+pub use self::keno_game::{draw, keno, paytable, play, probability, quick_pick, KenoGame,
+                          KenoResponse, PaytableEntry, PaytableResponse, PaytableRow, Picks,
+                          Rules, DRAWN, MAX_PICKS, NUMBERS};
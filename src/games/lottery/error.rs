@@ -0,0 +1,86 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum LotteryError {
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    /// The draw already took place
+    DrawClosed(u64),
+    InvalidBet(String),
+    /// The ticket as it was given
+    InvalidTicket(String),
+    /// The draw hasn't taken place, or had no tickets
+    NoDraw(u64),
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+    /// Most tickets one user can hold for a draw
+    TicketLimit(u32),
+}
+
+impl Display for LotteryError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            LotteryError::DrawClosed(draw) => write!(f, "Draw {} is closed", draw),
+            LotteryError::InvalidBet(ref why) => f.write_str(why),
+            LotteryError::InvalidTicket(ref ticket) => write!(
+                f,
+                "{} isn't a ticket, pick different numbers like `3-7-12`",
+                ticket
+            ),
+            LotteryError::NoDraw(draw) => write!(f, "Draw {} hasn't taken place", draw),
+            LotteryError::TicketLimit(max) => {
+                write!(f, "At most {} tickets per user for a draw", max)
+            }
+            #[cfg(feature = "auto_save")]
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for LotteryError {
+    fn description(&self) -> &str {
+        use self::LotteryError::*;
+        match *self {
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            DrawClosed(_) => "The draw is closed",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidTicket(_) => "Invalid ticket",
+            NoDraw(_) => "No such draw",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+            TicketLimit(_) => "Too many tickets",
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for LotteryError {
+    fn from(err: DieselResultError) -> Self {
+        LotteryError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for LotteryError {
+    fn from(err: R2d2Error) -> Self {
+        LotteryError::R2d2(err)
+    }
+}
+
+impl LotteryError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::LotteryError::*;
+        match *self {
+            DrawClosed(_) | InvalidBet(_) | InvalidTicket(_) | NoDraw(_) | TicketLimit(_) => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
@@ -0,0 +1,692 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use diesel::sql_types::{Array, BigInt, Integer};
+#[cfg(feature = "auto_save")]
+use metrics::METRICS;
+#[cfg(feature = "auto_save")]
+use models::{LotteryDraw, LotteryTicket};
+#[cfg(feature = "auto_save")]
+use rand::thread_rng;
+use rand::Rng;
+#[cfg(feature = "auto_save")]
+use std::cmp;
+#[cfg(feature = "auto_save")]
+use std::thread::{self, JoinHandle};
+#[cfg(feature = "auto_save")]
+use std::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::LotteryError;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+#[cfg(feature = "auto_save")]
+use PgConnection;
+
+/// Longest wait before retrying draws that failed to settle
+#[cfg(feature = "auto_save")]
+const RETRY_SECS: u64 = 60;
+/// Most recent tickets listed for a user
+#[cfg(feature = "auto_save")]
+const LISTED_TICKETS: i64 = 50;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Seconds between draws, a draw closes on every multiple of this since the unix epoch
+    pub interval: u64,
+    pub ticket_price: u64,
+    /// Numbers on a ticket
+    pub picks: u8,
+    /// Tickets pick from 1 to this
+    pub numbers: u8,
+    /// Kept from ticket sales, the rest goes to the pool
+    pub house_cut: f64,
+    /// Most tickets one user can hold for a draw
+    pub max_tickets: u32,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            interval: 3600,
+            ticket_price: 10,
+            picks: 3,
+            numbers: 15,
+            house_cut: 0.1,
+            max_tickets: 10,
+        }
+    }
+}
+
+impl Rules {
+    /// Draw tickets bought at unix `time` go to
+    pub fn draw_at(&self, time: u64) -> u64 {
+        time / self.interval
+    }
+
+    /// Unix time `draw` closes at
+    pub fn closes_at(&self, draw: u64) -> u64 {
+        (draw + 1) * self.interval
+    }
+
+    /// Parses a ticket like `3-7-12`, sorted
+    pub fn ticket(&self, input: &str) -> Result<Vec<u8>, LotteryError> {
+        let invalid = || LotteryError::InvalidTicket(input.to_string());
+        let mut numbers = Vec::with_capacity(self.picks as usize);
+        for number in input.split('-') {
+            match number.trim().parse::<u8>() {
+                Ok(n) if n >= 1 && n <= self.numbers => numbers.push(n),
+                _ => return Err(invalid()),
+            }
+        }
+
+        numbers.sort();
+        numbers.dedup();
+        if numbers.len() != self.picks as usize {
+            return Err(invalid());
+        }
+        Ok(numbers)
+    }
+
+    /// Random ticket, also how the winning numbers are drawn
+    pub fn quick_pick<R: Rng>(&self, rng: &mut R) -> Vec<u8> {
+        let mut numbers: Vec<u8> = (1..u16::from(self.numbers) + 1).map(|n| n as u8).collect();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(self.picks as usize);
+        numbers.sort();
+        numbers
+    }
+
+    /// Ticket sales minus the house cut, with what rolled over from earlier draws
+    pub fn pool(&self, sales: u64, rollover: u64) -> u64 {
+        (sales as f64 * (1.0 - self.house_cut)) as u64 + rollover
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.interval == 0 {
+            problems.push(String::from("lottery.interval must be above 0"));
+        }
+        if self.ticket_price == 0 || self.ticket_price > i64::max_value() as u64 {
+            problems.push(String::from("lottery.ticket_price must be above 0"));
+        }
+        if self.picks == 0 || self.picks >= self.numbers {
+            problems.push(format!(
+                "lottery.picks ({}) must be above 0 and below lottery.numbers ({})",
+                self.picks, self.numbers
+            ));
+        }
+        if self.house_cut < 0.0 || self.house_cut >= 1.0 {
+            problems.push(String::from(
+                "lottery.house_cut must be at least 0 and below 1",
+            ));
+        }
+        if self.max_tickets == 0 {
+            problems.push(String::from("lottery.max_tickets must be above 0"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Settlement {
+    /// Indexes of the winning tickets
+    pub winners: Vec<usize>,
+    /// Paid to each winner
+    pub payout: u64,
+    /// Left for the next draw, all of the pool when nobody won
+    pub rollover: u64,
+}
+
+/// Splits `pool` between the `tickets` matching the drawn `numbers`, both sorted
+pub fn settle(tickets: &[Vec<u8>], numbers: &[u8], pool: u64) -> Settlement {
+    let winners: Vec<usize> = tickets
+        .iter()
+        .enumerate()
+        .filter(|&(_, ticket)| &ticket[..] == numbers)
+        .map(|(i, _)| i)
+        .collect();
+
+    if winners.is_empty() {
+        Settlement {
+            winners,
+            payout: 0,
+            rollover: pool,
+        }
+    } else {
+        let count = winners.len() as u64;
+        Settlement {
+            winners,
+            payout: pool / count,
+            rollover: pool % count,
+        }
+    }
+}
+
+/// Seconds since the unix epoch
+pub fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Ticket {
+    pub id: i64,
+    pub draw: u64,
+    pub numbers: Vec<u8>,
+    pub price: u64,
+    /// None until drawn
+    pub won: Option<u64>,
+    /// Winnings are paid once claimed
+    pub claimed: bool,
+}
+
+#[cfg(feature = "auto_save")]
+impl From<LotteryTicket> for Ticket {
+    fn from(ticket: LotteryTicket) -> Self {
+        Self {
+            id: ticket.id,
+            draw: ticket.draw as u64,
+            numbers: c![number as u8, for number in ticket.numbers],
+            price: ticket.price as u64,
+            won: ticket.won.map(|won| won as u64),
+            claimed: ticket.claimed,
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TicketResult {
+    pub ticket: Ticket,
+    pub closes_at: u64,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DrawResult {
+    pub draw: u64,
+    pub numbers: Vec<u8>,
+    pub tickets: u32,
+    pub pool: u64,
+    pub winners: u32,
+    /// Paid to each winner
+    pub payout: u64,
+    /// Carried into the next draw
+    pub rollover: u64,
+    pub drawn_at: u64,
+}
+
+#[cfg(feature = "auto_save")]
+impl From<LotteryDraw> for DrawResult {
+    fn from(draw: LotteryDraw) -> Self {
+        Self {
+            draw: draw.id as u64,
+            numbers: c![number as u8, for number in draw.numbers],
+            tickets: draw.tickets as u32,
+            pool: draw.pool as u64,
+            winners: draw.winners as u32,
+            payout: draw.payout as u64,
+            rollover: draw.rollover as u64,
+            drawn_at: draw.drawn_at as u64,
+        }
+    }
+}
+
+/// The draw tickets are currently sold for
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CurrentDraw {
+    pub draw: u64,
+    pub closes_at: u64,
+    pub ticket_price: u64,
+    pub picks: u8,
+    pub numbers: u8,
+    pub tickets: u64,
+    /// Pool if the draw closed now
+    pub pool: u64,
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Claimed {
+    /// Winning tickets paid by the claim
+    pub tickets: usize,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Tickets and draw results are stored, the draws are settled by `schedule`
+#[cfg(feature = "auto_save")]
+pub struct Lottery;
+
+#[cfg(feature = "auto_save")]
+impl Lottery {
+    /// Buying holds a shared lock on `draw` and settling an exclusive one,
+    /// so no ticket is sold while its draw is being settled
+    fn lock_draw(conn: &PgConnection, draw: u64, exclusive: bool) -> QueryResult<()> {
+        let query = if exclusive {
+            "SELECT pg_advisory_xact_lock($1)"
+        } else {
+            "SELECT pg_advisory_xact_lock_shared($1)"
+        };
+
+        diesel::sql_query(query)
+            .bind::<BigInt, _>(draw as i64)
+            .execute(conn)
+            .map(|_| ())
+    }
+
+    /// Rollover of the last draw before `draw`, 0 if there is none
+    fn rollover(conn: &PgConnection, draw: u64) -> QueryResult<u64> {
+        use schema::lottery_draws;
+
+        let rollover = lottery_draws::table
+            .select(lottery_draws::rollover)
+            .filter(lottery_draws::id.lt(draw as i64))
+            .order(lottery_draws::id.desc())
+            .first::<i64>(conn)
+            .optional()?;
+        Ok(rollover.map_or(0, |rollover| rollover as u64))
+    }
+
+    /// Draw open at unix `now` and its pool so far
+    pub fn current(
+        db_pool: &ConnectionPool,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<CurrentDraw, LotteryError> {
+        use schema::lottery_tickets;
+
+        let draw = rules.draw_at(now);
+        let conn = db_pool.get()?;
+        let prices = lottery_tickets::table
+            .select(lottery_tickets::price)
+            .filter(lottery_tickets::draw.eq(draw as i64))
+            .load::<i64>(&*conn)?;
+        let sales = prices.iter().map(|&price| price as u64).sum();
+
+        Ok(CurrentDraw {
+            draw,
+            closes_at: rules.closes_at(draw),
+            ticket_price: rules.ticket_price,
+            picks: rules.picks,
+            numbers: rules.numbers,
+            tickets: prices.len() as u64,
+            pool: rules.pool(sales, Self::rollover(&*conn, draw)?),
+        })
+    }
+
+    /// Buys `player` a ticket for the draw open at unix `now`, a quick pick without `numbers`
+    pub fn buy(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        numbers: Option<&str>,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<TicketResult, LotteryError> {
+        use schema::lottery_draws;
+
+        let numbers = match numbers {
+            Some(numbers) => rules.ticket(numbers)?,
+            None => rules.quick_pick(&mut thread_rng()),
+        };
+        let draw = rules.draw_at(now);
+
+        let conn = db_pool.get()?;
+        let ticket = conn.transaction::<_, LotteryError, _>(|| {
+            Self::lock_draw(&*conn, draw, false)?;
+
+            // Sold only while the draw isn't settled and the user holds less than max_tickets
+            let ticket = diesel::sql_query(
+                "INSERT INTO lottery_tickets (draw, namespace, user_id, numbers, price) \
+                 SELECT $1, $2, $3, $4, $5 \
+                 WHERE NOT EXISTS (SELECT 1 FROM lottery_draws WHERE id = $1) \
+                 AND (SELECT COUNT(*) FROM lottery_tickets \
+                 WHERE draw = $1 AND namespace = $2 AND user_id = $3) < $6 \
+                 RETURNING *",
+            ).bind::<BigInt, _>(draw as i64)
+                .bind::<BigInt, _>(player_namespace)
+                .bind::<BigInt, _>(player as i64)
+                .bind::<Array<Integer>, _>(c![i32::from(*number), for number in &numbers])
+                .bind::<BigInt, _>(rules.ticket_price as i64)
+                .bind::<BigInt, _>(i64::from(rules.max_tickets))
+                .get_result::<LotteryTicket>(&*conn)
+                .optional()?;
+
+            match ticket {
+                Some(ticket) => Ok(ticket),
+                None => {
+                    let drawn: i64 = lottery_draws::table
+                        .filter(lottery_draws::id.eq(draw as i64))
+                        .count()
+                        .get_result(&*conn)?;
+                    if drawn != 0 {
+                        Err(LotteryError::DrawClosed(draw))
+                    } else {
+                        Err(LotteryError::TicketLimit(rules.max_tickets))
+                    }
+                }
+            }
+        })?;
+
+        Ok(TicketResult {
+            ticket: Ticket::from(ticket),
+            closes_at: rules.closes_at(draw),
+            bet: rules.ticket_price,
+            gain: -(rules.ticket_price as i64),
+        })
+    }
+
+    /// Most recent tickets of `player`
+    pub fn tickets(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Vec<Ticket>, LotteryError> {
+        use schema::lottery_tickets;
+
+        let conn = db_pool.get()?;
+        let tickets = lottery_tickets::table
+            .filter(lottery_tickets::namespace.eq(player_namespace))
+            .filter(lottery_tickets::user_id.eq(player as i64))
+            .order(lottery_tickets::id.desc())
+            .limit(LISTED_TICKETS)
+            .load::<LotteryTicket>(&*conn)?;
+
+        Ok(c![Ticket::from(ticket), for ticket in tickets])
+    }
+
+    /// Pays every winning ticket of `player` that wasn't claimed yet
+    pub fn claim(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Claimed, LotteryError> {
+        use schema::lottery_tickets;
+
+        let conn = db_pool.get()?;
+        // Marking and reading in one statement keeps winnings from being paid twice
+        let winnings = diesel::update(
+            lottery_tickets::table
+                .filter(lottery_tickets::namespace.eq(player_namespace))
+                .filter(lottery_tickets::user_id.eq(player as i64))
+                .filter(lottery_tickets::won.gt(0))
+                .filter(lottery_tickets::claimed.eq(false)),
+        ).set(lottery_tickets::claimed.eq(true))
+            .returning(lottery_tickets::won)
+            .get_results::<Option<i64>>(&*conn)?;
+
+        Ok(Claimed {
+            tickets: winnings.len(),
+            bet: 0,
+            gain: winnings.iter().map(|won| won.unwrap_or(0)).sum(),
+        })
+    }
+
+    /// Result of a settled draw
+    pub fn result(db_pool: &ConnectionPool, draw: u64) -> Result<DrawResult, LotteryError> {
+        use schema::lottery_draws;
+
+        let conn = db_pool.get()?;
+        lottery_draws::table
+            .find(draw as i64)
+            .first::<LotteryDraw>(&*conn)
+            .optional()?
+            .map(DrawResult::from)
+            .ok_or(LotteryError::NoDraw(draw))
+    }
+
+    /// Draws the numbers of `draw` and pays its winners, None if it had no tickets
+    /// or was settled elsewhere
+    fn settle_draw(
+        conn: &PgConnection,
+        draw: u64,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<Option<DrawResult>, LotteryError> {
+        use schema::{lottery_draws, lottery_tickets};
+
+        let result = conn.transaction::<_, LotteryError, _>(|| {
+            Self::lock_draw(conn, draw, true)?;
+
+            let tickets = lottery_tickets::table
+                .filter(lottery_tickets::draw.eq(draw as i64))
+                .filter(lottery_tickets::won.is_null())
+                .order(lottery_tickets::id.asc())
+                .load::<LotteryTicket>(conn)?;
+            if tickets.is_empty() {
+                return Ok(None);
+            }
+
+            let sales = tickets.iter().map(|ticket| ticket.price as u64).sum::<u64>();
+            let pool = rules.pool(sales, Self::rollover(conn, draw)?);
+            let numbers = rules.quick_pick(&mut thread_rng());
+            let picked = c![
+                ticket.numbers.iter().map(|&number| number as u8).collect::<Vec<_>>(),
+                for ticket in &tickets
+            ];
+            let settlement = settle(&picked, &numbers, pool);
+
+            let record = LotteryDraw {
+                id: draw as i64,
+                numbers: c![i32::from(number), for number in numbers],
+                tickets: tickets.len() as i32,
+                pool: pool as i64,
+                winners: settlement.winners.len() as i32,
+                payout: settlement.payout as i64,
+                rollover: settlement.rollover as i64,
+                drawn_at: now as i64,
+            };
+            // Another server settling the same draw keeps this one from paying it twice
+            let inserted = diesel::insert_into(lottery_draws::table)
+                .values(&record)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            if inserted != 1 {
+                return Ok(None);
+            }
+
+            let settled = c![ticket.id, for ticket in &tickets];
+            diesel::update(lottery_tickets::table.filter(lottery_tickets::id.eq_any(settled)))
+                .set(lottery_tickets::won.eq(0))
+                .execute(conn)?;
+            let winners: Vec<i64> = settlement.winners.iter().map(|&i| tickets[i].id).collect();
+            diesel::update(lottery_tickets::table.filter(lottery_tickets::id.eq_any(winners)))
+                .set(lottery_tickets::won.eq(settlement.payout as i64))
+                .execute(conn)?;
+
+            Ok(Some((sales, DrawResult::from(record))))
+        })?;
+
+        Ok(result.map(|(sales, result)| {
+            METRICS.record_game(
+                "lottery",
+                sales,
+                result.payout * u64::from(result.winners),
+            );
+            result
+        }))
+    }
+
+    /// Settles every draw closed before unix `now`, oldest first so rollovers carry over
+    pub fn settle_due(
+        db_pool: &ConnectionPool,
+        rules: &Rules,
+        now: u64,
+    ) -> Result<Vec<DrawResult>, LotteryError> {
+        use schema::lottery_tickets;
+
+        let conn = db_pool.get()?;
+        let due = lottery_tickets::table
+            .select(lottery_tickets::draw)
+            .filter(lottery_tickets::won.is_null())
+            .filter(lottery_tickets::draw.lt(rules.draw_at(now) as i64))
+            .distinct()
+            .order(lottery_tickets::draw.asc())
+            .load::<i64>(&*conn)?;
+
+        let mut results = Vec::with_capacity(due.len());
+        for draw in due {
+            if let Some(result) = Self::settle_draw(&*conn, draw as u64, rules, now)? {
+                results.push(result);
+            }
+        }
+        Ok(results)
+    }
+}
+
+/// Settles the lottery in the background, right after every draw closes
+#[cfg(feature = "auto_save")]
+pub fn schedule(db_pool: ConnectionPool, rules: Rules) -> JoinHandle<()> {
+    thread::spawn(move || loop {
+        let now = unix_time();
+        let settled = Lottery::settle_due(&db_pool, &rules, now);
+        match settled {
+            Ok(ref results) => for result in results {
+                info!(
+                    "Lottery draw {} drew {:?}, {} winners of {} tickets",
+                    result.draw, result.numbers, result.winners, result.tickets
+                );
+            },
+            Err(ref why) => error!("Failed to settle the lottery: {}", why),
+        }
+
+        // A second late, so tickets bought right before the close are in
+        let mut wait = rules.closes_at(rules.draw_at(now)) - now + 1;
+        if settled.is_err() {
+            wait = cmp::min(wait, RETRY_SECS);
+        }
+        thread::sleep(Duration::from_secs(wait));
+    })
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CurrentDrawResponse {
+    pub status_code: u16,
+    pub status: Result<CurrentDraw, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl CurrentDrawResponse {
+    pub fn new(result: Result<CurrentDraw, LotteryError>) -> Self {
+        match result {
+            Ok(draw) => Self {
+                status_code: 200,
+                status: Ok(draw),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TicketResponse {
+    pub status_code: u16,
+    pub status: Result<TicketResult, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl TicketResponse {
+    pub fn new(result: Result<TicketResult, LotteryError>) -> Self {
+        match result {
+            Ok(ticket) => Self {
+                status_code: 200,
+                status: Ok(ticket),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TicketsResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<Ticket>, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl TicketsResponse {
+    pub fn new(result: Result<Vec<Ticket>, LotteryError>) -> Self {
+        match result {
+            Ok(tickets) => Self {
+                status_code: 200,
+                status: Ok(tickets),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ClaimResponse {
+    pub status_code: u16,
+    pub status: Result<Claimed, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl ClaimResponse {
+    pub fn new(result: Result<Claimed, LotteryError>) -> Self {
+        match result {
+            Ok(claimed) => Self {
+                status_code: 200,
+                status: Ok(claimed),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DrawResponse {
+    pub status_code: u16,
+    pub status: Result<DrawResult, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl DrawResponse {
+    pub fn new(result: Result<DrawResult, LotteryError>) -> Self {
+        match result {
+            Ok(draw) => Self {
+                status_code: 200,
+                status: Ok(draw),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
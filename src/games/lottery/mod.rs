@@ -0,0 +1,9 @@
+mod error;
+mod lottery_game;
+
+pub use self::error::LotteryError;
+pub use self::lottery_game::{settle, unix_time, Rules, Settlement};
+#[cfg(feature = "auto_save")]
+pub use self::lottery_game::{schedule, ClaimResponse, Claimed, CurrentDraw, CurrentDrawResponse,
+                             DrawResponse, DrawResult, Lottery, Ticket, TicketResponse,
+                             TicketResult, TicketsResponse};
@@ -0,0 +1,96 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum MinesError {
+    AlreadyRevealed(u8),
+    /// The game was played by another request
+    Conflict,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    GameInProgress,
+    InvalidBet(String),
+    /// Mines outside of 1 to 24
+    InvalidMines(u8),
+    /// Tiles outside of 0 to 24
+    InvalidTile(u8),
+    NoGame,
+    /// Cashing out needs at least one safe tile
+    NothingRevealed,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+}
+
+impl Display for MinesError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            MinesError::AlreadyRevealed(tile) => write!(f, "Tile {} is already revealed", tile),
+            MinesError::InvalidBet(ref why) => f.write_str(why),
+            MinesError::InvalidMines(mines) => write!(
+                f,
+                "A grid can't hide {} mines, pick 1 to {}",
+                mines,
+                super::TILES - 1
+            ),
+            MinesError::InvalidTile(tile) => write!(
+                f,
+                "{} isn't a tile, they go from 0 to {} row by row",
+                tile,
+                super::TILES - 1
+            ),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for MinesError {
+    fn description(&self) -> &str {
+        use self::MinesError::*;
+        match *self {
+            AlreadyRevealed(_) => "The tile is already revealed",
+            Conflict => "The game was played by another request, please try again",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            GameInProgress => "A game is already going, please cash out first",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidMines(_) => "Invalid number of mines",
+            InvalidTile(_) => "Invalid tile",
+            NoGame => "No game was started",
+            NothingRevealed => "Reveal a tile before cashing out",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for MinesError {
+    fn from(err: DieselResultError) -> Self {
+        MinesError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for MinesError {
+    fn from(err: R2d2Error) -> Self {
+        MinesError::R2d2(err)
+    }
+}
+
+impl MinesError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::MinesError::*;
+        match *self {
+            Conflict => 409,
+            AlreadyRevealed(_) | GameInProgress | InvalidBet(_) | InvalidMines(_)
+            | InvalidTile(_) | NoGame | NothingRevealed => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
@@ -0,0 +1,422 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use metrics::METRICS;
+#[cfg(feature = "auto_save")]
+use models::MinesSession;
+#[cfg(feature = "auto_save")]
+use rand::thread_rng;
+use rand::Rng;
+use super::MinesError;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+/// Tiles of the 5x5 grid, numbered 0 to 24 row by row
+pub const TILES: u8 = 25;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Kept from every payout, 0.01 pays 99% of the fair odds of the revealed tiles
+    pub house_edge: f64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self { house_edge: 0.01 }
+    }
+}
+
+impl Rules {
+    /// Returned amount as a multiple of the bet after `revealed` safe tiles among `mines` mines
+    pub fn multiplier(&self, mines: u8, revealed: u8) -> f64 {
+        if revealed == 0 {
+            1.0
+        } else {
+            (1.0 - self.house_edge) / probability(mines, revealed)
+        }
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        if self.house_edge < 0.0 || self.house_edge >= 1.0 {
+            Err(vec![
+                String::from("mines.house_edge must be at least 0 and below 1"),
+            ])
+        } else {
+            Ok(())
+        }
+    }
+}
+
+fn check_mines(mines: u8) -> Result<(), MinesError> {
+    if mines == 0 || mines >= TILES {
+        Err(MinesError::InvalidMines(mines))
+    } else {
+        Ok(())
+    }
+}
+
+/// Exact chance of the first `revealed` tiles all being safe with `mines` mines
+pub fn probability(mines: u8, revealed: u8) -> f64 {
+    let safe = TILES.saturating_sub(mines);
+    if revealed > safe {
+        return 0.0;
+    }
+    (0..revealed).fold(1.0, |chance, i| {
+        chance * f64::from(safe - i) / f64::from(TILES - i)
+    })
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinesStep {
+    pub revealed: u8,
+    pub probability: f64,
+    pub multiplier: f64,
+}
+
+/// Multiplier after every number of safe tiles that can be revealed with `mines` mines
+pub fn paytable(mines: u8, rules: &Rules) -> Result<Vec<MinesStep>, MinesError> {
+    check_mines(mines)?;
+    Ok(c![MinesStep {
+        revealed,
+        probability: probability(mines, revealed),
+        multiplier: rules.multiplier(mines, revealed),
+    }, for revealed in 1..TILES - mines + 1])
+}
+
+/// Where the mines are and what was revealed
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MinesBoard {
+    /// Sorted
+    pub mines: Vec<u8>,
+    /// In the order they were revealed
+    pub revealed: Vec<u8>,
+}
+
+impl MinesBoard {
+    /// Hides `mines` mines at random
+    pub fn new<R: Rng>(rng: &mut R, mines: u8) -> Result<Self, MinesError> {
+        check_mines(mines)?;
+        let mut tiles: Vec<u8> = (0..TILES).collect();
+        rng.shuffle(&mut tiles);
+        tiles.truncate(mines as usize);
+        tiles.sort();
+
+        Ok(Self {
+            mines: tiles,
+            revealed: Vec::new(),
+        })
+    }
+
+    /// Reveals `tile`, true if it hid a mine
+    pub fn reveal(&mut self, tile: u8) -> Result<bool, MinesError> {
+        if tile >= TILES {
+            return Err(MinesError::InvalidTile(tile));
+        }
+        if self.revealed.contains(&tile) {
+            return Err(MinesError::AlreadyRevealed(tile));
+        }
+
+        self.revealed.push(tile);
+        Ok(self.mines.contains(&tile))
+    }
+
+    /// True once every safe tile is revealed
+    pub fn cleared(&self) -> bool {
+        self.revealed.len() + self.mines.len() == TILES as usize
+    }
+}
+
+/// A game as the player sees it, the mines stay hidden
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinesGame {
+    pub bet: u64,
+    pub mines: u8,
+    pub revealed: Vec<u8>,
+    pub multiplier: f64,
+    /// Paid on cash out, the bet times the multiplier
+    pub pot: u64,
+    /// Multiplier once the next tile turns out safe
+    pub next_multiplier: Option<f64>,
+}
+
+impl MinesGame {
+    pub fn new(bet: u64, board: &MinesBoard, rules: &Rules) -> Self {
+        let mines = board.mines.len() as u8;
+        let revealed = board.revealed.len() as u8;
+        let multiplier = rules.multiplier(mines, revealed);
+
+        Self {
+            bet,
+            mines,
+            revealed: board.revealed.clone(),
+            multiplier,
+            pot: (bet as f64 * multiplier) as u64,
+            next_multiplier: if board.cleared() {
+                None
+            } else {
+                Some(rules.multiplier(mines, revealed + 1))
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinesResult {
+    /// None when starting or cashing out
+    pub tile: Option<u8>,
+    /// Whether the revealed tile hid a mine
+    pub mine: Option<bool>,
+    /// The game after this request, None once it is lost or cashed out
+    pub game: Option<MinesGame>,
+    /// Where the mines were, shown once the game is over
+    pub mines: Option<Vec<u8>>,
+    /// Amount taken by this request, only starting a game takes a bet
+    pub bet: u64,
+    pub gain: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<MinesStep>, String>,
+}
+
+impl PaytableResponse {
+    pub fn new(result: Result<Vec<MinesStep>, MinesError>) -> Self {
+        match result {
+            Ok(steps) => Self {
+                status_code: 200,
+                status: Ok(steps),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+/// Games are stored from the start until a mine is hit or they are cashed out
+#[cfg(feature = "auto_save")]
+pub struct Mines;
+
+#[cfg(feature = "auto_save")]
+impl Mines {
+    fn board(session: &MinesSession) -> MinesBoard {
+        MinesBoard {
+            mines: session.mine_tiles.iter().map(|&tile| tile as u8).collect(),
+            revealed: session.revealed.iter().map(|&tile| tile as u8).collect(),
+        }
+    }
+
+    /// Game of `player`, if they have one going
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        rules: &Rules,
+    ) -> Result<Option<MinesGame>, MinesError> {
+        use schema::mines::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = mines
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<MinesSession>(&*conn)
+            .optional()?;
+
+        Ok(session.map(|session| MinesGame::new(session.bet as u64, &Self::board(&session), rules)))
+    }
+
+    /// Takes `new_bet` and hides `count` mines
+    pub fn start(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        count: u8,
+        new_bet: u64,
+        rules: &Rules,
+    ) -> Result<MinesResult, MinesError> {
+        use schema::mines;
+
+        let board = MinesBoard::new(&mut thread_rng(), count)?;
+
+        let conn = db_pool.get()?;
+        let inserted = diesel::insert_into(mines::table)
+            .values(&MinesSession {
+                namespace: player_namespace,
+                id: player as i64,
+                bet: new_bet as i64,
+                mine_tiles: board.mines.iter().map(|&tile| i32::from(tile)).collect(),
+                revealed: Vec::new(),
+            })
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+
+        // A game already under way for the player keeps its row
+        if inserted != 1 {
+            return Err(MinesError::GameInProgress);
+        }
+
+        Ok(MinesResult {
+            tile: None,
+            mine: None,
+            game: Some(MinesGame::new(new_bet, &board, rules)),
+            mines: None,
+            bet: new_bet,
+            gain: -(new_bet as i64),
+        })
+    }
+
+    /// Reveals `tile` of `player`'s game, clearing the grid cashes it out
+    pub fn reveal(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        tile: u8,
+        rules: &Rules,
+    ) -> Result<MinesResult, MinesError> {
+        use schema::mines::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = mines
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<MinesSession>(&*conn)
+            .optional()?
+            .ok_or(MinesError::NoGame)?;
+
+        let mut board = Self::board(&session);
+        let hit = board.reveal(tile)?;
+        let over = hit || board.cleared();
+
+        // Only applied if nobody else played the game since it was loaded
+        let unchanged = mines
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .filter(revealed.eq(&session.revealed));
+
+        let updated = if over {
+            diesel::delete(unchanged).execute(&*conn)?
+        } else {
+            let tiles: Vec<i32> = board.revealed.iter().map(|&tile| i32::from(tile)).collect();
+            diesel::update(unchanged)
+                .set(revealed.eq(tiles))
+                .execute(&*conn)?
+        };
+
+        if updated != 1 {
+            return Err(MinesError::Conflict);
+        }
+
+        let game = MinesGame::new(session.bet as u64, &board, rules);
+        let returned = if hit { 0 } else { game.pot };
+        if over {
+            METRICS.record_game("mines", session.bet as u64, returned);
+        }
+
+        Ok(MinesResult {
+            tile: Some(tile),
+            mine: Some(hit),
+            mines: if over { Some(board.mines) } else { None },
+            game: if over { None } else { Some(game) },
+            bet: 0,
+            gain: if over { returned as i64 } else { 0 },
+        })
+    }
+
+    /// Ends `player`'s game, paying its pot
+    pub fn cash_out(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        rules: &Rules,
+    ) -> Result<MinesResult, MinesError> {
+        use schema::mines::dsl::*;
+
+        let conn = db_pool.get()?;
+        // Deleting and reading in one statement keeps a game from being paid twice,
+        // only games with a revealed tile are cashed out
+        let session = diesel::delete(
+            mines
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64))
+                .filter(revealed.ne(Vec::<i32>::new())),
+        ).get_result::<MinesSession>(&*conn)
+            .optional()?;
+
+        let session = match session {
+            Some(session) => session,
+            None => {
+                return Err(match Self::restore(db_pool, player_namespace, player, rules)? {
+                    Some(_) => MinesError::NothingRevealed,
+                    None => MinesError::NoGame,
+                });
+            }
+        };
+
+        let board = Self::board(&session);
+        let pot = MinesGame::new(session.bet as u64, &board, rules).pot;
+        METRICS.record_game("mines", session.bet as u64, pot);
+
+        Ok(MinesResult {
+            tile: None,
+            mine: None,
+            game: None,
+            mines: Some(board.mines),
+            bet: 0,
+            gain: pot as i64,
+        })
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinesResponse {
+    pub status_code: u16,
+    pub status: Result<MinesResult, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl MinesResponse {
+    pub fn new(result: Result<MinesResult, MinesError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MinesStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when there is no game going
+    pub status: Result<Option<MinesGame>, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl MinesStatusResponse {
+    pub fn new(result: Result<Option<MinesGame>, MinesError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
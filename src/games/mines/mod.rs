@@ -0,0 +1,8 @@
+mod error;
+mod mines_game;
+
+pub use self::error::MinesError;
+pub use self::mines_game::{paytable, probability, MinesBoard, MinesGame, MinesResult, MinesStep,
+                           PaytableResponse, Rules, TILES};
+#[cfg(feature = "auto_save")]
+pub use self::mines_game::{Mines, MinesResponse, MinesStatusResponse};
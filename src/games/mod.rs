@@ -7,8 +7,38 @@ pub mod blackjack;
 pub mod slot_machine;
 pub mod coin_toss;
 pub mod rps;
+pub mod poker;
+pub mod video_poker;
+pub mod holdem;
+pub mod baccarat;
+pub mod roulette;
+pub mod craps;
+pub mod dice;
+pub mod war;
+pub mod hi_lo;
+pub mod keno;
+pub mod lottery;
+pub mod crash;
+pub mod mines;
+pub mod plinko;
 // pub mod gofish;
 
 pub use self::deck::{StandardDeck, StandardDeckError};
 pub use self::cards::{StandardCard, StandardCardFace, StandardCardParseError};
 pub use self::deck_of_cards::STANDARD_DECK_OF_CARDS;
+
+/// Sum of `amounts`, None on overflow
+pub fn checked_sum(amounts: &[u64]) -> Option<u64> {
+    amounts
+        .iter()
+        .fold(Some(0u64), |sum, &amount| sum.and_then(|sum| sum.checked_add(amount)))
+}
+
+/// `won` minus `lost`, None if either doesn't fit in a gain
+pub fn net_gain(won: u64, lost: u64) -> Option<i64> {
+    if won > i64::max_value() as u64 || lost > i64::max_value() as u64 {
+        None
+    } else {
+        Some(won as i64 - lost as i64)
+    }
+}
@@ -0,0 +1,49 @@
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum PlinkoError {
+    InvalidBet(String),
+    /// A path that doesn't bounce once per row, with its length and the rows
+    InvalidPath(usize, u8),
+    /// Rows outside of `plinko.min_rows` to `plinko.max_rows`
+    InvalidRows(u8),
+    /// The risk as it was given
+    UnknownRisk(String),
+}
+
+impl Display for PlinkoError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            PlinkoError::InvalidBet(ref why) => f.write_str(why),
+            PlinkoError::InvalidPath(len, rows) => write!(
+                f,
+                "A ball bounces {} times on a board with {} rows, not {}",
+                rows, rows, len
+            ),
+            PlinkoError::InvalidRows(rows) => write!(f, "The board can't have {} rows", rows),
+            PlinkoError::UnknownRisk(ref risk) => {
+                write!(f, "{} isn't a risk, pick low, medium or high", risk)
+            }
+        }
+    }
+}
+
+impl StdError for PlinkoError {
+    fn description(&self) -> &str {
+        use self::PlinkoError::*;
+        match *self {
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidPath(..) => "The path doesn't match the rows",
+            InvalidRows(_) => "Invalid number of rows",
+            UnknownRisk(_) => "Unknown risk",
+        }
+    }
+}
+
+impl PlinkoError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        501
+    }
+}
@@ -0,0 +1,6 @@
+mod error;
+mod plinko_game;
+
+pub use self::error::PlinkoError;
+pub use self::plinko_game::{drop_ball, paytable, play, plinko, probability, Bounce,
+                            PaytableResponse, PlinkoGame, PlinkoResponse, PlinkoSlot, Risk, Rules};
@@ -0,0 +1,244 @@
+use metrics::{payout, METRICS};
+use rand::{thread_rng, Rng};
+use std::str::FromStr;
+use super::PlinkoError;
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Kept from every drop, the multipliers of a board return 1 - house_edge on average
+    pub house_edge: f64,
+    pub min_rows: u8,
+    pub max_rows: u8,
+    /// A slot's multiplier grows by this factor for every step away from the middle
+    pub low: f64,
+    pub medium: f64,
+    pub high: f64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            house_edge: 0.01,
+            min_rows: 8,
+            max_rows: 16,
+            low: 1.3,
+            medium: 1.7,
+            high: 3.0,
+        }
+    }
+}
+
+impl Rules {
+    fn steepness(&self, risk: Risk) -> f64 {
+        match risk {
+            Risk::Low => self.low,
+            Risk::Medium => self.medium,
+            Risk::High => self.high,
+        }
+    }
+
+    /// Multiplier of every slot of a board, rounded down to cents
+    pub fn multipliers(&self, rows: u8, risk: Risk) -> Result<Vec<f64>, PlinkoError> {
+        if rows < self.min_rows || rows > self.max_rows {
+            return Err(PlinkoError::InvalidRows(rows));
+        }
+
+        let steepness = self.steepness(risk);
+        let weights = c![
+            steepness.powf((2 * i32::from(slot) - i32::from(rows)).abs() as f64 / 2.0),
+            for slot in 0..rows + 1
+        ];
+        // Scaled so the expected multiplier is exactly 1 - house_edge before rounding
+        let expected: f64 = weights
+            .iter()
+            .enumerate()
+            .map(|(slot, weight)| probability(rows, slot as u8) * weight)
+            .sum();
+
+        Ok(c![
+            (weight * (1.0 - self.house_edge) / expected * 100.0).floor() / 100.0,
+            for weight in weights
+        ])
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.house_edge < 0.0 || self.house_edge >= 1.0 {
+            problems.push(String::from(
+                "plinko.house_edge must be at least 0 and below 1",
+            ));
+        }
+        if self.min_rows == 0 || self.min_rows > self.max_rows || self.max_rows > 32 {
+            problems.push(format!(
+                "plinko.min_rows ({}) to plinko.max_rows ({}) must be within 1 to 32",
+                self.min_rows, self.max_rows
+            ));
+        }
+        let risks = [("low", self.low), ("medium", self.medium), ("high", self.high)];
+        for &(name, steepness) in &risks {
+            if steepness < 1.0 {
+                problems.push(format!("plinko.{} must be at least 1", name));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Risk {
+    Low,
+    Medium,
+    High,
+}
+
+impl FromStr for Risk {
+    type Err = PlinkoError;
+
+    fn from_str(risk: &str) -> Result<Self, Self::Err> {
+        match risk.to_lowercase().as_str() {
+            "low" => Ok(Risk::Low),
+            "medium" => Ok(Risk::Medium),
+            "high" => Ok(Risk::High),
+            _ => Err(PlinkoError::UnknownRisk(risk.to_string())),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Bounce {
+    Left,
+    Right,
+}
+
+/// Exact chance of the ball landing in `slot` of a board with `rows`, counting slots from the left
+pub fn probability(rows: u8, slot: u8) -> f64 {
+    if slot > rows {
+        return 0.0;
+    }
+    let ways = (0..slot).fold(1.0, |ways, i| {
+        ways * f64::from(rows - i) / f64::from(i + 1)
+    });
+    ways / 2f64.powi(i32::from(rows))
+}
+
+/// Bounces the ball once per row, it lands in the slot of how often it bounced right
+pub fn drop_ball<R: Rng>(rng: &mut R, rows: u8) -> Vec<Bounce> {
+    (0..rows)
+        .map(|_| if rng.gen() { Bounce::Right } else { Bounce::Left })
+        .collect()
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlinkoGame {
+    pub rows: u8,
+    pub risk: Risk,
+    pub path: Vec<Bounce>,
+    pub slot: u8,
+    pub multiplier: f64,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Settles `bet` on a ball that took `path`
+pub fn play(
+    bet: u64,
+    rows: u8,
+    risk: Risk,
+    path: Vec<Bounce>,
+    rules: &Rules,
+) -> Result<PlinkoGame, PlinkoError> {
+    let multipliers = rules.multipliers(rows, risk)?;
+    if path.len() != rows as usize {
+        return Err(PlinkoError::InvalidPath(path.len(), rows));
+    }
+    let slot = path.iter().filter(|&&bounce| bounce == Bounce::Right).count() as u8;
+    let multiplier = multipliers[slot as usize];
+
+    Ok(PlinkoGame {
+        rows,
+        risk,
+        path,
+        slot,
+        multiplier,
+        bet,
+        gain: (bet as f64 * multiplier).floor() as i64 - bet as i64,
+    })
+}
+
+/// Drops a ball for `bet` on a board with `rows`
+pub fn plinko(bet: u64, rows: u8, risk: Risk, rules: &Rules) -> Result<PlinkoGame, PlinkoError> {
+    let game = play(bet, rows, risk, drop_ball(&mut thread_rng(), rows), rules)?;
+    METRICS.record_game("plinko", bet, payout(bet, game.gain));
+    Ok(game)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlinkoSlot {
+    pub slot: u8,
+    pub probability: f64,
+    pub multiplier: f64,
+}
+
+/// Every slot of a board with its exact odds
+pub fn paytable(rows: u8, risk: Risk, rules: &Rules) -> Result<Vec<PlinkoSlot>, PlinkoError> {
+    let multipliers = rules.multipliers(rows, risk)?;
+    Ok(multipliers
+        .into_iter()
+        .enumerate()
+        .map(|(slot, multiplier)| PlinkoSlot {
+            slot: slot as u8,
+            probability: probability(rows, slot as u8),
+            multiplier,
+        })
+        .collect())
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PlinkoResponse {
+    pub status_code: u16,
+    pub status: Result<PlinkoGame, String>,
+}
+
+impl PlinkoResponse {
+    pub fn new(result: Result<PlinkoGame, PlinkoError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<PlinkoSlot>, String>,
+}
+
+impl PaytableResponse {
+    pub fn new(result: Result<Vec<PlinkoSlot>, PlinkoError>) -> Self {
+        match result {
+            Ok(slots) => Self {
+                status_code: 200,
+                status: Ok(slots),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use games::StandardCard;
+
+/// Rank of an ace, which also plays low in the 5 high straight
+pub const ACE: u8 = 14;
+
+/// Poker rank of a card, 2 through 14 with aces high
+/// Jokers are 0, no hand containing one can be evaluated
+pub fn rank_of(card: &StandardCard) -> u8 {
+    card.rank()
+}
+
+/// Suit of a card as 0 through 3
+pub fn suit_of(card: &StandardCard) -> u8 {
+    match *card {
+        StandardCard::Hearts(_) => 0,
+        StandardCard::Spades(_) => 1,
+        StandardCard::Clubs(_) => 2,
+        StandardCard::Diamonds(_) => 3,
+    }
+}
+
+/// Name of a rank, `plural` for "Kings over Fives"
+pub fn rank_name(rank: u8, plural: bool) -> &'static str {
+    match (rank, plural) {
+        (2, false) => "Two",
+        (2, true) => "Twos",
+        (3, false) => "Three",
+        (3, true) => "Threes",
+        (4, false) => "Four",
+        (4, true) => "Fours",
+        (5, false) => "Five",
+        (5, true) => "Fives",
+        (6, false) => "Six",
+        (6, true) => "Sixes",
+        (7, false) => "Seven",
+        (7, true) => "Sevens",
+        (8, false) => "Eight",
+        (8, true) => "Eights",
+        (9, false) => "Nine",
+        (9, true) => "Nines",
+        (10, false) => "Ten",
+        (10, true) => "Tens",
+        (11, false) => "Jack",
+        (11, true) => "Jacks",
+        (12, false) => "Queen",
+        (12, true) => "Queens",
+        (13, false) => "King",
+        (13, true) => "Kings",
+        (ACE, false) => "Ace",
+        (ACE, true) => "Aces",
+        (_, false) => "Joker",
+        (_, true) => "Jokers",
+    }
+}
+
+/// Hand categories, weakest first
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum HandCategory {
+    HighCard,
+    OnePair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    pub fn name(&self) -> &'static str {
+        match *self {
+            HandCategory::HighCard => "High Card",
+            HandCategory::OnePair => "Pair",
+            HandCategory::TwoPair => "Two Pair",
+            HandCategory::ThreeOfAKind => "Three of a Kind",
+            HandCategory::Straight => "Straight",
+            HandCategory::Flush => "Flush",
+            HandCategory::FullHouse => "Full House",
+            HandCategory::FourOfAKind => "Four of a Kind",
+            HandCategory::StraightFlush => "Straight Flush",
+        }
+    }
+}
+
+/// Value of a 5 card hand, hands compare by category then by `ranks`
+#[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct HandRank {
+    pub category: HandCategory,
+    /// Ranks in the order they break ties: grouped cards first (biggest group first), then kickers
+    /// Straights only hold their top card, 5 for the wheel (A-2-3-4-5)
+    pub ranks: Vec<u8>,
+}
+
+impl HandRank {
+    /// Ace high straight flush
+    pub fn is_royal(&self) -> bool {
+        self.category == HandCategory::StraightFlush && self.ranks[0] == ACE
+    }
+
+    /// Readable hand, like "Full House, Kings over Fives" or "Pair of Jacks"
+    pub fn description(&self) -> String {
+        let name = |i: usize, plural| rank_name(self.ranks[i], plural);
+
+        match self.category {
+            HandCategory::StraightFlush if self.is_royal() => "Royal Flush".to_string(),
+            HandCategory::HighCard => format!("{} High", name(0, false)),
+            HandCategory::OnePair => format!("Pair of {}", name(0, true)),
+            HandCategory::TwoPair => format!(
+                "{}, {} and {}",
+                self.category.name(),
+                name(0, true),
+                name(1, true)
+            ),
+            HandCategory::FullHouse => format!(
+                "{}, {} over {}",
+                self.category.name(),
+                name(0, true),
+                name(1, true)
+            ),
+            HandCategory::ThreeOfAKind | HandCategory::FourOfAKind => {
+                format!("{}, {}", self.category.name(), name(0, true))
+            }
+            HandCategory::Straight | HandCategory::Flush | HandCategory::StraightFlush => {
+                format!("{}, {} High", self.category.name(), name(0, false))
+            }
+        }
+    }
+}
+
+/// The best 5 cards out of more
+#[derive(Clone, Debug)]
+pub struct BestHand {
+    pub rank: HandRank,
+    pub cards: Vec<StandardCard>,
+}
+
+/// Top card of the straight in `ranks`, a bitmask with bit `r` set for rank `r`
+fn straight_top(ranks: u16) -> Option<u8> {
+    // The wheel, the ace counts as 1
+    let ranks = if ranks & (1 << ACE) != 0 {
+        ranks | 1 << 1
+    } else {
+        ranks
+    };
+
+    (5..ACE + 1)
+        .rev()
+        .find(|&top| (ranks >> (top - 4)) & 0b1_1111 == 0b1_1111)
+}
+
+/// Evaluates exactly 5 cards, None for any other count or if a joker is among them
+pub fn evaluate(cards: &[StandardCard]) -> Option<HandRank> {
+    if cards.len() != 5 {
+        return None;
+    }
+
+    let mut counts = [0u8; ACE as usize + 1];
+    let mut rank_mask = 0u16;
+    for card in cards {
+        let rank = rank_of(card);
+        if rank == 0 {
+            return None;
+        }
+        counts[rank as usize] += 1;
+        rank_mask |= 1 << rank;
+    }
+    let flush = cards.iter().all(|card| suit_of(card) == suit_of(&cards[0]));
+    let straight = straight_top(rank_mask);
+
+    // (count, rank) of every rank present, biggest group first then highest rank
+    let mut groups = c![
+        (counts[rank], rank as u8),
+        for rank in 2..counts.len(),
+        if counts[rank] > 0
+    ];
+    groups.sort_by(|a, b| b.cmp(a));
+    let ranks: Vec<u8> = groups.iter().map(|&(_, rank)| rank).collect();
+
+    let category = match (straight, flush, groups[0].0, groups.get(1).map(|group| group.0)) {
+        (Some(_), true, _, _) => HandCategory::StraightFlush,
+        (_, _, 4, _) => HandCategory::FourOfAKind,
+        (_, _, 3, Some(2)) => HandCategory::FullHouse,
+        (_, true, _, _) => HandCategory::Flush,
+        (Some(_), _, _, _) => HandCategory::Straight,
+        (_, _, 3, _) => HandCategory::ThreeOfAKind,
+        (_, _, 2, Some(2)) => HandCategory::TwoPair,
+        (_, _, 2, _) => HandCategory::OnePair,
+        _ => HandCategory::HighCard,
+    };
+
+    Some(HandRank {
+        category,
+        ranks: match straight {
+            Some(top) if category == HandCategory::Straight
+                || category == HandCategory::StraightFlush =>
+            {
+                vec![top]
+            }
+            _ => ranks,
+        },
+    })
+}
+
+/// Best 5 card hand out of 5 to 7 cards, the way hold'em plays hole cards with the board
+/// None for any other count or if a joker is among them
+pub fn best_hand(cards: &[StandardCard]) -> Option<BestHand> {
+    if cards.len() < 5 || cards.len() > 7 {
+        return None;
+    }
+
+    let mut best: Option<BestHand> = None;
+    // Every set of 5 cards is a mask with 5 bits set, at most 21 of them out of 7
+    for mask in 0u8..1 << cards.len() {
+        if mask.count_ones() != 5 {
+            continue;
+        }
+
+        let hand: Vec<StandardCard> = cards
+            .iter()
+            .enumerate()
+            .filter(|&(i, _)| mask & (1 << i) != 0)
+            .map(|(_, card)| *card)
+            .collect();
+        let rank = evaluate(&hand)?;
+        if best.as_ref().map_or(true, |best| rank > best.rank) {
+            best = Some(BestHand { rank, cards: hand });
+        }
+    }
+
+    best
+}
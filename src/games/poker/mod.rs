@@ -0,0 +1,4 @@
+mod hand;
+
+pub use self::hand::{best_hand, evaluate, rank_name, rank_of, suit_of, BestHand, HandCategory,
+                     HandRank, ACE};
@@ -0,0 +1,163 @@
+use games::net_gain;
+use super::{color_of, pocket_label, Color, RouletteError, Wheel, DOUBLE_ZERO};
+
+/// Inside bets are named by their lowest number, splits by both
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BetKind {
+    Straight(u8),
+    Split(u8, u8),
+    /// A row of 3
+    Street(u8),
+    /// A square of 4
+    Corner(u8),
+    /// Two rows, 6 numbers
+    SixLine(u8),
+    /// 1 to 3, for 1-12, 13-24 and 25-36
+    Dozen(u8),
+    /// 1 to 3, column 1 starts with 1
+    Column(u8),
+    Red,
+    Black,
+    Odd,
+    Even,
+    /// 1-18
+    Low,
+    /// 19-36
+    High,
+}
+
+/// True if `a` and `b` touch on the layout of `wheel`
+fn adjacent(a: u8, b: u8, wheel: Wheel) -> bool {
+    let (low, high) = if a < b { (a, b) } else { (b, a) };
+
+    match (low, high) {
+        (0, 1) | (0, 2) => true,
+        (0, 3) => wheel == Wheel::European,
+        (0, DOUBLE_ZERO) | (2, DOUBLE_ZERO) | (3, DOUBLE_ZERO) => wheel == Wheel::American,
+        (0, _) | (_, DOUBLE_ZERO) => false,
+        // Same column, or next to each other in a row of 3
+        _ => high - low == 3 || (high - low == 1 && low % 3 != 0),
+    }
+}
+
+impl BetKind {
+    /// Parses bets like `straight 17`, `split 17-20`, `street 4`, `dozen 2` or `red`
+    pub fn parse(input: &str, wheel: Wheel) -> Result<Self, RouletteError> {
+        let lower = input.trim().to_lowercase();
+        let mut words = lower.split_whitespace();
+        let kind = words.next().unwrap_or("");
+        let args: Vec<&str> = words
+            .next()
+            .map_or_else(Vec::new, |args| args.split('-').collect());
+        let number = |i: usize| args.get(i).and_then(|arg| wheel.parse(arg));
+        let third = || match number(0) {
+            Some(n) if n >= 1 && n <= 3 => Some(n),
+            _ => None,
+        };
+
+        if words.next().is_some() {
+            return Err(RouletteError::InvalidSpot(input.to_string()));
+        }
+
+        let bet = match (kind, args.len()) {
+            ("straight", 1) => number(0).map(BetKind::Straight),
+            ("split", 2) => match (number(0), number(1)) {
+                (Some(a), Some(b)) if adjacent(a, b, wheel) => Some(BetKind::Split(a, b)),
+                _ => None,
+            },
+            ("street", 1) => match number(0) {
+                Some(n) if n >= 1 && n <= 34 && n % 3 == 1 => Some(BetKind::Street(n)),
+                _ => None,
+            },
+            ("corner", 1) => match number(0) {
+                Some(n) if n >= 1 && n <= 32 && n % 3 != 0 => Some(BetKind::Corner(n)),
+                _ => None,
+            },
+            ("six_line", 1) | ("sixline", 1) => match number(0) {
+                Some(n) if n >= 1 && n <= 31 && n % 3 == 1 => Some(BetKind::SixLine(n)),
+                _ => None,
+            },
+            ("dozen", 1) => third().map(BetKind::Dozen),
+            ("column", 1) => third().map(BetKind::Column),
+            ("red", 0) => Some(BetKind::Red),
+            ("black", 0) => Some(BetKind::Black),
+            ("odd", 0) => Some(BetKind::Odd),
+            ("even", 0) => Some(BetKind::Even),
+            ("low", 0) => Some(BetKind::Low),
+            ("high", 0) => Some(BetKind::High),
+            _ => None,
+        };
+
+        bet.ok_or_else(|| RouletteError::InvalidSpot(input.to_string()))
+    }
+
+    /// Pockets the bet wins on
+    pub fn pockets(&self) -> Vec<u8> {
+        let numbers = 1..DOUBLE_ZERO;
+
+        match *self {
+            BetKind::Straight(n) => vec![n],
+            BetKind::Split(a, b) => vec![a, b],
+            BetKind::Street(n) => vec![n, n + 1, n + 2],
+            BetKind::Corner(n) => vec![n, n + 1, n + 3, n + 4],
+            BetKind::SixLine(n) => (n..n + 6).collect(),
+            BetKind::Dozen(d) => ((d - 1) * 12 + 1..d * 12 + 1).collect(),
+            BetKind::Column(c) => numbers.filter(|n| n % 3 == c % 3).collect(),
+            BetKind::Red => numbers.filter(|&n| color_of(n) == Color::Red).collect(),
+            BetKind::Black => numbers.filter(|&n| color_of(n) == Color::Black).collect(),
+            BetKind::Odd => numbers.filter(|n| n % 2 == 1).collect(),
+            BetKind::Even => numbers.filter(|n| n % 2 == 0).collect(),
+            BetKind::Low => (1..19).collect(),
+            BetKind::High => (19..DOUBLE_ZERO).collect(),
+        }
+    }
+
+    /// Gain as a multiple of the bet, 35 to 1 on a straight down to 1 to 1 on even money bets
+    /// The zeros are what give the house its edge
+    pub fn pays(&self) -> u64 {
+        36 / self.pockets().len() as u64 - 1
+    }
+}
+
+/// A bet as it is sent
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RouletteBet {
+    /// Like `straight 17`, `split 17-20`, `street 4`, `corner 1`, `six_line 1`, `dozen 2`,
+    /// `column 3`, `red`, `black`, `odd`, `even`, `low` or `high`
+    pub bet: String,
+    pub amount: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SettledBet {
+    pub bet: String,
+    /// Pockets the bet covered
+    pub pockets: Vec<String>,
+    pub amount: u64,
+    pub pays: u64,
+    pub won: bool,
+    pub gain: i64,
+}
+
+impl SettledBet {
+    pub fn new(bet: &RouletteBet, kind: BetKind, pocket: u8) -> Result<Self, RouletteError> {
+        let pockets = kind.pockets();
+        let won = pockets.contains(&pocket);
+        let gain = if won {
+            bet.amount
+                .checked_mul(kind.pays())
+                .and_then(|winnings| net_gain(winnings, 0))
+        } else {
+            net_gain(0, bet.amount)
+        };
+
+        Ok(Self {
+            bet: bet.bet.clone(),
+            pockets: c![pocket_label(*pocket), for pocket in &pockets],
+            amount: bet.amount,
+            pays: kind.pays(),
+            won,
+            gain: gain.ok_or(RouletteError::BetTooLarge)?,
+        })
+    }
+}
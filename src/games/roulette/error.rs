@@ -0,0 +1,84 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum RouletteError {
+    BetTooLarge,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    InvalidBet(String),
+    /// The bet as it was given
+    InvalidSpot(String),
+    NoBet,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+    /// Most bets allowed on one spin
+    TooManyBets(usize),
+    UnknownTable(String),
+}
+
+impl Display for RouletteError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            RouletteError::InvalidBet(ref why) => f.write_str(why),
+            RouletteError::InvalidSpot(ref spot) => write!(
+                f,
+                "{} isn't a bet on this wheel, bet like `straight 17`, `split 17-20`, \
+                 `street 4`, `corner 1`, `six_line 1`, `dozen 2`, `column 3` or `red`",
+                spot
+            ),
+            RouletteError::TooManyBets(max) => write!(f, "At most {} bets per spin", max),
+            RouletteError::UnknownTable(ref table) => write!(f, "No table named {}", table),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for RouletteError {
+    fn description(&self) -> &str {
+        use self::RouletteError::*;
+        match *self {
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            BetTooLarge => "Bet is too large",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidSpot(_) => "Invalid bet",
+            NoBet => "Nothing was bet",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+            TooManyBets(_) => "Too many bets",
+            UnknownTable(_) => "Unknown table",
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for RouletteError {
+    fn from(err: DieselResultError) -> Self {
+        RouletteError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for RouletteError {
+    fn from(err: R2d2Error) -> Self {
+        RouletteError::R2d2(err)
+    }
+}
+
+impl RouletteError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::RouletteError::*;
+        match *self {
+            BetTooLarge | InvalidBet(_) | InvalidSpot(_) | NoBet | TooManyBets(_)
+            | UnknownTable(_) => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
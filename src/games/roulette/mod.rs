@@ -0,0 +1,12 @@
+mod bets;
+mod error;
+mod roulette_game;
+mod wheel;
+
+pub use self::bets::{BetKind, RouletteBet, SettledBet};
+pub use self::error::RouletteError;
+pub use self::roulette_game::{settle, spin_bets, total_bet, History, HistoryResponse, PocketCount,
+                              RouletteResponse, RouletteSpin, Rules, Table, TablesResponse};
+#[cfg(feature = "auto_save")]
+pub use self::roulette_game::Roulette;
+pub use self::wheel::{color_of, pocket_label, Color, Wheel, DOUBLE_ZERO};
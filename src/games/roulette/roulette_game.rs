@@ -0,0 +1,320 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use metrics::{payout, METRICS};
+#[cfg(feature = "auto_save")]
+use models::NewRouletteSpin;
+use games::checked_sum;
+use super::{color_of, pocket_label, BetKind, Color, RouletteBet, RouletteError, SettledBet, Wheel};
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Table {
+    pub name: String,
+    pub wheel: Wheel,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    pub tables: Vec<Table>,
+    /// Most bets settled on one spin
+    pub max_bets: usize,
+    /// Spins of a table the hot and cold numbers are taken from
+    pub history_size: usize,
+    /// How many hot and cold numbers are listed
+    pub hot_cold: usize,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            tables: vec![
+                Table {
+                    name: String::from("european"),
+                    wheel: Wheel::European,
+                },
+                Table {
+                    name: String::from("american"),
+                    wheel: Wheel::American,
+                },
+            ],
+            max_bets: 50,
+            history_size: 100,
+            hot_cold: 5,
+        }
+    }
+}
+
+impl Rules {
+    /// Table named `name`, ignoring case
+    pub fn table(&self, name: &str) -> Result<&Table, RouletteError> {
+        self.tables
+            .iter()
+            .find(|table| table.name.to_lowercase() == name.to_lowercase())
+            .ok_or_else(|| RouletteError::UnknownTable(name.to_string()))
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        for (i, table) in self.tables.iter().enumerate() {
+            if table.name.is_empty() {
+                problems.push(String::from("roulette has an unnamed table"));
+            }
+            if self.tables[..i]
+                .iter()
+                .any(|other| other.name.to_lowercase() == table.name.to_lowercase())
+            {
+                problems.push(format!("roulette lists table {} twice", table.name));
+            }
+        }
+        if self.max_bets == 0 {
+            problems.push(String::from("roulette.max_bets must be above 0"));
+        }
+        if self.history_size == 0 {
+            problems.push(String::from("roulette.history_size must be above 0"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Settles every bet against `pocket`, nothing is settled if any bet is invalid
+pub fn settle(
+    bets: &[RouletteBet],
+    wheel: Wheel,
+    pocket: u8,
+) -> Result<Vec<SettledBet>, RouletteError> {
+    let mut kinds = Vec::with_capacity(bets.len());
+    for bet in bets {
+        kinds.push(BetKind::parse(&bet.bet, wheel)?);
+    }
+
+    bets.iter()
+        .zip(&kinds)
+        .map(|(bet, &kind)| SettledBet::new(bet, kind, pocket))
+        .collect()
+}
+
+/// Everything bet in `bets`
+pub fn total_bet(bets: &[RouletteBet]) -> Result<u64, RouletteError> {
+    checked_sum(&c![bet.amount, for bet in bets]).ok_or(RouletteError::BetTooLarge)
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RouletteSpin {
+    pub table: String,
+    pub wheel: Wheel,
+    pub pocket: String,
+    pub color: Color,
+    pub bets: Vec<SettledBet>,
+    /// Everything bet
+    pub bet: u64,
+    pub gain: i64,
+}
+
+/// Settles `bets` placed on `table` against `pocket`
+pub fn spin_bets(
+    table: &Table,
+    bets: &[RouletteBet],
+    pocket: u8,
+    rules: &Rules,
+) -> Result<RouletteSpin, RouletteError> {
+    if bets.len() > rules.max_bets {
+        return Err(RouletteError::TooManyBets(rules.max_bets));
+    }
+    let total = total_bet(bets)?;
+    if total == 0 {
+        return Err(RouletteError::NoBet);
+    }
+
+    let settled = settle(bets, table.wheel, pocket)?;
+    let gain = settled
+        .iter()
+        .fold(Some(0i64), |sum, bet| sum.and_then(|sum| sum.checked_add(bet.gain)))
+        .ok_or(RouletteError::BetTooLarge)?;
+
+    Ok(RouletteSpin {
+        table: table.name.clone(),
+        wheel: table.wheel,
+        pocket: pocket_label(pocket),
+        color: color_of(pocket),
+        bets: settled,
+        bet: total,
+        gain,
+    })
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PocketCount {
+    pub pocket: String,
+    pub count: usize,
+}
+
+/// Recent spins of a table
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct History {
+    pub table: String,
+    /// Most recent first
+    pub spins: Vec<String>,
+    /// Every pocket of the wheel in order, with how often it came up
+    pub counts: Vec<PocketCount>,
+    /// Most frequent pockets, ties go to the lower pocket
+    pub hot: Vec<PocketCount>,
+    /// Least frequent pockets, ties go to the lower pocket
+    pub cold: Vec<PocketCount>,
+}
+
+impl History {
+    /// `spins` are pockets, most recent first
+    pub fn new(table: &Table, spins: &[u8], hot_cold: usize) -> Self {
+        let counts = c![
+            (pocket, spins.iter().filter(|&&spin| spin == pocket).count()),
+            for pocket in table.wheel.pockets()
+        ];
+        let pocket_count = |&(pocket, count): &(u8, usize)| PocketCount {
+            pocket: pocket_label(pocket),
+            count,
+        };
+
+        // Sorts are stable, so equal counts stay in wheel order
+        let mut hot = counts.clone();
+        hot.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut cold = counts.clone();
+        cold.sort_by(|a, b| a.1.cmp(&b.1));
+
+        Self {
+            table: table.name.clone(),
+            spins: c![pocket_label(*spin), for spin in spins],
+            counts: counts.iter().map(&pocket_count).collect(),
+            hot: hot.iter().take(hot_cold).map(&pocket_count).collect(),
+            cold: cold.iter().take(hot_cold).map(&pocket_count).collect(),
+        }
+    }
+}
+
+/// Spins are recorded per table for the hot and cold numbers
+#[cfg(feature = "auto_save")]
+pub struct Roulette;
+
+#[cfg(feature = "auto_save")]
+impl Roulette {
+    /// Spins table `name` and settles `bets` on it
+    pub fn spin(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        name: &str,
+        bets: &[RouletteBet],
+        rules: &Rules,
+    ) -> Result<RouletteSpin, RouletteError> {
+        use schema::roulette_spins;
+
+        let table = rules.table(name)?;
+        let pocket = table.wheel.spin();
+        let spin = spin_bets(table, bets, pocket, rules)?;
+
+        let conn = db_pool.get()?;
+        diesel::insert_into(roulette_spins::table)
+            .values(&NewRouletteSpin {
+                namespace: player_namespace,
+                table_name: &table.name,
+                pocket: i32::from(pocket),
+            })
+            .execute(&*conn)?;
+        METRICS.record_game("roulette", spin.bet, payout(spin.bet, spin.gain));
+
+        Ok(spin)
+    }
+
+    /// The last `history_size` spins of table `name`
+    pub fn history(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        name: &str,
+        rules: &Rules,
+    ) -> Result<History, RouletteError> {
+        use schema::roulette_spins::dsl::*;
+
+        let table = rules.table(name)?;
+        let conn = db_pool.get()?;
+        let spins = roulette_spins
+            .select(pocket)
+            .filter(namespace.eq(player_namespace))
+            .filter(table_name.eq(&table.name))
+            .order(id.desc())
+            .limit(rules.history_size as i64)
+            .load::<i32>(&*conn)?;
+
+        Ok(History::new(
+            table,
+            &c![spin as u8, for spin in spins],
+            rules.hot_cold,
+        ))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RouletteResponse {
+    pub status_code: u16,
+    pub status: Result<RouletteSpin, String>,
+}
+
+impl RouletteResponse {
+    pub fn new(result: Result<RouletteSpin, RouletteError>) -> Self {
+        match result {
+            Ok(spin) => Self {
+                status_code: 200,
+                status: Ok(spin),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct HistoryResponse {
+    pub status_code: u16,
+    pub status: Result<History, String>,
+}
+
+impl HistoryResponse {
+    pub fn new(result: Result<History, RouletteError>) -> Self {
+        match result {
+            Ok(history) => Self {
+                status_code: 200,
+                status: Ok(history),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct TablesResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<Table>, String>,
+}
+
+impl TablesResponse {
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(rules.tables.clone()),
+        }
+    }
+}
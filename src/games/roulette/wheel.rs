@@ -0,0 +1,80 @@
+use rand::{thread_rng, Rng};
+
+/// The American 00 pocket, every other pocket is its own number
+pub const DOUBLE_ZERO: u8 = 37;
+
+const RED: [u8; 18] = [
+    1, 3, 5, 7, 9, 12, 14, 16, 18, 19, 21, 23, 25, 27, 30, 32, 34, 36
+];
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Wheel {
+    /// Single zero, 37 pockets
+    European,
+    /// Zero and double zero, 38 pockets
+    American,
+}
+
+impl Wheel {
+    /// Every pocket of the wheel
+    pub fn pockets(&self) -> Vec<u8> {
+        match *self {
+            Wheel::European => (0..DOUBLE_ZERO).collect(),
+            Wheel::American => (0..DOUBLE_ZERO + 1).collect(),
+        }
+    }
+
+    pub fn has(&self, pocket: u8) -> bool {
+        pocket < DOUBLE_ZERO || (pocket == DOUBLE_ZERO && *self == Wheel::American)
+    }
+
+    /// Pocket named `label`, 0 to 36 or 00 on the American wheel
+    pub fn parse(&self, label: &str) -> Option<u8> {
+        let pocket = if label == "00" {
+            DOUBLE_ZERO
+        } else {
+            match label.parse::<u8>() {
+                Ok(pocket) if pocket < DOUBLE_ZERO => pocket,
+                _ => return None,
+            }
+        };
+
+        if self.has(pocket) {
+            Some(pocket)
+        } else {
+            None
+        }
+    }
+
+    pub fn spin(&self) -> u8 {
+        let pockets = self.pockets();
+        pockets[thread_rng().gen_range(0, pockets.len())]
+    }
+}
+
+/// Pocket as it is written on the wheel
+pub fn pocket_label(pocket: u8) -> String {
+    if pocket == DOUBLE_ZERO {
+        String::from("00")
+    } else {
+        pocket.to_string()
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Color {
+    Red,
+    Black,
+    /// The zeros
+    Green,
+}
+
+pub fn color_of(pocket: u8) -> Color {
+    if pocket == 0 || pocket == DOUBLE_ZERO {
+        Color::Green
+    } else if RED.contains(&pocket) {
+        Color::Red
+    } else {
+        Color::Black
+    }
+}
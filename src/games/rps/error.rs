@@ -0,0 +1,137 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum RpsError {
+    AlreadyAccepted,
+    ChallengeExpired,
+    /// Holds the unix time the challenge expires at
+    ChallengeNotExpired(u64),
+    ChallengeSettled,
+    CommitmentMismatch,
+    /// Someone else played the match or challenge during this request
+    Conflict,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    InvalidBestOf,
+    InvalidBet(String),
+    InvalidCommitment,
+    /// Weapon stored in a format that can't be read
+    #[cfg(feature = "auto_save")]
+    InvalidStoredWeapon(String),
+    /// Holds the valid choices
+    InvalidWeapon(String),
+    MatchInProgress,
+    MatchOver,
+    NoChallenge,
+    NoMatch,
+    NotAccepted,
+    SelfChallenge,
+    UnknownStrategy(String),
+    UnknownVariant(String),
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+}
+
+impl Display for RpsError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            RpsError::ChallengeNotExpired(expires_at) => {
+                write!(f, "The challenge can't be timed out before {}", expires_at)
+            }
+            RpsError::InvalidBet(ref why) => f.write_str(why),
+            #[cfg(feature = "auto_save")]
+            RpsError::InvalidStoredWeapon(ref weapon) => {
+                write!(f, "Stored weapon {} is invalid", weapon)
+            }
+            RpsError::InvalidWeapon(ref choices) => write!(f, "Valid choices are {}", choices),
+            RpsError::UnknownStrategy(ref name) => write!(
+                f,
+                "No opponent named {}, valid opponents are uniform/frequency/markov \
+                 or easy/medium/hard",
+                name
+            ),
+            RpsError::UnknownVariant(ref name) => write!(f, "No RPS variant named {}", name),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for RpsError {
+    fn description(&self) -> &str {
+        use self::RpsError::*;
+        match *self {
+            AlreadyAccepted => "The challenge was already accepted",
+            ChallengeExpired => "The challenge expired",
+            ChallengeNotExpired(_) => "The challenge hasn't expired yet",
+            ChallengeSettled => "The challenge is settled",
+            CommitmentMismatch => "The throw and salt don't match the commitment",
+            Conflict => "The game changed during the request, please retry",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            InvalidBestOf => "Matches are best of 3, 5 or 7",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidCommitment => "Commitments are hex encoded sha256 hashes",
+            #[cfg(feature = "auto_save")]
+            InvalidStoredWeapon(_) => "Stored weapon is invalid",
+            InvalidWeapon(_) => "Invalid weapon",
+            MatchInProgress => "A match is already in progress, please finish it",
+            MatchOver => "The match is over",
+            NoChallenge => "No such challenge",
+            NoMatch => "No match in progress",
+            NotAccepted => "The challenge hasn't been accepted yet",
+            SelfChallenge => "Users can't challenge themselves",
+            UnknownStrategy(_) => "Unknown opponent",
+            UnknownVariant(_) => "Unknown RPS variant",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for RpsError {
+    fn from(err: DieselResultError) -> Self {
+        RpsError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for RpsError {
+    fn from(err: R2d2Error) -> Self {
+        RpsError::R2d2(err)
+    }
+}
+
+impl RpsError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::RpsError::*;
+        match *self {
+            Conflict => 409,
+            AlreadyAccepted
+            | ChallengeExpired
+            | ChallengeNotExpired(_)
+            | ChallengeSettled
+            | CommitmentMismatch
+            | InvalidBestOf
+            | InvalidBet(_)
+            | InvalidCommitment
+            | InvalidWeapon(_)
+            | MatchInProgress
+            | MatchOver
+            | NoChallenge
+            | NoMatch
+            | NotAccepted
+            | SelfChallenge
+            | UnknownStrategy(_)
+            | UnknownVariant(_) => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
@@ -1,7 +1,24 @@
+mod error;
+mod opponent;
+#[cfg(feature = "auto_save")]
+mod pvp;
 mod rps_game;
+mod rps_match;
+mod variant;
 mod weapons;
 mod response;
 
+pub use self::error::RpsError;
+pub use self::opponent::{adaptive_multiplier, computer_throw, AdaptiveGame, AdaptiveResponse,
+                         AdaptiveRules, Strategy, ThrowModel};
+#[cfg(feature = "auto_save")]
+pub use self::opponent::rps_adaptive;
 pub use self::weapons::Weapons;
-pub use self::rps_game::rps;
+#[cfg(feature = "auto_save")]
+pub use self::pvp::{commitment, is_commitment, Challenge, ChallengeResponse, ChallengeResult,
+                    ChallengeStatus, ChallengeStatusResponse};
+pub use self::rps_game::{rps, rps_variant, rps_with_rules, PvpRules, Rules, VariantsResponse};
+pub use self::rps_match::{MatchResponse, MatchResult, MatchStatusResponse, Round, RpsMatch,
+                          MATCH_LENGTHS};
 pub use self::response::Response;
+pub use self::variant::{Variant, Weapon};
@@ -0,0 +1,401 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use metrics::{payout, METRICS};
+#[cfg(feature = "auto_save")]
+use models::RpsModelSession;
+use rand::{thread_rng, Rng};
+use std::str::FromStr;
+use super::{RpsError, Rules, Variant};
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+/// How the computer picks its throw
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Strategy {
+    /// Uniformly random, the quick game
+    Uniform,
+    /// Counters the user's most thrown weapon
+    Frequency,
+    /// Counters the weapon the user most often throws after their last throw
+    Markov,
+}
+
+impl FromStr for Strategy {
+    type Err = RpsError;
+
+    /// Strategy names or difficulties: easy, medium and hard
+    fn from_str(strategy: &str) -> Result<Self, Self::Err> {
+        match strategy.to_lowercase().as_str() {
+            "uniform" | "easy" => Ok(Strategy::Uniform),
+            "frequency" | "medium" => Ok(Strategy::Frequency),
+            "markov" | "hard" => Ok(Strategy::Markov),
+            _ => Err(RpsError::UnknownStrategy(strategy.to_string())),
+        }
+    }
+}
+
+/// How predictable the adaptive strategies are, uniform opponents pay `win_multiplier`
+/// Their win multipliers follow from these, see `adaptive_multiplier`
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AdaptiveRules {
+    /// Share of the frequency opponent's throws picked uniformly
+    pub frequency_uniform_share: f64,
+    /// Share of the markov opponent's throws picked uniformly
+    pub markov_uniform_share: f64,
+    /// What a user playing the opponent at best still loses on average, as a fraction of the bet
+    pub house_edge: f64,
+}
+
+impl Default for AdaptiveRules {
+    fn default() -> Self {
+        Self {
+            frequency_uniform_share: 0.5,
+            markov_uniform_share: 0.4,
+            house_edge: 0.02,
+        }
+    }
+}
+
+impl AdaptiveRules {
+    /// Share of the throws of `strategy` that ignore the user's model
+    pub fn uniform_share(&self, strategy: Strategy) -> f64 {
+        match strategy {
+            Strategy::Uniform => 1.0,
+            Strategy::Frequency => self.frequency_uniform_share,
+            Strategy::Markov => self.markov_uniform_share,
+        }
+    }
+}
+
+/// What the computer knows about a user's throws in a variant
+/// Weapons are indexes into the weapons of the variant
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct ThrowModel {
+    /// Times each weapon was thrown
+    pub counts: Vec<u32>,
+    /// Times weapon `b` followed weapon `a`, at `a * weapons + b`
+    pub transitions: Vec<u32>,
+    pub last: Option<usize>,
+}
+
+fn counted(counts: &[u32]) -> bool {
+    counts.iter().any(|&count| count > 0)
+}
+
+impl ThrowModel {
+    pub fn new(weapons: usize) -> Self {
+        Self {
+            counts: vec![0; weapons],
+            transitions: vec![0; weapons * weapons],
+            last: None,
+        }
+    }
+
+    pub fn weapons(&self) -> usize {
+        self.counts.len()
+    }
+
+    pub fn record(&mut self, weapon: usize) {
+        let weapons = self.weapons();
+
+        self.counts[weapon] += 1;
+        if let Some(last) = self.last {
+            self.transitions[last * weapons + weapon] += 1;
+        }
+        self.last = Some(weapon);
+    }
+
+    /// Counts `strategy` predicts the next throw from, None if it has nothing to go on
+    /// Markov falls back on the totals until something followed the last throw
+    pub fn weights(&self, strategy: Strategy) -> Option<&[u32]> {
+        let weapons = self.weapons();
+        let weights = match (strategy, self.last) {
+            (Strategy::Uniform, _) => return None,
+            (Strategy::Markov, Some(last))
+                if counted(&self.transitions[last * weapons..(last + 1) * weapons]) =>
+            {
+                &self.transitions[last * weapons..(last + 1) * weapons]
+            }
+            _ => &self.counts[..],
+        };
+
+        if counted(weights) {
+            Some(weights)
+        } else {
+            None
+        }
+    }
+
+    /// A next throw of the user drawn in proportion to the weights of `strategy`
+    pub fn predict(&self, strategy: Strategy) -> Option<usize> {
+        self.weights(strategy).map(|weights| {
+            let total = weights.iter().map(|&weight| u64::from(weight)).sum::<u64>();
+            let mut drawn = thread_rng().gen_range(0, total);
+
+            weights
+                .iter()
+                .position(|&weight| {
+                    if drawn < u64::from(weight) {
+                        true
+                    } else {
+                        drawn -= u64::from(weight);
+                        false
+                    }
+                })
+                .unwrap_or(0)
+        })
+    }
+}
+
+/// Weapons beating `predicted`, every weapon if none does
+fn counters(variant: &Variant, predicted: usize) -> Vec<usize> {
+    let countering = c![
+        i,
+        for i in 0..variant.weapons.len(),
+        if variant.beats(&variant.weapons[i].name, &variant.weapons[predicted].name)
+    ];
+
+    if countering.is_empty() {
+        c![i, for i in 0..variant.weapons.len()]
+    } else {
+        countering
+    }
+}
+
+/// Throw of the computer by `strategy`
+/// A uniform share of the throws is random, the others counter a prediction drawn from the model
+pub fn computer_throw(
+    variant: &Variant,
+    model: &ThrowModel,
+    strategy: Strategy,
+    rules: &AdaptiveRules,
+) -> usize {
+    let mut rng = thread_rng();
+    let prediction = if rng.gen::<f64>() < rules.uniform_share(strategy) {
+        None
+    } else {
+        model.predict(strategy)
+    };
+
+    match prediction {
+        Some(predicted) => {
+            let countering = counters(variant, predicted);
+            countering[rng.gen_range(0, countering.len())]
+        }
+        None => rng.gen_range(0, variant.weapons.len()),
+    }
+}
+
+/// Chance `player` wins, or loses when `wins` is false, against a throw drawn from `throws`
+fn chance(variant: &Variant, player: usize, throws: &[usize], wins: bool) -> f64 {
+    let player = &variant.weapons[player].name;
+    let decided = throws
+        .iter()
+        .filter(|&&throw| {
+            let other = &variant.weapons[throw].name;
+            if wins {
+                variant.beats(player, other)
+            } else {
+                variant.beats(other, player)
+            }
+        })
+        .count();
+
+    decided as f64 / throws.len() as f64
+}
+
+/// Largest win multiplier leaving the house `edge` of the bet against the best possible play
+/// Users steer their own model, at best they know which weapon gets countered
+/// and only the `uniform` share of the computer's throws is a surprise
+pub fn adaptive_multiplier(variant: &Variant, uniform: f64, edge: f64) -> f64 {
+    let every = c![i, for i in 0..variant.weapons.len()];
+    let mut multiplier = f64::INFINITY;
+
+    for predicted in 0..variant.weapons.len() {
+        let countering = counters(variant, predicted);
+
+        for player in 0..variant.weapons.len() {
+            let win = (1.0 - uniform) * chance(variant, player, &countering, true)
+                + uniform * chance(variant, player, &every, true);
+            let loss = (1.0 - uniform) * chance(variant, player, &countering, false)
+                + uniform * chance(variant, player, &every, false);
+
+            if win > 0.0 {
+                multiplier = multiplier.min((loss - edge) / win);
+            }
+        }
+    }
+
+    if multiplier.is_finite() {
+        multiplier.max(0.0)
+    } else {
+        0.0
+    }
+}
+
+impl Rules {
+    /// Gain on a win against `strategy` in `variant`, as a fraction of the bet
+    pub fn strategy_multiplier(&self, strategy: Strategy, variant: &Variant) -> f64 {
+        match strategy {
+            Strategy::Uniform => self.win_multiplier,
+            _ => adaptive_multiplier(
+                variant,
+                self.adaptive.uniform_share(strategy),
+                self.adaptive.house_edge,
+            ),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AdaptiveGame {
+    pub strategy: Strategy,
+    pub player: String,
+    pub computer: String,
+    /// None on a draw
+    pub result: Option<bool>,
+    pub win_multiplier: f64,
+    pub bet: u64,
+    pub gain: i64,
+}
+
+#[cfg(feature = "auto_save")]
+impl From<RpsModelSession> for ThrowModel {
+    fn from(session: RpsModelSession) -> Self {
+        Self {
+            counts: c![count as u32, for count in session.counts],
+            transitions: c![count as u32, for count in session.transitions],
+            last: session.last_throw.map(|last| last as usize),
+        }
+    }
+}
+
+/// Plays `weapon` against a `strategy` opponent, then trains `player`'s model with it
+/// Every throw is recorded, whatever the strategy
+#[cfg(feature = "auto_save")]
+#[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+pub fn rps_adaptive(
+    db_pool: &ConnectionPool,
+    player_namespace: i64,
+    player: u64,
+    variant_name: &str,
+    strategy: Strategy,
+    weapon: &str,
+    bet: u64,
+    rules: &Rules,
+) -> Result<AdaptiveGame, RpsError> {
+    use schema::rps_models;
+    use schema::rps_models::dsl::*;
+
+    let rps_variant = rules
+        .variant(variant_name)
+        .ok_or_else(|| RpsError::UnknownVariant(variant_name.to_string()))?;
+    let thrown = rps_variant.parse(weapon)?;
+    let thrown = rps_variant
+        .weapons
+        .iter()
+        .position(|candidate| candidate.name == thrown.name)
+        .unwrap_or(0);
+
+    let conn = db_pool.get()?;
+    let session = rps_models
+        .filter(namespace.eq(player_namespace))
+        .filter(id.eq(player as i64))
+        .filter(variant.eq(&rps_variant.name))
+        .first::<RpsModelSession>(&*conn)
+        .optional()?;
+    let previous = session.as_ref().map_or(0, |session| session.throws);
+
+    // Weapons changed in the config, the old model means nothing anymore
+    let mut model = session
+        .map(ThrowModel::from)
+        .into_iter()
+        .find(|model| model.weapons() == rps_variant.weapons.len())
+        .unwrap_or_else(|| ThrowModel::new(rps_variant.weapons.len()));
+
+    let computer = computer_throw(rps_variant, &model, strategy, &rules.adaptive);
+    model.record(thrown);
+
+    let new_counts = c![*count as i32, for count in &model.counts];
+    let new_transitions = c![*count as i32, for count in &model.transitions];
+    let updated = if previous == 0 {
+        diesel::insert_into(rps_models::table)
+            .values(&RpsModelSession {
+                namespace: player_namespace,
+                id: player as i64,
+                variant: rps_variant.name.clone(),
+                counts: new_counts,
+                transitions: new_transitions,
+                last_throw: Some(thrown as i32),
+                throws: 1,
+            })
+            .on_conflict_do_nothing()
+            .execute(&*conn)?
+    } else {
+        // Only applied if no other throw was recorded since the model was loaded
+        diesel::update(
+            rps_models
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64))
+                .filter(variant.eq(&rps_variant.name))
+                .filter(throws.eq(previous)),
+        ).set((
+            counts.eq(new_counts),
+            transitions.eq(new_transitions),
+            last_throw.eq(Some(thrown as i32)),
+            throws.eq(previous + 1),
+        ))
+            .execute(&*conn)?
+    };
+
+    if updated != 1 {
+        return Err(RpsError::Conflict);
+    }
+
+    let player_weapon = &rps_variant.weapons[thrown].name;
+    let computer_weapon = &rps_variant.weapons[computer].name;
+    let multiplier = rules.strategy_multiplier(strategy, rps_variant);
+    let result = rps_variant.outcome(player_weapon, computer_weapon);
+    let gain = match result {
+        Some(true) => (bet as f64 * multiplier) as i64,
+        Some(false) => -(bet as i64),
+        None => 0,
+    };
+
+    METRICS.record_game("rps_adaptive", bet, payout(bet, gain));
+
+    Ok(AdaptiveGame {
+        strategy,
+        player: player_weapon.clone(),
+        computer: computer_weapon.clone(),
+        result,
+        win_multiplier: multiplier,
+        bet,
+        gain,
+    })
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct AdaptiveResponse {
+    pub status_code: u16,
+    pub status: Result<AdaptiveGame, String>,
+}
+
+impl AdaptiveResponse {
+    pub fn new(result: Result<AdaptiveGame, RpsError>) -> Self {
+        match result {
+            Ok(game) => Self {
+                status_code: 200,
+                status: Ok(game),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
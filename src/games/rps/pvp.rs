@@ -0,0 +1,410 @@
+use diesel;
+use diesel::prelude::*;
+use metrics::METRICS;
+use models::{NewRpsChallenge, RpsChallengeSession};
+use sha2::{Digest, Sha256};
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::{RpsError, Rules, Variant};
+use {ConnectionPool, PgConnection};
+
+/// Commitment of a challenger to `weapon`, sha256 of `<weapon in lowercase>:<salt>`, hex encoded
+/// The salt keeps the opponent from guessing the throw, it should be long and random
+pub fn commitment(weapon: &str, salt: &str) -> String {
+    let digest = Sha256::digest(format!("{}:{}", weapon.to_lowercase(), salt).as_bytes());
+    c![format!("{:02x}", byte), for byte in digest.iter()].concat()
+}
+
+/// True if `input` looks like the output of `commitment`
+pub fn is_commitment(input: &str) -> bool {
+    input.len() == 64 && input.chars().all(|c| c.is_digit(16))
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ChallengeStatus {
+    /// Waiting for the opponent to throw
+    Open,
+    /// Waiting for the challenger to reveal
+    Accepted,
+    /// Both throws are known
+    Settled,
+    /// Nobody accepted in time (refunded) or the challenger didn't reveal in time (forfeit)
+    TimedOut,
+}
+
+/// A challenge between two users of the same API key
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Challenge {
+    pub id: i64,
+    pub challenger: u64,
+    pub opponent: u64,
+    /// Put up by both sides, the winner takes both
+    pub stake: u64,
+    pub variant: String,
+    pub commitment: String,
+    pub opponent_throw: Option<String>,
+    /// Only known once revealed
+    pub challenger_throw: Option<String>,
+    pub status: ChallengeStatus,
+    /// Unix time after which the challenge can be timed out
+    pub expires_at: u64,
+    /// None until settled and on draws
+    pub winner: Option<u64>,
+}
+
+/// Outcome of a request on a challenge
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChallengeResult {
+    pub challenge: Challenge,
+    /// Amount taken from the requesting user, creating and accepting take the stake
+    pub bet: u64,
+    /// Paid to each side, only once the challenge is settled or timed out
+    pub challenger_payout: u64,
+    pub opponent_payout: u64,
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs())
+        .unwrap_or(0)
+}
+
+impl Challenge {
+    fn from_session(session: RpsChallengeSession, variant: Option<&Variant>) -> Self {
+        let status = match (
+            session.settled,
+            &session.opponent_throw,
+            &session.challenger_throw,
+        ) {
+            (false, &None, _) => ChallengeStatus::Open,
+            (false, &Some(_), _) => ChallengeStatus::Accepted,
+            (true, _, &Some(_)) => ChallengeStatus::Settled,
+            (true, _, &None) => ChallengeStatus::TimedOut,
+        };
+
+        let winner = match (
+            variant,
+            &session.challenger_throw,
+            &session.opponent_throw,
+        ) {
+            (Some(variant), &Some(ref challenger), &Some(ref opponent)) => {
+                match variant.outcome(challenger, opponent) {
+                    Some(true) => Some(session.challenger),
+                    Some(false) => Some(session.opponent),
+                    None => None,
+                }
+            }
+            // Forfeit, the challenger never revealed
+            (_, &None, &Some(_)) if session.settled => Some(session.opponent),
+            _ => None,
+        };
+
+        Self {
+            id: session.id,
+            challenger: session.challenger as u64,
+            opponent: session.opponent as u64,
+            stake: session.stake as u64,
+            variant: session.variant,
+            commitment: session.commitment,
+            opponent_throw: session.opponent_throw,
+            challenger_throw: session.challenger_throw,
+            status,
+            expires_at: session.expires_at as u64,
+            winner: winner.map(|winner| winner as u64),
+        }
+    }
+
+    /// Payouts of a settled or timed out challenge as (challenger, opponent)
+    fn payouts(&self) -> (u64, u64) {
+        let pot = self.stake * 2;
+
+        match (self.status, self.winner) {
+            (ChallengeStatus::Open, _) | (ChallengeStatus::Accepted, _) => (0, 0),
+            (_, Some(winner)) if winner == self.challenger => (pot, 0),
+            (_, Some(_)) => (0, pot),
+            // Draws refund both sides, an unaccepted challenge refunds the challenger
+            (_, None) if self.opponent_throw.is_some() => (self.stake, self.stake),
+            (_, None) => (self.stake, 0),
+        }
+    }
+
+    fn load(
+        conn: &PgConnection,
+        player_namespace: i64,
+        challenge: i64,
+    ) -> Result<RpsChallengeSession, RpsError> {
+        use schema::rps_challenges::dsl::*;
+
+        Ok(rps_challenges
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(challenge))
+            .first::<RpsChallengeSession>(conn)
+            .optional()?
+            .ok_or(RpsError::NoChallenge)?)
+    }
+
+    /// Challenge `challenge` as seen by `player`, who has to be part of it
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        challenge: i64,
+        rules: &Rules,
+    ) -> Result<Self, RpsError> {
+        let conn = db_pool.get()?;
+        let session = Self::load(&*conn, player_namespace, challenge)?;
+
+        if session.challenger != player as i64 && session.opponent != player as i64 {
+            return Err(RpsError::NoChallenge);
+        }
+
+        let variant = rules.variant(&session.variant);
+        Ok(Self::from_session(session, variant))
+    }
+
+    /// `player` challenges `opponent` to a game of `variant`, committing to a throw
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        new_opponent: u64,
+        variant_name: &str,
+        new_stake: u64,
+        new_commitment: &str,
+        rules: &Rules,
+    ) -> Result<ChallengeResult, RpsError> {
+        use schema::rps_challenges;
+
+        if player == new_opponent {
+            return Err(RpsError::SelfChallenge);
+        }
+        if !is_commitment(new_commitment) {
+            return Err(RpsError::InvalidCommitment);
+        }
+        let variant = rules
+            .variant(variant_name)
+            .ok_or_else(|| RpsError::UnknownVariant(variant_name.to_string()))?;
+
+        let conn = db_pool.get()?;
+        let session = diesel::insert_into(rps_challenges::table)
+            .values(&NewRpsChallenge {
+                namespace: player_namespace,
+                challenger: player as i64,
+                opponent: new_opponent as i64,
+                stake: new_stake as i64,
+                variant: &variant.name,
+                commitment: &new_commitment.to_lowercase(),
+                expires_at: (now() + rules.pvp.accept_timeout) as i64,
+            })
+            .get_result::<RpsChallengeSession>(&*conn)?;
+
+        Ok(ChallengeResult {
+            challenge: Self::from_session(session, Some(variant)),
+            bet: new_stake,
+            challenger_payout: 0,
+            opponent_payout: 0,
+        })
+    }
+
+    /// The opponent of `challenge` throws `weapon`, putting up the stake
+    /// The challenger then has `reveal_timeout` seconds to reveal
+    pub fn accept(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        challenge: i64,
+        weapon: &str,
+        rules: &Rules,
+    ) -> Result<ChallengeResult, RpsError> {
+        use schema::rps_challenges::dsl::{expires_at, id, opponent_throw, rps_challenges,
+                                          settled};
+
+        let conn = db_pool.get()?;
+        let session = Self::load(&*conn, player_namespace, challenge)?;
+
+        if session.opponent != player as i64 {
+            return Err(RpsError::NoChallenge);
+        }
+        if session.settled || session.opponent_throw.is_some() {
+            return Err(RpsError::AlreadyAccepted);
+        }
+        if session.expires_at as u64 <= now() {
+            return Err(RpsError::ChallengeExpired);
+        }
+
+        let variant = rules
+            .variant(&session.variant)
+            .ok_or_else(|| RpsError::UnknownVariant(session.variant.clone()))?;
+        let weapon = variant.parse(weapon)?;
+
+        // Only one accept can go through
+        let session = diesel::update(
+            rps_challenges
+                .filter(id.eq(session.id))
+                .filter(settled.eq(false))
+                .filter(opponent_throw.is_null()),
+        ).set((
+            opponent_throw.eq(&weapon.name),
+            expires_at.eq((now() + rules.pvp.reveal_timeout) as i64),
+        ))
+            .get_result::<RpsChallengeSession>(&*conn)
+            .optional()?
+            .ok_or(RpsError::Conflict)?;
+
+        let stake_taken = session.stake as u64;
+        Ok(ChallengeResult {
+            challenge: Self::from_session(session, Some(variant)),
+            bet: stake_taken,
+            challenger_payout: 0,
+            opponent_payout: 0,
+        })
+    }
+
+    /// The challenger reveals the throw they committed to, settling the challenge
+    pub fn reveal(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        challenge: i64,
+        weapon: &str,
+        salt: &str,
+        rules: &Rules,
+    ) -> Result<ChallengeResult, RpsError> {
+        use schema::rps_challenges::dsl::{challenger_throw, id, rps_challenges, settled};
+
+        let conn = db_pool.get()?;
+        let session = Self::load(&*conn, player_namespace, challenge)?;
+
+        if session.challenger != player as i64 {
+            return Err(RpsError::NoChallenge);
+        }
+        if session.settled {
+            return Err(RpsError::ChallengeSettled);
+        }
+        if session.opponent_throw.is_none() {
+            return Err(RpsError::NotAccepted);
+        }
+        if session.expires_at as u64 <= now() {
+            return Err(RpsError::ChallengeExpired);
+        }
+
+        let variant = rules
+            .variant(&session.variant)
+            .ok_or_else(|| RpsError::UnknownVariant(session.variant.clone()))?;
+        let weapon = variant.parse(weapon)?;
+        if commitment(&weapon.name, salt) != session.commitment {
+            return Err(RpsError::CommitmentMismatch);
+        }
+
+        let session = diesel::update(
+            rps_challenges
+                .filter(id.eq(session.id))
+                .filter(settled.eq(false)),
+        ).set((settled.eq(true), challenger_throw.eq(&weapon.name)))
+            .get_result::<RpsChallengeSession>(&*conn)
+            .optional()?
+            .ok_or(RpsError::Conflict)?;
+
+        Ok(Self::settle(session, Some(variant)))
+    }
+
+    /// Ends a challenge past its deadline, either side can call it
+    /// An open challenge refunds the challenger, an unrevealed one is forfeit to the opponent
+    pub fn time_out(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        challenge: i64,
+        rules: &Rules,
+    ) -> Result<ChallengeResult, RpsError> {
+        use schema::rps_challenges::dsl::{expires_at, id, rps_challenges, settled};
+
+        let conn = db_pool.get()?;
+        let session = Self::load(&*conn, player_namespace, challenge)?;
+
+        if session.challenger != player as i64 && session.opponent != player as i64 {
+            return Err(RpsError::NoChallenge);
+        }
+        if session.settled {
+            return Err(RpsError::ChallengeSettled);
+        }
+        if session.expires_at as u64 > now() {
+            return Err(RpsError::ChallengeNotExpired(session.expires_at as u64));
+        }
+
+        let session = diesel::update(
+            rps_challenges
+                .filter(id.eq(session.id))
+                .filter(settled.eq(false))
+                .filter(expires_at.le(now() as i64)),
+        ).set(settled.eq(true))
+            .get_result::<RpsChallengeSession>(&*conn)
+            .optional()?
+            .ok_or(RpsError::Conflict)?;
+
+        let variant = rules.variant(&session.variant);
+        Ok(Self::settle(session, variant))
+    }
+
+    fn settle(session: RpsChallengeSession, variant: Option<&Variant>) -> ChallengeResult {
+        let challenge = Self::from_session(session, variant);
+        let (challenger_payout, opponent_payout) = challenge.payouts();
+        let staked = if challenge.opponent_throw.is_some() {
+            challenge.stake * 2
+        } else {
+            challenge.stake
+        };
+
+        METRICS.record_game("rps_pvp", staked, challenger_payout + opponent_payout);
+
+        ChallengeResult {
+            challenge,
+            bet: 0,
+            challenger_payout,
+            opponent_payout,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChallengeResponse {
+    pub status_code: u16,
+    pub status: Result<ChallengeResult, String>,
+}
+
+impl ChallengeResponse {
+    pub fn new(result: Result<ChallengeResult, RpsError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ChallengeStatusResponse {
+    pub status_code: u16,
+    pub status: Result<Challenge, String>,
+}
+
+impl ChallengeStatusResponse {
+    pub fn new(result: Result<Challenge, RpsError>) -> Self {
+        match result {
+            Ok(challenge) => Self {
+                status_code: 200,
+                status: Ok(challenge),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -21,9 +21,7 @@ pub struct Response {
 
 impl Response {
     /// Creates a response for a win
-    pub fn win(bet: u64, player: String, computer: String) -> Self {
-        let gain: i64 = (bet as f64 * 0.25) as i64;
-
+    pub fn win(bet: u64, gain: i64, player: String, computer: String) -> Self {
         Self {
             status_code: 200,
             status: Ok(Game {
@@ -64,6 +62,11 @@ impl Response {
         }
     }
 
+    /// Gain of the game, None on errors
+    pub fn gain(&self) -> Option<i64> {
+        self.status.as_ref().ok().map(|game| game.gain)
+    }
+
     /// Creates a response for an error
     pub fn error(bet: u64, msg: String) -> Self {
         Self {
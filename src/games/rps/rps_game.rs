@@ -1,23 +1,158 @@
-use super::{Response, Weapons};
+use metrics::{payout, METRICS};
+use super::{AdaptiveRules, Response, Variant};
+
+/// Deadlines of player vs player challenges, in seconds
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PvpRules {
+    /// Time the opponent has to accept, the challenger is refunded afterwards
+    pub accept_timeout: u64,
+    /// Time the challenger has to reveal once accepted, the challenge is forfeit afterwards
+    pub reveal_timeout: u64,
+}
+
+impl Default for PvpRules {
+    fn default() -> Self {
+        Self {
+            accept_timeout: 3600,
+            reveal_timeout: 300,
+        }
+    }
+}
+
+/// Configurable RPS rules
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Gain on a win, as a fraction of the bet
+    pub win_multiplier: f64,
+    /// Gain on winning a best of N match, as a fraction of the bet
+    pub match_win_multiplier: f64,
+    /// Variants playable by name, classic RPS and RPSLS by default
+    pub variants: Vec<Variant>,
+    pub pvp: PvpRules,
+    pub adaptive: AdaptiveRules,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            win_multiplier: 0.25,
+            match_win_multiplier: 0.9,
+            variants: vec![Variant::classic(), Variant::rpsls()],
+            pvp: PvpRules::default(),
+            adaptive: AdaptiveRules::default(),
+        }
+    }
+}
+
+impl Rules {
+    pub fn variant(&self, name: &str) -> Option<&Variant> {
+        self.variants
+            .iter()
+            .find(|variant| variant.name.to_lowercase() == name.to_lowercase())
+    }
+
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.win_multiplier < 0.0 {
+            problems.push(String::from("rps.win_multiplier can't be negative"));
+        }
+        if self.match_win_multiplier < 0.0 {
+            problems.push(String::from("rps.match_win_multiplier can't be negative"));
+        }
+        let shares = [
+            self.adaptive.frequency_uniform_share,
+            self.adaptive.markov_uniform_share,
+        ];
+        if shares.iter().any(|&share| share < 0.0 || share > 1.0) {
+            problems.push(String::from("rps.adaptive uniform shares must be between 0 and 1"));
+        }
+        if self.adaptive.house_edge < 0.0 || self.adaptive.house_edge >= 1.0 {
+            problems.push(String::from("rps.adaptive.house_edge must be at least 0 and below 1"));
+        }
+        if self.pvp.accept_timeout == 0 || self.pvp.reveal_timeout == 0 {
+            problems.push(String::from("rps.pvp timeouts must be above 0"));
+        }
+        for (i, variant) in self.variants.iter().enumerate() {
+            if self.variants[..i]
+                .iter()
+                .any(|other| other.name.to_lowercase() == variant.name.to_lowercase())
+            {
+                problems.push(format!("rps variant {} is listed twice", variant.name));
+            }
+            if let Err(variant_problems) = variant.validate() {
+                problems.extend(variant_problems);
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
 
 /// Quick RPS Game
 /// Weapons: rock/paper/scissors
 pub fn rps(bet: u64, weapon: &str) -> Response {
-    let weapon = match weapon.parse::<Weapons>() {
+    rps_with_rules(bet, weapon, &Rules::default())
+}
+
+/// Quick RPS Game, paying out by `rules`
+pub fn rps_with_rules(bet: u64, weapon: &str, rules: &Rules) -> Response {
+    play(bet, weapon, &Variant::classic(), rules)
+}
+
+/// Quick game of the variant of `rules` named `variant`
+pub fn rps_variant(bet: u64, variant: &str, weapon: &str, rules: &Rules) -> Response {
+    match rules.variant(variant) {
+        Some(variant) => play(bet, weapon, variant, rules),
+        None => Response::error(bet, format!("No RPS variant named {}", variant)),
+    }
+}
+
+fn play(bet: u64, weapon: &str, variant: &Variant, rules: &Rules) -> Response {
+    let weapon = match variant.parse(weapon) {
         Ok(v) => v,
-        Err(_) => {
-            return Response::error(bet, String::from("Valid choices are rock/paper/scissors"));
+        Err(why) => {
+            return Response::error(bet, why.to_string());
         }
     };
 
-    let comp = Weapons::rand_weapon();
+    let comp = variant.random_weapon();
 
-    if weapon == comp {
-        Response::draw(bet, weapon.to_string(), comp.to_string())
-    } else if weapon > comp {
-        // Win
-        Response::win(bet, weapon.to_string(), comp.to_string())
-    } else {
-        Response::lose(bet, weapon.to_string(), comp.to_string())
+    let response = match variant.outcome(&weapon.name, &comp.name) {
+        None => Response::draw(bet, weapon.name.clone(), comp.name.clone()),
+        Some(true) => {
+            // Win
+            let gain = (bet as f64 * rules.win_multiplier) as i64;
+            Response::win(bet, gain, weapon.name.clone(), comp.name.clone())
+        }
+        Some(false) => Response::lose(bet, weapon.name.clone(), comp.name.clone()),
+    };
+
+    if let Some(gain) = response.gain() {
+        METRICS.record_game("rps", bet, payout(bet, gain));
+    }
+
+    response
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VariantsResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<Variant>, String>,
+}
+
+impl VariantsResponse {
+    pub fn new(rules: &Rules) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(rules.variants.clone()),
+        }
     }
 }
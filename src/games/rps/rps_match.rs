@@ -0,0 +1,313 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use metrics::METRICS;
+#[cfg(feature = "auto_save")]
+use models::RpsMatchSession;
+use super::{RpsError, Rules, Weapons};
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+/// Match lengths a match can be started with
+pub const MATCH_LENGTHS: [u32; 3] = [3, 5, 7];
+
+/// A single throw of a match
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Round {
+    pub player: Weapons,
+    pub computer: Weapons,
+    /// None on a draw, draws don't count towards the score
+    pub won: Option<bool>,
+}
+
+impl Round {
+    pub fn new(player: Weapons, computer: Weapons) -> Self {
+        Self {
+            player,
+            computer,
+            won: if player == computer {
+                None
+            } else {
+                Some(player.beats(&computer))
+            },
+        }
+    }
+}
+
+/// A best of `best_of` match, stored between requests
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RpsMatch {
+    pub bet: u64,
+    pub best_of: u32,
+    pub player_score: u32,
+    pub computer_score: u32,
+    /// Every throw so far, draws included
+    pub rounds: Vec<Round>,
+}
+
+impl RpsMatch {
+    pub fn new(bet: u64, best_of: u32) -> Result<Self, RpsError> {
+        if !MATCH_LENGTHS.contains(&best_of) {
+            return Err(RpsError::InvalidBestOf);
+        }
+
+        Ok(Self {
+            bet,
+            best_of,
+            player_score: 0,
+            computer_score: 0,
+            rounds: Vec::new(),
+        })
+    }
+
+    /// Wins needed to take the match
+    pub fn majority(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    /// Some(true) once the player took the match, Some(false) once the computer did
+    pub fn winner(&self) -> Option<bool> {
+        if self.player_score >= self.majority() {
+            Some(true)
+        } else if self.computer_score >= self.majority() {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// Plays `player` against `computer`
+    pub fn play(&mut self, player: Weapons, computer: Weapons) -> Result<Round, RpsError> {
+        if self.winner().is_some() {
+            return Err(RpsError::MatchOver);
+        }
+
+        let round = Round::new(player, computer);
+        match round.won {
+            Some(true) => self.player_score += 1,
+            Some(false) => self.computer_score += 1,
+            None => (),
+        }
+        self.rounds.push(round);
+
+        Ok(round)
+    }
+
+    /// Amount returned once the match is settled, 0 unless the player took it
+    pub fn payout(&self, rules: &Rules) -> u64 {
+        match self.winner() {
+            Some(true) => self.bet + (self.bet as f64 * rules.match_win_multiplier) as u64,
+            _ => 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MatchResult {
+    /// The match after this request
+    #[serde(rename = "match")]
+    pub rps_match: RpsMatch,
+    /// Throw played by this request, None when starting a match
+    pub round: Option<Round>,
+    /// Amount taken by this request, only starting a match takes a bet
+    pub bet: u64,
+    /// Amount paid by this request, only the throw settling the match pays
+    pub gain: i64,
+}
+
+#[cfg(feature = "auto_save")]
+impl RpsMatch {
+    /// Match stored in `session`, refused if a throw isn't a weapon
+    fn from_session(session: RpsMatchSession) -> Result<Self, RpsError> {
+        let parse = |weapon: &String| {
+            weapon
+                .parse::<Weapons>()
+                .map_err(|_| RpsError::InvalidStoredWeapon(weapon.clone()))
+        };
+
+        let mut rounds = Vec::with_capacity(session.player_throws.len());
+        for (player, computer) in session
+            .player_throws
+            .iter()
+            .zip(session.computer_throws.iter())
+        {
+            rounds.push(Round::new(parse(player)?, parse(computer)?));
+        }
+
+        Ok(Self {
+            bet: session.bet as u64,
+            best_of: session.best_of as u32,
+            player_score: session.player_score as u32,
+            computer_score: session.computer_score as u32,
+            rounds,
+        })
+    }
+
+    /// Match of `player`, if they have one going
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Option<Self>, RpsError> {
+        use schema::rps_matches::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = rps_matches
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<RpsMatchSession>(&*conn)
+            .optional()?;
+
+        match session {
+            Some(session) => Ok(Some(Self::from_session(session)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Starts a best of `new_best_of` match for `player`, taking `new_bet`
+    pub fn start(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        new_best_of: u32,
+        new_bet: u64,
+    ) -> Result<MatchResult, RpsError> {
+        use schema::rps_matches;
+
+        let rps_match = Self::new(new_bet, new_best_of)?;
+
+        let conn = db_pool.get()?;
+        // The key is the player, a match already going keeps this one from being stored
+        let inserted = diesel::insert_into(rps_matches::table)
+            .values(&RpsMatchSession {
+                namespace: player_namespace,
+                id: player as i64,
+                bet: new_bet as i64,
+                best_of: new_best_of as i32,
+                player_score: 0,
+                computer_score: 0,
+                player_throws: Vec::new(),
+                computer_throws: Vec::new(),
+            })
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+
+        if inserted != 1 {
+            return Err(RpsError::MatchInProgress);
+        }
+
+        Ok(MatchResult {
+            rps_match,
+            round: None,
+            bet: new_bet,
+            gain: -(new_bet as i64),
+        })
+    }
+
+    /// Throws `weapon` in `player`'s match, the match is removed once settled
+    pub fn throw(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        weapon: Weapons,
+        rules: &Rules,
+    ) -> Result<MatchResult, RpsError> {
+        use schema::rps_matches::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = rps_matches
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<RpsMatchSession>(&*conn)
+            .optional()?
+            .ok_or(RpsError::NoMatch)?;
+
+        let mut rps_match = Self::from_session(session.clone())?;
+        let round = rps_match.play(weapon, Weapons::rand_weapon())?;
+
+        // Only applied if nobody else played the match since it was loaded
+        let unchanged = rps_matches
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .filter(player_throws.eq(&session.player_throws));
+
+        let updated = if rps_match.winner().is_some() {
+            diesel::delete(unchanged).execute(&*conn)?
+        } else {
+            let mut new_player_throws = session.player_throws.clone();
+            let mut new_computer_throws = session.computer_throws.clone();
+            new_player_throws.push(round.player.to_string());
+            new_computer_throws.push(round.computer.to_string());
+
+            diesel::update(unchanged)
+                .set((
+                    player_score.eq(rps_match.player_score as i32),
+                    computer_score.eq(rps_match.computer_score as i32),
+                    player_throws.eq(new_player_throws),
+                    computer_throws.eq(new_computer_throws),
+                ))
+                .execute(&*conn)?
+        };
+
+        if updated != 1 {
+            return Err(RpsError::Conflict);
+        }
+
+        let gain = rps_match.payout(rules);
+        if rps_match.winner().is_some() {
+            METRICS.record_game("rps_match", rps_match.bet, gain);
+        }
+
+        Ok(MatchResult {
+            rps_match,
+            round: Some(round),
+            bet: 0,
+            gain: gain as i64,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MatchResponse {
+    pub status_code: u16,
+    pub status: Result<MatchResult, String>,
+}
+
+impl MatchResponse {
+    pub fn new(result: Result<MatchResult, RpsError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct MatchStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when there is no match going
+    pub status: Result<Option<RpsMatch>, String>,
+}
+
+impl MatchStatusResponse {
+    pub fn new(result: Result<Option<RpsMatch>, RpsError>) -> Self {
+        match result {
+            Ok(rps_match) => Self {
+                status_code: 200,
+                status: Ok(rps_match),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use rand::{thread_rng, Rng};
+use super::RpsError;
+
+/// A weapon and the weapons it beats
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Weapon {
+    pub name: String,
+    pub beats: Vec<String>,
+}
+
+/// A set of weapons and who beats who
+/// Balanced: every weapon beats exactly half of the others, so no throw is better than another
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Variant {
+    pub name: String,
+    pub weapons: Vec<Weapon>,
+}
+
+impl Variant {
+    /// Balanced odd cycle, every weapon beats the (n - 1) / 2 weapons listed right before it
+    pub fn cycle(name: &str, weapons: &[&str]) -> Self {
+        let count = weapons.len();
+
+        Self {
+            name: name.to_string(),
+            weapons: c![Weapon {
+                name: weapons[i].to_string(),
+                beats: c![
+                    weapons[(i + count - j) % count].to_string(),
+                    for j in 1..count / 2 + 1
+                ],
+            }, for i in 0..count],
+        }
+    }
+
+    /// Rock paper scissors
+    pub fn classic() -> Self {
+        Self::cycle("rps", &["Rock", "Paper", "Scissors"])
+    }
+
+    /// Rock paper scissors lizard Spock
+    pub fn rpsls() -> Self {
+        Self::cycle("rpsls", &["Rock", "Spock", "Paper", "Lizard", "Scissors"])
+    }
+
+    /// Weapon names joined for error messages
+    pub fn choices(&self) -> String {
+        c![weapon.name.to_lowercase(), for weapon in &self.weapons].join("/")
+    }
+
+    /// Weapon named `input`, ignoring case
+    /// A prefix is accepted as long as only one weapon starts with it
+    pub fn parse(&self, input: &str) -> Result<&Weapon, RpsError> {
+        let input = input.to_lowercase();
+        let invalid = || RpsError::InvalidWeapon(self.choices());
+
+        if input.is_empty() {
+            return Err(invalid());
+        }
+        if let Some(weapon) = self.weapons
+            .iter()
+            .find(|weapon| weapon.name.to_lowercase() == input)
+        {
+            return Ok(weapon);
+        }
+
+        let mut matching = self.weapons
+            .iter()
+            .filter(|weapon| weapon.name.to_lowercase().starts_with(&input));
+        match (matching.next(), matching.next()) {
+            (Some(weapon), None) => Ok(weapon),
+            _ => Err(invalid()),
+        }
+    }
+
+    /// True if `weapon` beats `other`
+    pub fn beats(&self, weapon: &str, other: &str) -> bool {
+        self.weapons
+            .iter()
+            .find(|candidate| candidate.name == weapon)
+            .map_or(false, |weapon| weapon.beats.iter().any(|beaten| beaten == other))
+    }
+
+    /// Some(true) if `player` wins, None on a draw
+    pub fn outcome(&self, player: &str, computer: &str) -> Option<bool> {
+        if player == computer {
+            None
+        } else {
+            Some(self.beats(player, computer))
+        }
+    }
+
+    pub fn random_weapon(&self) -> &Weapon {
+        &self.weapons[thread_rng().gen_range(0, self.weapons.len())]
+    }
+
+    /// Returns every problem with the variant
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        let count = self.weapons.len();
+
+        if count < 3 || count % 2 == 0 {
+            problems.push(format!(
+                "rps variant {} needs an odd number of weapons, at least 3",
+                self.name
+            ));
+        }
+        for (i, weapon) in self.weapons.iter().enumerate() {
+            if weapon.name.is_empty() {
+                problems.push(format!("rps variant {} has an unnamed weapon", self.name));
+            }
+            if self.weapons[..i]
+                .iter()
+                .any(|other| other.name.to_lowercase() == weapon.name.to_lowercase())
+            {
+                problems.push(format!(
+                    "rps variant {} lists {} twice",
+                    self.name, weapon.name
+                ));
+            }
+            if weapon.beats.len() != count / 2 {
+                problems.push(format!(
+                    "{} of rps variant {} must beat exactly {} weapons",
+                    weapon.name,
+                    self.name,
+                    count / 2
+                ));
+            }
+            for (j, beaten) in weapon.beats.iter().enumerate() {
+                if weapon.beats[..j].contains(beaten) {
+                    problems.push(format!(
+                        "{} of rps variant {} lists {} twice",
+                        weapon.name, self.name, beaten
+                    ));
+                } else if !self.weapons.iter().any(|other| other.name == *beaten) {
+                    problems.push(format!(
+                        "{} of rps variant {} beats unknown weapon {}",
+                        weapon.name, self.name, beaten
+                    ));
+                } else if *beaten == weapon.name {
+                    problems.push(format!(
+                        "{} of rps variant {} beats itself",
+                        weapon.name, self.name
+                    ));
+                } else if self.beats(beaten, &weapon.name) {
+                    problems.push(format!(
+                        "{} and {} of rps variant {} beat each other",
+                        weapon.name, beaten, self.name
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
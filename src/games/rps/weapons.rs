@@ -1,11 +1,11 @@
 use rand::{thread_rng, Rng};
-use std::cmp::{Ord, Ordering, PartialOrd};
 use std::error::Error;
 use std::str::FromStr;
 use std::fmt;
+use super::Variant;
 
 /// Rock Paper Scissors
-#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub enum Weapons {
     Rock,
     Paper,
@@ -38,6 +38,16 @@ impl Weapons {
         }
     }
 
+    /// True if `self` beats `other`, Rock beats Scissors beats Paper beats Rock
+    pub fn beats(&self, other: &Self) -> bool {
+        match (*self, *other) {
+            (Weapons::Rock, Weapons::Scissors)
+            | (Weapons::Scissors, Weapons::Paper)
+            | (Weapons::Paper, Weapons::Rock) => true,
+            _ => false,
+        }
+    }
+
     /// Returns a random Weapon
     pub fn rand_weapon() -> Self {
         // Although the type system designates that selecting an Nth char of a
@@ -55,50 +65,17 @@ impl Weapons {
 impl FromStr for Weapons {
     type Err = WeaponParseError;
 
+    /// Full names, or any prefix of them, ignoring case
     fn from_str(weapon: &str) -> Result<Self, Self::Err> {
-        match weapon.to_lowercase().chars().nth(0) {
-            Some(wep) => Ok(match wep {
-                'r' => Weapons::Rock,
-                'p' => Weapons::Paper,
-                's' => Weapons::Scissors,
-                _ => {
-                    return Err(Self::Err {
-                        description: weapon.to_string(),
-                    })
-                }
+        match Variant::classic().parse(weapon) {
+            Ok(parsed) => Ok(match parsed.name.as_str() {
+                "Rock" => Weapons::Rock,
+                "Paper" => Weapons::Paper,
+                _ => Weapons::Scissors,
             }),
-            None => Err(Self::Err {
+            Err(_) => Err(Self::Err {
                 description: weapon.to_string(),
             }),
         }
     }
 }
-
-impl PartialOrd for Weapons {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-// Makes creating the game much simpler
-impl Ord for Weapons {
-    fn cmp(&self, other: &Self) -> Ordering {
-        match *self {
-            Weapons::Rock => match *other {
-                Weapons::Rock => Ordering::Equal,
-                Weapons::Paper => Ordering::Less,
-                Weapons::Scissors => Ordering::Greater,
-            },
-            Weapons::Paper => match *other {
-                Weapons::Rock => Ordering::Greater,
-                Weapons::Paper => Ordering::Equal,
-                Weapons::Scissors => Ordering::Less,
-            },
-            Weapons::Scissors => match *other {
-                Weapons::Rock => Ordering::Less,
-                Weapons::Paper => Ordering::Greater,
-                Weapons::Scissors => Ordering::Equal,
-            },
-        }
-    }
-}
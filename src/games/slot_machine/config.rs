@@ -0,0 +1,254 @@
+use rand::Rng;
+use super::GridConfig;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Default symbols, the last one is the rare ⭐
+pub const ROW: [&str; 7] = ["🍒", "🍊", "🍓", "🍍", "🍇", "🍉", "⭐"];
+
+/// Matches any symbol in a `Combination::Symbols`
+pub const ANY_SYMBOL: &str = "*";
+
+/// A position on a reel strip
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Stop {
+    pub symbol: String,
+    /// Relative chance of the reel landing on this stop
+    pub weight: u32,
+}
+
+/// A reel strip, stops are in the order they appear on the reel
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Reel {
+    pub stops: Vec<Stop>,
+}
+
+impl Reel {
+    pub fn total_weight(&self) -> u64 {
+        self.stops.iter().map(|stop| u64::from(stop.weight)).sum()
+    }
+
+    /// Picks a stop index, weighted by the stop weights
+    pub fn spin<R: Rng>(&self, rng: &mut R) -> usize {
+        let mut roll = rng.gen_range(0, self.total_weight());
+
+        for (index, stop) in self.stops.iter().enumerate() {
+            if roll < u64::from(stop.weight) {
+                return index;
+            }
+            roll -= u64::from(stop.weight);
+        }
+
+        // Only reachable with an empty reel, which validation refuses
+        0
+    }
+
+    pub fn symbol(&self, stop: usize) -> &str {
+        &self.stops[stop % self.stops.len()].symbol
+    }
+
+    /// Chance of each distinct symbol landing
+    pub fn symbol_probabilities(&self) -> Vec<(&str, f64)> {
+        let total = self.total_weight() as f64;
+        let mut weights: Vec<(&str, u64)> = Vec::new();
+
+        for stop in &self.stops {
+            match weights.iter().position(|&(symbol, _)| symbol == stop.symbol) {
+                Some(index) => weights[index].1 += u64::from(stop.weight),
+                None => weights.push((stop.symbol.as_str(), u64::from(stop.weight))),
+            }
+        }
+
+        weights
+            .into_iter()
+            .map(|(symbol, weight)| (symbol, weight as f64 / total))
+            .collect()
+    }
+}
+
+/// A winning combination of symbols
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Combination {
+    /// Any `n` of the same symbol
+    OfAKind(usize),
+    /// These symbols in any order, `ANY_SYMBOL` matches every symbol
+    Symbols(Vec<String>),
+}
+
+impl Display for Combination {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            Combination::OfAKind(n) => write!(f, "Any {} of a kind", n),
+            Combination::Symbols(ref symbols) => f.write_str(&symbols.join(" ")),
+        }
+    }
+}
+
+impl Combination {
+    pub fn matches(&self, symbols: &[&str]) -> bool {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for &symbol in symbols {
+            *counts.entry(symbol).or_insert(0) += 1;
+        }
+
+        match *self {
+            Combination::OfAKind(n) => counts.values().any(|&count| count >= n),
+            Combination::Symbols(ref needed) => {
+                if needed.len() > symbols.len() {
+                    return false;
+                }
+
+                let mut needed_counts: HashMap<&str, usize> = HashMap::new();
+                for symbol in needed {
+                    if symbol != ANY_SYMBOL {
+                        *needed_counts.entry(symbol.as_str()).or_insert(0) += 1;
+                    }
+                }
+
+                needed_counts
+                    .iter()
+                    .all(|(symbol, &count)| counts.get(symbol).map_or(false, |&c| c >= count))
+            }
+        }
+    }
+}
+
+/// Paytable entry
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Payout {
+    pub combination: Combination,
+    /// Returned amount as a multiple of the bet, the bet included (e.g. 50 pays 50x)
+    pub pays: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SlotConfig {
+    pub reels: Vec<Reel>,
+    /// Only the best matching entry pays
+    pub paytable: Vec<Payout>,
+    /// The multi-line machine
+    pub grid: GridConfig,
+}
+
+impl Default for SlotConfig {
+    /// Fruits are equally likely, ⭐ is rarer and pays the most, RTP is ~94.4%
+    fn default() -> Self {
+        let reel = Reel {
+            stops: c![Stop {
+                symbol: symbol.to_string(),
+                weight: if *symbol == "⭐" { 3 } else { 8 },
+            }, for symbol in ROW.iter()],
+        };
+        let star = String::from("⭐");
+
+        Self {
+            reels: vec![reel.clone(), reel.clone(), reel],
+            paytable: vec![
+                Payout {
+                    combination: Combination::Symbols(vec![star.clone(); 3]),
+                    pays: 50.0,
+                },
+                Payout {
+                    combination: Combination::Symbols(vec![
+                        star.clone(),
+                        star,
+                        ANY_SYMBOL.to_string(),
+                    ]),
+                    pays: 5.0,
+                },
+                Payout {
+                    combination: Combination::OfAKind(3),
+                    pays: 10.0,
+                },
+                Payout {
+                    combination: Combination::OfAKind(2),
+                    pays: 1.75,
+                },
+            ],
+            grid: GridConfig::default(),
+        }
+    }
+}
+
+impl SlotConfig {
+    /// What `symbols` pays as a multiple of the bet, 0 if nothing matches
+    pub fn pays(&self, symbols: &[&str]) -> f64 {
+        self.paytable
+            .iter()
+            .filter(|payout| payout.combination.matches(symbols))
+            .map(|payout| payout.pays)
+            .fold(0.0, f64::max)
+    }
+
+    /// Exact expected return per unit bet, computed over every combination of symbols
+    pub fn rtp(&self) -> f64 {
+        let reels: Vec<Vec<(&str, f64)>> = c![reel.symbol_probabilities(), for reel in &self.reels];
+        let mut symbols = Vec::with_capacity(reels.len());
+
+        self.expected_pays(&reels, &mut symbols, 1.0)
+    }
+
+    fn expected_pays<'a>(
+        &self,
+        reels: &[Vec<(&'a str, f64)>],
+        symbols: &mut Vec<&'a str>,
+        probability: f64,
+    ) -> f64 {
+        match reels.split_first() {
+            None => probability * self.pays(symbols),
+            Some((reel, rest)) => {
+                let mut total = 0.0;
+                for &(symbol, chance) in reel {
+                    symbols.push(symbol);
+                    total += self.expected_pays(rest, symbols, probability * chance);
+                    symbols.pop();
+                }
+                total
+            }
+        }
+    }
+
+    /// Returns every problem with the config
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.reels.is_empty() {
+            problems.push(String::from("slot_machine needs at least one reel"));
+        }
+        for (index, reel) in self.reels.iter().enumerate() {
+            if reel.total_weight() == 0 {
+                problems.push(format!(
+                    "slot_machine.reels[{}] needs a stop with a weight above 0",
+                    index
+                ));
+            }
+        }
+        for (index, payout) in self.paytable.iter().enumerate() {
+            if payout.pays < 0.0 {
+                problems.push(format!("slot_machine.paytable[{}] pays can't be negative", index));
+            }
+            let len = match payout.combination {
+                Combination::OfAKind(n) => n,
+                Combination::Symbols(ref symbols) => symbols.len(),
+            };
+            if len == 0 || len > self.reels.len() {
+                problems.push(format!(
+                    "slot_machine.paytable[{}] needs between 1 and {} symbols",
+                    index,
+                    self.reels.len()
+                ));
+            }
+        }
+        if let Err(grid_problems) = self.grid.validate() {
+            problems.extend(grid_problems);
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+#[derive(Debug)]
+pub enum SlotError {
+    BetTooLarge,
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    /// Free spins left to play before the next paid spin
+    FreeSpinsPending(u32),
+    /// Number of paylines of the machine
+    InvalidLines(usize),
+    NoFreeSpins,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+}
+
+impl Display for SlotError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            SlotError::FreeSpinsPending(left) => {
+                write!(f, "{} free spins must be played first", left)
+            }
+            SlotError::InvalidLines(max) => write!(f, "Lines must be between 1 and {}", max),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for SlotError {
+    fn description(&self) -> &str {
+        use self::SlotError::*;
+        match *self {
+            BetTooLarge => "Bet is too large",
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            FreeSpinsPending(_) => "Free spins must be played first",
+            InvalidLines(_) => "Invalid number of lines",
+            NoFreeSpins => "No free spins left",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for SlotError {
+    fn from(err: DieselResultError) -> Self {
+        SlotError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for SlotError {
+    fn from(err: R2d2Error) -> Self {
+        SlotError::R2d2(err)
+    }
+}
+
+impl SlotError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::SlotError::*;
+        match *self {
+            BetTooLarge | FreeSpinsPending(_) | InvalidLines(_) | NoFreeSpins => 501,
+            #[cfg(feature = "auto_save")]
+            _ => 500,
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use diesel;
+use diesel::prelude::*;
+use jackpot::{Jackpot, JackpotConfig};
+use super::{FreeSpinsSession, GridConfig, GridSpin, SlotError};
+use ConnectionPool;
+
+/// Free spins a user has left, stored between requests
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct FreeSpins {
+    pub remaining: u32,
+    pub line_bet: u64,
+    pub lines: usize,
+    pub multiplier: f64,
+}
+
+impl From<FreeSpinsSession> for FreeSpins {
+    fn from(session: FreeSpinsSession) -> Self {
+        Self {
+            remaining: session.remaining as u32,
+            line_bet: session.line_bet as u64,
+            lines: session.lines as usize,
+            multiplier: session.multiplier,
+        }
+    }
+}
+
+impl FreeSpins {
+    /// Free spins of `player`, all zero if they have none
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Self, SlotError> {
+        use schema::slot_free_spins::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = slot_free_spins
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<FreeSpinsSession>(&*conn)
+            .optional()?;
+
+        Ok(session.map(Self::from).unwrap_or_default())
+    }
+
+    /// Paid spin for `player`, refused while they have free spins left
+    /// Free spins won are stored with the bet of this spin, part of the bet feeds the jackpot
+    pub fn spin(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        line_bet: u64,
+        line_count: usize,
+        config: &GridConfig,
+        jackpot: &JackpotConfig,
+    ) -> Result<GridSpin, SlotError> {
+        use schema::slot_free_spins;
+        // line_bet is left out, it would shadow the argument
+        use schema::slot_free_spins::dsl::{id, namespace, remaining};
+
+        let conn = db_pool.get()?;
+        conn.transaction(|| {
+            let pending = slot_free_spins::table
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64))
+                .select(remaining)
+                .first::<i32>(&*conn)
+                .optional()?;
+
+            if let Some(left) = pending {
+                return Err(SlotError::FreeSpinsPending(left as u32));
+            }
+
+            let mut spin = GridSpin::spin(line_bet, line_count, config)?;
+            spin.jackpot =
+                Jackpot::contribute(&*conn, jackpot.contribution_of(spin.bet), jackpot.seed)?;
+
+            if spin.free_spins_won > 0 {
+                let session = FreeSpinsSession {
+                    namespace: player_namespace,
+                    id: player as i64,
+                    remaining: spin.free_spins_won as i32,
+                    line_bet: line_bet as i64,
+                    lines: line_count as i32,
+                    multiplier: config
+                        .scatter
+                        .as_ref()
+                        .map_or(1.0, |scatter| scatter.multiplier),
+                };
+
+                // Another spin may have won free spins since the check above
+                let inserted = diesel::insert_into(slot_free_spins::table)
+                    .values(&session)
+                    .on_conflict_do_nothing()
+                    .execute(&*conn)?;
+                if inserted != 1 {
+                    let left = slot_free_spins::table
+                        .filter(namespace.eq(player_namespace))
+                        .filter(id.eq(player as i64))
+                        .select(remaining)
+                        .first::<i32>(&*conn)
+                        .optional()?;
+                    return Err(SlotError::FreeSpinsPending(left.map_or(0, |left| left as u32)));
+                }
+            }
+
+            Ok(spin)
+        })
+    }
+
+    /// Plays one of `player`'s free spins, retriggers add to the spins left
+    pub fn free_spin(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        config: &GridConfig,
+        jackpot: &JackpotConfig,
+    ) -> Result<GridSpin, SlotError> {
+        use schema::slot_free_spins::dsl::*;
+
+        let conn = db_pool.get()?;
+        conn.transaction(|| {
+            // Taking the spin in the update keeps concurrent requests from playing it twice
+            let session = diesel::update(
+                slot_free_spins
+                    .filter(namespace.eq(player_namespace))
+                    .filter(id.eq(player as i64))
+                    .filter(remaining.gt(0)),
+            )
+                .set(remaining.eq(remaining - 1))
+                .get_result::<FreeSpinsSession>(&*conn)
+                .optional()?
+                .ok_or(SlotError::NoFreeSpins)?;
+
+            let mut spin = GridSpin::free_spin(
+                session.line_bet as u64,
+                session.lines as usize,
+                session.multiplier,
+                config,
+            )?;
+            let left = session.remaining as u32 + spin.free_spins_won;
+
+            if left == 0 {
+                diesel::delete(&session).execute(&*conn)?;
+            } else if spin.free_spins_won > 0 {
+                diesel::update(&session)
+                    .set(remaining.eq(left as i32))
+                    .execute(&*conn)?;
+            }
+
+            spin.free_spins_remaining = left;
+            spin.jackpot = Jackpot::current(&*conn, jackpot.seed)?;
+
+            Ok(spin)
+        })
+    }
+}
@@ -0,0 +1,547 @@
+use metrics::{payout, METRICS};
+use rand::thread_rng;
+use super::{Reel, SlotError, Stop, ROW};
+
+/// Default wild symbol, substitutes for every symbol but the scatter
+pub const WILD: &str = "🃏";
+
+/// Default scatter symbol, pays anywhere on the grid
+pub const SCATTER: &str = "💎";
+
+/// Pays when `count` or more `symbol`s line up from the leftmost reel
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LinePayout {
+    pub symbol: String,
+    pub count: usize,
+    /// Returned amount as a multiple of the line bet
+    pub pays: f64,
+}
+
+/// Pays when `count` or more scatters are anywhere on the grid
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ScatterPayout {
+    pub count: usize,
+    /// Returned amount as a multiple of the total bet
+    pub pays: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Scatter {
+    pub symbol: String,
+    pub paytable: Vec<ScatterPayout>,
+    /// Scatters needed to win free spins
+    pub trigger: usize,
+    pub free_spins: u32,
+    /// Applied to every win during free spins
+    pub multiplier: f64,
+}
+
+impl Default for Scatter {
+    fn default() -> Self {
+        Self {
+            symbol: SCATTER.to_string(),
+            paytable: vec![
+                ScatterPayout { count: 3, pays: 2.0 },
+                ScatterPayout {
+                    count: 4,
+                    pays: 10.0,
+                },
+                ScatterPayout {
+                    count: 5,
+                    pays: 50.0,
+                },
+            ],
+            trigger: 3,
+            free_spins: 10,
+            multiplier: 2.0,
+        }
+    }
+}
+
+/// A video slot, `rows` symbols of every reel are visible
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GridConfig {
+    pub rows: usize,
+    pub reels: Vec<Reel>,
+    /// Row picked on every reel, top row is 0
+    pub paylines: Vec<Vec<usize>>,
+    pub paytable: Vec<LinePayout>,
+    pub wild: Option<String>,
+    pub scatter: Option<Scatter>,
+}
+
+impl Default for GridConfig {
+    /// 3x5 with 9 paylines, every row has the same odds and the RTP is ~96.4% with free spins
+    fn default() -> Self {
+        // Each fruit is on 3 of the 22 stops, ⭐ on 2, the wild and the scatter on 1
+        let mut stops = Vec::with_capacity(22);
+        for _ in 0..3 {
+            for symbol in ROW.iter().filter(|symbol| **symbol != "⭐") {
+                stops.push(Stop {
+                    symbol: symbol.to_string(),
+                    weight: 1,
+                });
+            }
+        }
+        for &(index, symbol) in &[(6, "⭐"), (10, WILD), (14, "⭐"), (19, SCATTER)] {
+            stops.insert(
+                index,
+                Stop {
+                    symbol: symbol.to_string(),
+                    weight: 1,
+                },
+            );
+        }
+        let reel = Reel { stops };
+
+        let mut paytable = Vec::new();
+        for symbol in ROW.iter().chain(&[WILD]) {
+            let pays: [f64; 3] = match *symbol {
+                WILD => [50.0, 250.0, 1000.0],
+                "⭐" => [20.0, 100.0, 500.0],
+                _ => [5.0, 30.0, 150.0],
+            };
+            for (count, pays) in (3..6).zip(pays.iter()) {
+                paytable.push(LinePayout {
+                    symbol: symbol.to_string(),
+                    count,
+                    pays: *pays,
+                });
+            }
+        }
+
+        Self {
+            rows: 3,
+            reels: vec![reel; 5],
+            paylines: vec![
+                vec![1, 1, 1, 1, 1],
+                vec![0, 0, 0, 0, 0],
+                vec![2, 2, 2, 2, 2],
+                vec![0, 1, 2, 1, 0],
+                vec![2, 1, 0, 1, 2],
+                vec![0, 0, 1, 2, 2],
+                vec![2, 2, 1, 0, 0],
+                vec![1, 0, 0, 0, 1],
+                vec![1, 2, 2, 2, 1],
+            ],
+            paytable,
+            wild: Some(WILD.to_string()),
+            scatter: Some(Scatter::default()),
+        }
+    }
+}
+
+impl GridConfig {
+    fn is_wild(&self, symbol: &str) -> bool {
+        self.wild.as_ref().map_or(false, |wild| wild == symbol)
+    }
+
+    fn is_scatter(&self, symbol: &str) -> bool {
+        self.scatter
+            .as_ref()
+            .map_or(false, |scatter| scatter.symbol == symbol)
+    }
+
+    /// Best entry for a run of `count` `symbol`s
+    fn best_payout(&self, symbol: &str, count: usize) -> Option<&LinePayout> {
+        self.paytable
+            .iter()
+            .filter(|payout| payout.symbol == symbol && payout.count <= count)
+            .max_by(|a, b| a.pays.partial_cmp(&b.pays).unwrap_or(::std::cmp::Ordering::Equal))
+    }
+
+    /// What a line of `symbols` pays as a multiple of the line bet, with the winning symbol and count
+    /// Wilds count as the first other symbol of the line, or pay on their own if that's better
+    pub fn line_pays(&self, symbols: &[&str]) -> Option<(String, usize, f64)> {
+        let wilds = symbols
+            .iter()
+            .take_while(|symbol| self.is_wild(symbol))
+            .count();
+
+        let wild_payout = match self.wild {
+            Some(ref wild) if wilds > 0 => self.best_payout(wild, wilds),
+            _ => None,
+        };
+        let symbol_payout = match symbols.get(wilds) {
+            Some(target) if !self.is_scatter(target) => {
+                let run = wilds
+                    + symbols[wilds..]
+                        .iter()
+                        .take_while(|symbol| *symbol == target || self.is_wild(symbol))
+                        .count();
+                self.best_payout(target, run)
+            }
+            _ => None,
+        };
+
+        let best = match (wild_payout, symbol_payout) {
+            (Some(wild), Some(symbol)) => Some(if wild.pays > symbol.pays { wild } else { symbol }),
+            (wild, symbol) => wild.or(symbol),
+        };
+
+        best.map(|payout| (payout.symbol.clone(), payout.count, payout.pays))
+    }
+
+    /// What `count` scatters pay as a multiple of the total bet
+    pub fn scatter_pays(&self, count: usize) -> f64 {
+        self.scatter.as_ref().map_or(0.0, |scatter| {
+            scatter
+                .paytable
+                .iter()
+                .filter(|payout| payout.count <= count)
+                .map(|payout| payout.pays)
+                .fold(0.0, f64::max)
+        })
+    }
+
+    /// Free spins won by `count` scatters
+    pub fn free_spins_won(&self, count: usize) -> u32 {
+        match self.scatter {
+            Some(ref scatter) if count >= scatter.trigger => scatter.free_spins,
+            _ => 0,
+        }
+    }
+
+    /// Chance of each distinct symbol showing on `row` of `reel`
+    fn row_probabilities(reel: &Reel, row: usize) -> Vec<(&str, f64)> {
+        let total = reel.total_weight() as f64;
+        let mut chances: Vec<(&str, f64)> = Vec::new();
+
+        for (index, stop) in reel.stops.iter().enumerate() {
+            let symbol = reel.symbol(index + row);
+            let chance = f64::from(stop.weight) / total;
+            match chances.iter().position(|&(s, _)| s == symbol) {
+                Some(position) => chances[position].1 += chance,
+                None => chances.push((symbol, chance)),
+            }
+        }
+
+        chances
+    }
+
+    /// Exact expected return per unit line bet of payline `line`
+    pub fn line_rtp(&self, line: usize) -> f64 {
+        let reels: Vec<Vec<(&str, f64)>> = self.reels
+            .iter()
+            .zip(self.paylines[line].iter())
+            .map(|(reel, row)| Self::row_probabilities(reel, *row))
+            .collect();
+        let mut symbols = Vec::with_capacity(reels.len());
+
+        self.expected_pays(&reels, &mut symbols, 1.0)
+    }
+
+    /// Exact expected line wins per unit bet when playing the first `lines` paylines
+    pub fn lines_rtp(&self, lines: usize) -> f64 {
+        let lines = ::std::cmp::min(lines, self.paylines.len());
+        if lines == 0 {
+            return 0.0;
+        }
+
+        (0..lines).map(|line| self.line_rtp(line)).sum::<f64>() / lines as f64
+    }
+
+    /// Chance of `n` scatters showing on the grid, indexed by `n`
+    pub fn scatter_chances(&self) -> Vec<f64> {
+        let mut chances = vec![1.0];
+        let symbol = match self.scatter {
+            Some(ref scatter) => scatter.symbol.as_str(),
+            None => return chances,
+        };
+
+        for reel in &self.reels {
+            let total = reel.total_weight() as f64;
+            let mut reel_chances = vec![0.0; self.rows + 1];
+            for (index, stop) in reel.stops.iter().enumerate() {
+                let shown = (0..self.rows)
+                    .filter(|row| reel.symbol(index + row) == symbol)
+                    .count();
+                reel_chances[shown] += f64::from(stop.weight) / total;
+            }
+
+            let mut next = vec![0.0; chances.len() + self.rows];
+            for (count, chance) in chances.iter().enumerate() {
+                for (shown, reel_chance) in reel_chances.iter().enumerate() {
+                    next[count + shown] += chance * reel_chance;
+                }
+            }
+            chances = next;
+        }
+
+        chances
+    }
+
+    /// Exact expected scatter wins per unit bet
+    pub fn scatter_rtp(&self) -> f64 {
+        self.scatter_chances()
+            .iter()
+            .enumerate()
+            .map(|(count, chance)| chance * self.scatter_pays(count))
+            .sum()
+    }
+
+    /// Chance of a spin winning free spins
+    pub fn trigger_chance(&self) -> f64 {
+        self.scatter_chances()
+            .iter()
+            .enumerate()
+            .filter(|&(count, _)| self.free_spins_won(count) > 0)
+            .map(|(_, chance)| chance)
+            .sum()
+    }
+
+    /// Expected free spins played per trigger, retriggers included
+    /// None if retriggers are expected to go on forever
+    pub fn expected_free_spins(&self) -> Option<f64> {
+        let spins = self.scatter.as_ref().map_or(0, |scatter| scatter.free_spins);
+        let retriggered = self.trigger_chance() * f64::from(spins);
+
+        if retriggered < 1.0 {
+            Some(f64::from(spins) / (1.0 - retriggered))
+        } else {
+            None
+        }
+    }
+
+    /// Exact expected return per unit bet when playing the first `lines` paylines,
+    /// free spins included
+    pub fn rtp(&self, lines: usize) -> f64 {
+        let base = self.lines_rtp(lines) + self.scatter_rtp();
+        let multiplier = self.scatter.as_ref().map_or(0.0, |scatter| scatter.multiplier);
+
+        match self.expected_free_spins() {
+            Some(spins) => base * (1.0 + self.trigger_chance() * spins * multiplier),
+            None => ::std::f64::INFINITY,
+        }
+    }
+
+    fn expected_pays<'a>(
+        &self,
+        reels: &[Vec<(&'a str, f64)>],
+        symbols: &mut Vec<&'a str>,
+        probability: f64,
+    ) -> f64 {
+        match reels.split_first() {
+            None => probability * self.line_pays(symbols).map_or(0.0, |(_, _, pays)| pays),
+            Some((reel, rest)) => {
+                let mut total = 0.0;
+                for &(symbol, chance) in reel {
+                    symbols.push(symbol);
+                    total += self.expected_pays(rest, symbols, probability * chance);
+                    symbols.pop();
+                }
+                total
+            }
+        }
+    }
+
+    /// Returns every problem with the config
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        if self.rows == 0 {
+            problems.push(String::from("slot_machine.grid.rows must be above 0"));
+        }
+        if self.reels.is_empty() {
+            problems.push(String::from("slot_machine.grid needs at least one reel"));
+        }
+        for (index, reel) in self.reels.iter().enumerate() {
+            if reel.total_weight() == 0 {
+                problems.push(format!(
+                    "slot_machine.grid.reels[{}] needs a stop with a weight above 0",
+                    index
+                ));
+            }
+        }
+        if self.paylines.is_empty() {
+            problems.push(String::from("slot_machine.grid needs at least one payline"));
+        }
+        for (index, line) in self.paylines.iter().enumerate() {
+            if line.len() != self.reels.len() || line.iter().any(|row| *row >= self.rows) {
+                problems.push(format!(
+                    "slot_machine.grid.paylines[{}] needs a row below {} for each of the {} reels",
+                    index,
+                    self.rows,
+                    self.reels.len()
+                ));
+            }
+        }
+        for (index, payout) in self.paytable.iter().enumerate() {
+            if payout.pays < 0.0 {
+                problems.push(format!(
+                    "slot_machine.grid.paytable[{}] pays can't be negative",
+                    index
+                ));
+            }
+            if payout.count == 0 || payout.count > self.reels.len() {
+                problems.push(format!(
+                    "slot_machine.grid.paytable[{}] count must be between 1 and {}",
+                    index,
+                    self.reels.len()
+                ));
+            }
+        }
+        if let Some(ref scatter) = self.scatter {
+            if self.wild.as_ref() == Some(&scatter.symbol) {
+                problems.push(String::from(
+                    "slot_machine.grid.scatter.symbol can't also be the wild",
+                ));
+            }
+            if scatter.trigger == 0 {
+                problems.push(String::from(
+                    "slot_machine.grid.scatter.trigger must be above 0",
+                ));
+            }
+            if scatter.multiplier < 0.0 {
+                problems.push(String::from(
+                    "slot_machine.grid.scatter.multiplier can't be negative",
+                ));
+            }
+            for (index, payout) in scatter.paytable.iter().enumerate() {
+                if payout.pays < 0.0 {
+                    problems.push(format!(
+                        "slot_machine.grid.scatter.paytable[{}] pays can't be negative",
+                        index
+                    ));
+                }
+            }
+        }
+
+        // The odds are only meaningful once the grid itself is valid
+        if problems.is_empty() && self.expected_free_spins().is_none() {
+            problems.push(String::from(
+                "slot_machine.grid.scatter retriggers free spins forever, lower free_spins",
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// A winning payline
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct LineWin {
+    /// Index into the configured paylines
+    pub line: usize,
+    pub symbol: String,
+    pub count: usize,
+    pub payout: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GridSpin {
+    /// grid[row][reel]
+    pub grid: Vec<Vec<String>>,
+    pub lines: usize,
+    pub line_bet: u64,
+    /// line_bet * lines, nothing is wagered on free spins
+    pub bet: u64,
+    pub wins: Vec<LineWin>,
+    pub scatters: usize,
+    pub scatter_payout: u64,
+    /// Applied to every win, above 1 during free spins
+    pub multiplier: f64,
+    pub free_spin: bool,
+    pub free_spins_won: u32,
+    /// Free spins left to play after this spin
+    pub free_spins_remaining: u32,
+    /// Progressive jackpot pool after this spin
+    pub jackpot: u64,
+    pub gain: i64,
+}
+
+impl GridSpin {
+    /// Spins the grid betting `line_bet` on each of the first `lines` paylines
+    pub fn spin(line_bet: u64, lines: usize, config: &GridConfig) -> Result<Self, SlotError> {
+        Self::play(line_bet, lines, 1.0, false, config)
+    }
+
+    /// Spins the grid without wagering, wins are multiplied by `multiplier`
+    pub fn free_spin(
+        line_bet: u64,
+        lines: usize,
+        multiplier: f64,
+        config: &GridConfig,
+    ) -> Result<Self, SlotError> {
+        Self::play(line_bet, lines, multiplier, true, config)
+    }
+
+    fn play(
+        line_bet: u64,
+        lines: usize,
+        multiplier: f64,
+        free_spin: bool,
+        config: &GridConfig,
+    ) -> Result<Self, SlotError> {
+        if lines == 0 || lines > config.paylines.len() {
+            return Err(SlotError::InvalidLines(config.paylines.len()));
+        }
+        let bet = line_bet
+            .checked_mul(lines as u64)
+            .ok_or(SlotError::BetTooLarge)?;
+
+        let mut rng = thread_rng();
+        let stops: Vec<usize> = c![reel.spin(&mut rng), for reel in &config.reels];
+        let grid: Vec<Vec<&str>> = c![
+            config
+                .reels
+                .iter()
+                .zip(stops.iter())
+                .map(|(reel, &stop)| reel.symbol(stop + row))
+                .collect(),
+            for row in 0..config.rows
+        ];
+
+        let mut wins = Vec::new();
+        for (line, rows) in config.paylines.iter().take(lines).enumerate() {
+            let symbols: Vec<&str> = rows.iter()
+                .enumerate()
+                .map(|(reel, row)| grid[*row][reel])
+                .collect();
+
+            if let Some((symbol, count, pays)) = config.line_pays(&symbols) {
+                wins.push(LineWin {
+                    line,
+                    symbol,
+                    count,
+                    payout: (line_bet as f64 * pays * multiplier) as u64,
+                });
+            }
+        }
+
+        let scatters = grid.iter()
+            .flat_map(|row| row.iter())
+            .filter(|symbol| config.is_scatter(symbol))
+            .count();
+        let scatter_payout = (bet as f64 * config.scatter_pays(scatters) * multiplier) as u64;
+        let free_spins_won = config.free_spins_won(scatters);
+
+        let wagered = if free_spin { 0 } else { bet };
+        let paid: u64 = wins.iter().map(|win| win.payout).sum::<u64>() + scatter_payout;
+        let gain = paid as i64 - wagered as i64;
+
+        METRICS.record_game("slot_machine_grid", wagered, payout(wagered, gain));
+
+        Ok(Self {
+            grid: c![c![symbol.to_string(), for symbol in row], for row in grid],
+            lines,
+            line_bet,
+            bet: wagered,
+            wins,
+            scatters,
+            scatter_payout,
+            multiplier,
+            free_spin,
+            free_spins_won,
+            free_spins_remaining: free_spins_won,
+            jackpot: 0,
+            gain,
+        })
+    }
+}
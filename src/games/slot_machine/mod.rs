@@ -0,0 +1,21 @@
+mod config;
+mod error;
+#[cfg(feature = "auto_save")]
+mod free_spins;
+mod grid;
+mod response;
+mod slot_game;
+
+pub use self::config::{Combination, Payout, Reel, SlotConfig, Stop, ANY_SYMBOL, ROW};
+pub use self::error::SlotError;
+#[cfg(feature = "auto_save")]
+pub use self::free_spins::FreeSpins;
+#[cfg(feature = "auto_save")]
+pub use models::FreeSpinsSession;
+pub use self::grid::{GridConfig, GridSpin, LinePayout, LineWin, Scatter, ScatterPayout, SCATTER,
+                     WILD};
+pub use self::response::{GridInfo, GridInfoResponse, GridResponse, Info, InfoResponse,
+                         Response};
+#[cfg(feature = "auto_save")]
+pub use self::response::FreeSpinsResponse;
+pub use self::slot_game::SlotMachine;
@@ -0,0 +1,123 @@
+#[cfg(feature = "auto_save")]
+use super::FreeSpins;
+use super::{GridConfig, GridSpin, LinePayout, Scatter, SlotConfig, SlotError, SlotMachine};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Response {
+    pub status_code: u16,
+    pub status: Result<SlotMachine, String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Info {
+    /// Expected return per unit bet
+    pub rtp: f64,
+    pub reels: usize,
+    pub paytable: Vec<(String, f64)>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct InfoResponse {
+    pub status_code: u16,
+    pub status: Result<Info, String>,
+}
+
+impl InfoResponse {
+    pub fn new(config: &SlotConfig) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(Info {
+                rtp: config.rtp(),
+                reels: config.reels.len(),
+                paytable: c![
+                    (payout.combination.to_string(), payout.pays),
+                    for payout in &config.paytable
+                ],
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GridResponse {
+    pub status_code: u16,
+    pub status: Result<GridSpin, String>,
+}
+
+impl GridResponse {
+    pub fn success(spin: GridSpin) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(spin),
+        }
+    }
+
+    pub fn error(err: &SlotError) -> Self {
+        Self {
+            status_code: err.status_code(),
+            status: Err(err.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct FreeSpinsResponse {
+    pub status_code: u16,
+    pub status: Result<FreeSpins, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl FreeSpinsResponse {
+    pub fn success(free_spins: FreeSpins) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(free_spins),
+        }
+    }
+
+    pub fn error(err: &SlotError) -> Self {
+        Self {
+            status_code: err.status_code(),
+            status: Err(err.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GridInfo {
+    pub rows: usize,
+    pub reels: usize,
+    pub paylines: Vec<Vec<usize>>,
+    /// Expected line wins per unit line bet of each payline
+    pub line_rtp: Vec<f64>,
+    /// Expected return per unit bet playing every payline, free spins included
+    pub rtp: f64,
+    pub paytable: Vec<LinePayout>,
+    pub wild: Option<String>,
+    pub scatter: Option<Scatter>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct GridInfoResponse {
+    pub status_code: u16,
+    pub status: Result<GridInfo, String>,
+}
+
+impl GridInfoResponse {
+    pub fn new(config: &GridConfig) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(GridInfo {
+                rows: config.rows,
+                reels: config.reels.len(),
+                paylines: config.paylines.clone(),
+                line_rtp: c![config.line_rtp(line), for line in 0..config.paylines.len()],
+                rtp: config.rtp(config.paylines.len()),
+                paytable: config.paytable.clone(),
+                wild: config.wild.clone(),
+                scatter: config.scatter.clone(),
+            }),
+        }
+    }
+}
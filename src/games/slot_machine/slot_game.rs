@@ -0,0 +1,83 @@
+#[cfg(feature = "auto_save")]
+use jackpot::{Jackpot, JackpotConfig, JackpotError};
+use metrics::{payout, METRICS};
+use rand::thread_rng;
+use super::SlotConfig;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+lazy_static! {
+    static ref DEFAULT_CONFIG: SlotConfig = SlotConfig::default();
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct SlotMachine {
+    pub picks: Vec<String>,
+    pub bet: u64,
+    /// Jackpot winnings included
+    pub gain: i64,
+    /// Progressive jackpot pool after this spin
+    pub jackpot: u64,
+    pub jackpot_won: u64,
+}
+
+impl SlotMachine {
+    /// Spins the default machine
+    pub fn new(bet: u64) -> Self {
+        Self::with_config(bet, &DEFAULT_CONFIG)
+    }
+
+    /// Spins every reel of `config` once, paying by its paytable
+    pub fn with_config(bet: u64, config: &SlotConfig) -> Self {
+        let spin = Self::spin(bet, config);
+        METRICS.record_game("slot_machine", bet, payout(bet, spin.gain));
+        spin
+    }
+
+    /// Same as `with_config`, leaving the metrics to the caller
+    fn spin(bet: u64, config: &SlotConfig) -> Self {
+        let mut rng = thread_rng();
+        let picks: Vec<&str> = c![reel.symbol(reel.spin(&mut rng)), for reel in &config.reels];
+
+        #[cfg(test)]
+        {
+            assert_eq!(picks.len(), config.reels.len());
+        }
+
+        let gain = (bet as f64 * config.pays(&picks)) as i64 - bet as i64;
+
+        Self {
+            bet,
+            gain,
+            picks: c![pick.to_string(), for pick in picks],
+            jackpot: 0,
+            jackpot_won: 0,
+        }
+    }
+
+    /// Spins `config`, feeding the progressive jackpot and winning it on `jackpot.combination`
+    #[cfg(feature = "auto_save")]
+    pub fn with_jackpot(
+        bet: u64,
+        config: &SlotConfig,
+        jackpot: &JackpotConfig,
+        db_pool: &ConnectionPool,
+    ) -> Result<Self, JackpotError> {
+        let mut spin = Self::spin(bet, config);
+        let hit = {
+            let picks: Vec<&str> = c![pick.as_str(), for pick in &spin.picks];
+            jackpot.combination.matches(&picks)
+        };
+
+        let conn = db_pool.get()?;
+        let result = Jackpot::wager(&*conn, jackpot.contribution_of(bet), hit, jackpot.seed)?;
+
+        spin.jackpot = result.pool;
+        spin.jackpot_won = result.won;
+        spin.gain += result.won as i64;
+        // Recorded once the jackpot is in, it is part of what the spin paid out
+        METRICS.record_game("slot_machine", bet, payout(bet, spin.gain));
+
+        Ok(spin)
+    }
+}
@@ -0,0 +1,85 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use games::{StandardCardParseError, StandardDeckError};
+
+#[derive(Debug)]
+pub enum VideoPokerError {
+    CardParse(StandardCardParseError),
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    HandInProgress,
+    InvalidBet(String),
+    InvalidHold,
+    NoCard,
+    NoHand,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+}
+
+impl Display for VideoPokerError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            VideoPokerError::InvalidBet(ref why) => f.write_str(why),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for VideoPokerError {
+    fn description(&self) -> &str {
+        use self::VideoPokerError::*;
+        match *self {
+            CardParse(ref inner) => inner.description(),
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            HandInProgress => "A hand is already dealt, please draw first",
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            InvalidHold => "Hold with 5 characters, 1 to hold a card and 0 to draw it, e.g. 11001",
+            NoCard => "No card was able to be drawn",
+            NoHand => "No hand was dealt",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+impl From<StandardCardParseError> for VideoPokerError {
+    fn from(err: StandardCardParseError) -> Self {
+        VideoPokerError::CardParse(err)
+    }
+}
+
+impl From<StandardDeckError> for VideoPokerError {
+    fn from(_: StandardDeckError) -> Self {
+        VideoPokerError::NoCard
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for VideoPokerError {
+    fn from(err: DieselResultError) -> Self {
+        VideoPokerError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for VideoPokerError {
+    fn from(err: R2d2Error) -> Self {
+        VideoPokerError::R2d2(err)
+    }
+}
+
+impl VideoPokerError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::VideoPokerError::*;
+        match *self {
+            HandInProgress | InvalidBet(_) | InvalidHold | NoHand => 501,
+            _ => 500,
+        }
+    }
+}
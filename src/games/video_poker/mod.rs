@@ -0,0 +1,8 @@
+mod error;
+mod video_poker_game;
+
+pub use self::error::VideoPokerError;
+pub use self::video_poker_game::{draw, parse_held, Paytable, PaytableEntry, PaytableResponse,
+                                 VideoPokerHand, VideoPokerResponse, HAND_SIZE};
+#[cfg(feature = "auto_save")]
+pub use self::video_poker_game::{VideoPoker, VideoPokerStatusResponse};
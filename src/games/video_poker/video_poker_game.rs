@@ -0,0 +1,348 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+use games::poker::{evaluate, HandCategory, HandRank};
+use games::{StandardCard, StandardDeck};
+#[cfg(feature = "auto_save")]
+use metrics::METRICS;
+#[cfg(feature = "auto_save")]
+use models::VideoPokerSession;
+use super::VideoPokerError;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+pub const HAND_SIZE: usize = 5;
+
+/// Jacks or Better paytable, amounts returned per unit bet
+/// The defaults are the full pay 9/6 table
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Paytable {
+    pub royal_flush: u64,
+    pub straight_flush: u64,
+    pub four_of_a_kind: u64,
+    pub full_house: u64,
+    pub flush: u64,
+    pub straight: u64,
+    pub three_of_a_kind: u64,
+    pub two_pair: u64,
+    /// A pair of jacks, queens, kings or aces
+    pub jacks_or_better: u64,
+}
+
+impl Default for Paytable {
+    fn default() -> Self {
+        Self {
+            royal_flush: 800,
+            straight_flush: 50,
+            four_of_a_kind: 25,
+            full_house: 9,
+            flush: 6,
+            straight: 4,
+            three_of_a_kind: 3,
+            two_pair: 2,
+            jacks_or_better: 1,
+        }
+    }
+}
+
+impl Paytable {
+    /// Name and amount paid per unit bet of the paying hand `rank` is, None if it doesn't pay
+    pub fn paying_hand(&self, rank: &HandRank) -> Option<(&'static str, u64)> {
+        match rank.category {
+            HandCategory::StraightFlush if rank.is_royal() => {
+                Some(("Royal Flush", self.royal_flush))
+            }
+            HandCategory::StraightFlush => Some(("Straight Flush", self.straight_flush)),
+            HandCategory::FourOfAKind => Some(("Four of a Kind", self.four_of_a_kind)),
+            HandCategory::FullHouse => Some(("Full House", self.full_house)),
+            HandCategory::Flush => Some(("Flush", self.flush)),
+            HandCategory::Straight => Some(("Straight", self.straight)),
+            HandCategory::ThreeOfAKind => Some(("Three of a Kind", self.three_of_a_kind)),
+            HandCategory::TwoPair => Some(("Two Pair", self.two_pair)),
+            // Jacks are 11
+            HandCategory::OnePair if rank.ranks[0] >= 11 => {
+                Some(("Jacks or Better", self.jacks_or_better))
+            }
+            _ => None,
+        }
+    }
+
+    /// Every paying hand and what it pays, best first
+    pub fn entries(&self) -> Vec<PaytableEntry> {
+        [
+            ("Royal Flush", self.royal_flush),
+            ("Straight Flush", self.straight_flush),
+            ("Four of a Kind", self.four_of_a_kind),
+            ("Full House", self.full_house),
+            ("Flush", self.flush),
+            ("Straight", self.straight),
+            ("Three of a Kind", self.three_of_a_kind),
+            ("Two Pair", self.two_pair),
+            ("Jacks or Better", self.jacks_or_better),
+        ].iter()
+            .map(|&(hand, pays)| PaytableEntry {
+                hand: hand.to_string(),
+                pays,
+            })
+            .collect()
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableEntry {
+    pub hand: String,
+    /// Returned amount as a multiple of the bet
+    pub pays: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PaytableResponse {
+    pub status_code: u16,
+    pub status: Result<Vec<PaytableEntry>, String>,
+}
+
+impl PaytableResponse {
+    pub fn new(paytable: &Paytable) -> Self {
+        Self {
+            status_code: 200,
+            status: Ok(paytable.entries()),
+        }
+    }
+}
+
+/// Parses held cards, 5 characters with 1 to hold and 0 to draw
+pub fn parse_held(held: &str) -> Result<Vec<bool>, VideoPokerError> {
+    if held.chars().count() != HAND_SIZE {
+        return Err(VideoPokerError::InvalidHold);
+    }
+
+    held.chars()
+        .map(|c| match c {
+            '1' => Ok(true),
+            '0' => Ok(false),
+            _ => Err(VideoPokerError::InvalidHold),
+        })
+        .collect()
+}
+
+/// Replaces every card of `hand` that isn't held with one from `deck`
+pub fn draw(
+    hand: &mut [StandardCard],
+    held: &[bool],
+    deck: &mut StandardDeck,
+) -> Result<(), VideoPokerError> {
+    if held.len() != hand.len() {
+        return Err(VideoPokerError::InvalidHold);
+    }
+
+    for (card, &keep) in hand.iter_mut().zip(held) {
+        if !keep {
+            *card = deck.draw()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// A hand right after the deal or after the draw
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VideoPokerHand {
+    pub hand: Vec<String>,
+    /// Cards held on the draw, None until drawn
+    pub held: Option<Vec<bool>>,
+    pub category: HandCategory,
+    /// Paying hand the cards make, after the deal it shows what holding everything would pay
+    pub paying_hand: Option<String>,
+    pub bet: u64,
+    /// The deal takes the bet, the draw pays
+    pub gain: i64,
+}
+
+impl VideoPokerHand {
+    /// Scores `hand`, it is paid once `held` is given by the draw
+    pub fn new(
+        hand: &[StandardCard],
+        held: Option<Vec<bool>>,
+        bet: u64,
+        paytable: &Paytable,
+    ) -> Result<Self, VideoPokerError> {
+        let rank = evaluate(hand).ok_or(VideoPokerError::NoCard)?;
+        let paying_hand = paytable.paying_hand(&rank);
+        let gain = match (held.is_some(), paying_hand) {
+            (true, Some((_, pays))) => (bet * pays) as i64,
+            (true, None) => 0,
+            (false, _) => -(bet as i64),
+        };
+
+        Ok(Self {
+            hand: c![card.to_string(), for card in hand],
+            held,
+            category: rank.category,
+            paying_hand: paying_hand.map(|(name, _)| name.to_string()),
+            bet,
+            gain,
+        })
+    }
+}
+
+/// Hands are stored between the deal and the draw, so the draw comes from the same deck
+#[cfg(feature = "auto_save")]
+pub struct VideoPoker;
+
+#[cfg(feature = "auto_save")]
+impl VideoPoker {
+    fn cards(cards: &[String]) -> Result<Vec<StandardCard>, VideoPokerError> {
+        let mut parsed = Vec::with_capacity(cards.len());
+        for card in cards {
+            parsed.push(card.parse::<StandardCard>()?);
+        }
+        Ok(parsed)
+    }
+
+    /// Hand `player` was dealt and hasn't drawn yet
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        paytable: &Paytable,
+    ) -> Result<Option<VideoPokerHand>, VideoPokerError> {
+        use schema::video_poker::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = video_poker
+            .filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<VideoPokerSession>(&*conn)
+            .optional()?;
+
+        match session {
+            Some(session) => Ok(Some(VideoPokerHand::new(
+                &Self::cards(&session.hand)?,
+                None,
+                session.bet as u64,
+                paytable,
+            )?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Deals 5 cards to `player`, taking `new_bet`
+    pub fn deal(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        new_bet: u64,
+        paytable: &Paytable,
+    ) -> Result<VideoPokerHand, VideoPokerError> {
+        use schema::video_poker;
+
+        let conn = db_pool.get()?;
+        let mut new_deck = StandardDeck::new();
+        let mut dealt = Vec::with_capacity(HAND_SIZE);
+        for _ in 0..HAND_SIZE {
+            dealt.push(new_deck.draw()?);
+        }
+
+        // The key is the player, a hand already dealt keeps this one from being stored
+        let inserted = diesel::insert_into(video_poker::table)
+            .values(&VideoPokerSession {
+                namespace: player_namespace,
+                id: player as i64,
+                bet: new_bet as i64,
+                hand: c![card.to_string(), for card in &dealt],
+                deck: new_deck.export(),
+            })
+            .on_conflict_do_nothing()
+            .execute(&*conn)?;
+
+        if inserted != 1 {
+            return Err(VideoPokerError::HandInProgress);
+        }
+
+        VideoPokerHand::new(&dealt, None, new_bet, paytable)
+    }
+
+    /// Draws replacements for every card not `held` and pays the final hand
+    pub fn draw(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        held: &[bool],
+        paytable: &Paytable,
+    ) -> Result<VideoPokerHand, VideoPokerError> {
+        use schema::video_poker::dsl::*;
+
+        let conn = db_pool.get()?;
+        // Deleting and reading in one statement keeps a hand from being drawn twice
+        let session = diesel::delete(
+            video_poker
+                .filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64)),
+        ).get_result::<VideoPokerSession>(&*conn)
+            .optional()?
+            .ok_or(VideoPokerError::NoHand)?;
+
+        let mut cards = Self::cards(&session.hand)?;
+        let mut remaining = StandardDeck {
+            cards: Self::cards(&session.deck)?,
+        };
+        draw(&mut cards, held, &mut remaining)?;
+
+        let result = VideoPokerHand::new(
+            &cards,
+            Some(held.to_vec()),
+            session.bet as u64,
+            paytable,
+        )?;
+        METRICS.record_game("video_poker", result.bet, result.gain as u64);
+
+        Ok(result)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VideoPokerResponse {
+    pub status_code: u16,
+    pub status: Result<VideoPokerHand, String>,
+}
+
+impl VideoPokerResponse {
+    pub fn new(result: Result<VideoPokerHand, VideoPokerError>) -> Self {
+        match result {
+            Ok(result) => Self {
+                status_code: 200,
+                status: Ok(result),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct VideoPokerStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when no hand is waiting for a draw
+    pub status: Result<Option<VideoPokerHand>, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl VideoPokerStatusResponse {
+    pub fn new(result: Result<Option<VideoPokerHand>, VideoPokerError>) -> Self {
+        match result {
+            Ok(hand) => Self {
+                status_code: 200,
+                status: Ok(hand),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
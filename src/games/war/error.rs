@@ -0,0 +1,83 @@
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use games::{StandardCardParseError, StandardDeckError};
+
+#[derive(Debug)]
+pub enum WarError {
+    CardParse(StandardCardParseError),
+    #[cfg(feature = "auto_save")]
+    DieselResult(DieselResultError),
+    InvalidBet(String),
+    NoCard,
+    NoTie,
+    #[cfg(feature = "auto_save")]
+    R2d2(R2d2Error),
+    TieInProgress,
+}
+
+impl Display for WarError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            WarError::InvalidBet(ref why) => f.write_str(why),
+            _ => f.write_str(self.description()),
+        }
+    }
+}
+
+impl StdError for WarError {
+    fn description(&self) -> &str {
+        use self::WarError::*;
+        match *self {
+            CardParse(ref inner) => inner.description(),
+            #[cfg(feature = "auto_save")]
+            DieselResult(ref inner) => inner.description(),
+            InvalidBet(_) => "Bet is outside of the allowed limits",
+            NoCard => "No card was able to be drawn",
+            NoTie => "No tie is waiting, please deal first",
+            #[cfg(feature = "auto_save")]
+            R2d2(ref inner) => inner.description(),
+            TieInProgress => "A tie is waiting, please go to war or surrender first",
+        }
+    }
+}
+
+impl From<StandardCardParseError> for WarError {
+    fn from(err: StandardCardParseError) -> Self {
+        WarError::CardParse(err)
+    }
+}
+
+impl From<StandardDeckError> for WarError {
+    fn from(_: StandardDeckError) -> Self {
+        WarError::NoCard
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for WarError {
+    fn from(err: DieselResultError) -> Self {
+        WarError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for WarError {
+    fn from(err: R2d2Error) -> Self {
+        WarError::R2d2(err)
+    }
+}
+
+impl WarError {
+    /// Return Status Code based on the error
+    pub fn status_code(&self) -> u16 {
+        use self::WarError::*;
+        match *self {
+            InvalidBet(_) | NoTie | TieInProgress => 501,
+            _ => 500,
+        }
+    }
+}
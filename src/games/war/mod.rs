@@ -0,0 +1,8 @@
+mod error;
+mod war_game;
+
+pub use self::error::WarError;
+pub use self::war_game::{deal, go_to_war, surrender, Rules, WarBattle, WarOutcome, WarResponse,
+                         WarRound};
+#[cfg(feature = "auto_save")]
+pub use self::war_game::{War, WarStatusResponse};
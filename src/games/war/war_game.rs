@@ -0,0 +1,345 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use metrics::{payout, METRICS};
+#[cfg(feature = "auto_save")]
+use models::WarSession;
+use games::{StandardCard, StandardDeck};
+use std::cmp::Ordering;
+use super::WarError;
+#[cfg(feature = "auto_save")]
+use ConnectionPool;
+
+/// Casino War rules, the higher card wins and aces are high
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Rules {
+    /// Decks in the shoe
+    pub decks: usize,
+    /// Cards burned before the war is dealt
+    pub burn_cards: usize,
+    /// Paid on the original bet, as a multiple of it, when the war ties again
+    pub tie_bonus: u64,
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            decks: 6,
+            burn_cards: 3,
+            tie_bonus: 1,
+        }
+    }
+}
+
+impl Rules {
+    /// Returns every problem with the rules
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        if self.decks == 0 {
+            Err(vec![String::from("war.decks must be above 0")])
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum WarOutcome {
+    /// The bet pays 1 to 1
+    Win,
+    Lose,
+    /// Waiting for the player to go to war or surrender
+    Tie,
+    /// Half the bet is returned
+    Surrender,
+    /// The raise pays 1 to 1 and the bet is returned
+    WonWar,
+    /// The raise pays 1 to 1, the bet is returned with the tie bonus
+    TiedWar,
+    /// The bet and the raise are lost
+    LostWar,
+}
+
+/// Cards dealt once the player goes to war
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WarBattle {
+    pub burned: Vec<String>,
+    pub player: String,
+    pub dealer: String,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WarRound {
+    pub player: String,
+    pub dealer: String,
+    pub war: Option<WarBattle>,
+    pub outcome: WarOutcome,
+    pub bet: u64,
+    /// Matches the bet to go to war, 0 otherwise
+    pub raise: u64,
+    /// The deal takes the bet on a tie, going to war takes the raise and pays everything returned
+    pub gain: i64,
+}
+
+impl WarRound {
+    /// Round waiting on a tie
+    pub fn tied(player: &StandardCard, dealer: &StandardCard, bet: u64) -> Self {
+        Self {
+            player: player.to_string(),
+            dealer: dealer.to_string(),
+            war: None,
+            outcome: WarOutcome::Tie,
+            bet,
+            raise: 0,
+            gain: -(bet as i64),
+        }
+    }
+}
+
+/// Deals a card each from `shoe`, `bet` is settled unless they tie
+pub fn deal(shoe: &mut StandardDeck, bet: u64) -> Result<WarRound, WarError> {
+    let (player, dealer) = (shoe.draw()?, shoe.draw()?);
+    let mut round = WarRound::tied(&player, &dealer, bet);
+
+    match player.cmp_rank(&dealer) {
+        Ordering::Greater => {
+            round.outcome = WarOutcome::Win;
+            round.gain = bet as i64;
+        }
+        Ordering::Less => round.outcome = WarOutcome::Lose,
+        Ordering::Equal => (),
+    }
+    Ok(round)
+}
+
+/// Matches `bet` after the tie of `player` and `dealer`, burns and deals a card each from `shoe`
+pub fn go_to_war(
+    player: &StandardCard,
+    dealer: &StandardCard,
+    bet: u64,
+    shoe: &mut StandardDeck,
+    rules: &Rules,
+) -> Result<WarRound, WarError> {
+    let mut burned = Vec::with_capacity(rules.burn_cards);
+    for _ in 0..rules.burn_cards {
+        burned.push(shoe.draw()?.to_string());
+    }
+    let (player_war, dealer_war) = (shoe.draw()?, shoe.draw()?);
+
+    let raise = bet;
+    let (outcome, returned) = match player_war.cmp_rank(&dealer_war) {
+        Ordering::Greater => (WarOutcome::WonWar, bet + raise * 2),
+        Ordering::Equal => (WarOutcome::TiedWar, bet + raise * 2 + bet * rules.tie_bonus),
+        Ordering::Less => (WarOutcome::LostWar, 0),
+    };
+
+    let mut round = WarRound::tied(player, dealer, bet);
+    round.war = Some(WarBattle {
+        burned,
+        player: player_war.to_string(),
+        dealer: dealer_war.to_string(),
+    });
+    round.outcome = outcome;
+    round.raise = raise;
+    round.gain = returned as i64 - raise as i64;
+    Ok(round)
+}
+
+/// Gives up after the tie of `player` and `dealer`, half of `bet` is returned
+pub fn surrender(player: &StandardCard, dealer: &StandardCard, bet: u64) -> WarRound {
+    let mut round = WarRound::tied(player, dealer, bet);
+    round.outcome = WarOutcome::Surrender;
+    round.gain = (bet / 2) as i64;
+    round
+}
+
+/// Ties are stored until the player goes to war or surrenders
+#[cfg(feature = "auto_save")]
+pub struct War;
+
+#[cfg(feature = "auto_save")]
+impl War {
+    /// Removes the stored tie of `player`, so it can only be played once
+    fn take(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<WarSession, WarError> {
+        use schema::war::dsl::*;
+
+        let conn = db_pool.get()?;
+        diesel::delete(
+            war.filter(namespace.eq(player_namespace))
+                .filter(id.eq(player as i64)),
+        ).get_result::<WarSession>(&*conn)
+            .optional()?
+            .ok_or(WarError::NoTie)
+    }
+
+    /// Tie `player` hasn't gone to war on or surrendered yet
+    pub fn restore(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<Option<WarRound>, WarError> {
+        use schema::war::dsl::*;
+
+        let conn = db_pool.get()?;
+        let session = war.filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .first::<WarSession>(&*conn)
+            .optional()?;
+
+        match session {
+            Some(session) => Ok(Some(WarRound::tied(
+                &session.player_card.parse::<StandardCard>()?,
+                &session.dealer_card.parse::<StandardCard>()?,
+                session.bet as u64,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    /// Takes `new_bet` and deals, a tie is stored for the player to decide on
+    pub fn deal(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        new_bet: u64,
+        rules: &Rules,
+    ) -> Result<WarRound, WarError> {
+        use schema::war;
+        use schema::war::dsl::*;
+
+        let conn = db_pool.get()?;
+        let count: i64 = war.filter(namespace.eq(player_namespace))
+            .filter(id.eq(player as i64))
+            .count()
+            .get_result(&*conn)?;
+
+        if count != 0 {
+            return Err(WarError::TieInProgress);
+        }
+
+        let mut new_shoe = StandardDeck::shoe(rules.decks);
+        let round = deal(&mut new_shoe, new_bet)?;
+
+        if round.outcome == WarOutcome::Tie {
+            // The check above only covers rounds settled at once, a tie dealt
+            // alongside another one must not overwrite it
+            let inserted = diesel::insert_into(war::table)
+                .values(&WarSession {
+                    namespace: player_namespace,
+                    id: player as i64,
+                    bet: new_bet as i64,
+                    player_card: round.player.clone(),
+                    dealer_card: round.dealer.clone(),
+                    shoe: new_shoe.export(),
+                })
+                .on_conflict_do_nothing()
+                .execute(&*conn)?;
+
+            if inserted != 1 {
+                return Err(WarError::TieInProgress);
+            }
+        } else {
+            METRICS.record_game("war", new_bet, payout(new_bet, round.gain));
+        }
+
+        Ok(round)
+    }
+
+    /// Takes the raise and deals the war
+    pub fn war(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+        rules: &Rules,
+    ) -> Result<WarRound, WarError> {
+        let session = Self::take(db_pool, player_namespace, player)?;
+
+        let mut cards = Vec::with_capacity(session.shoe.len());
+        for card in &session.shoe {
+            cards.push(card.parse::<StandardCard>()?);
+        }
+        let round = go_to_war(
+            &session.player_card.parse::<StandardCard>()?,
+            &session.dealer_card.parse::<StandardCard>()?,
+            session.bet as u64,
+            &mut StandardDeck { cards },
+            rules,
+        )?;
+        METRICS.record_game(
+            "war",
+            round.bet + round.raise,
+            (round.gain + round.raise as i64) as u64,
+        );
+
+        Ok(round)
+    }
+
+    /// Gives up the tie for half the bet
+    pub fn surrender(
+        db_pool: &ConnectionPool,
+        player_namespace: i64,
+        player: u64,
+    ) -> Result<WarRound, WarError> {
+        let session = Self::take(db_pool, player_namespace, player)?;
+
+        let round = surrender(
+            &session.player_card.parse::<StandardCard>()?,
+            &session.dealer_card.parse::<StandardCard>()?,
+            session.bet as u64,
+        );
+        METRICS.record_game("war", round.bet, round.gain as u64);
+
+        Ok(round)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WarResponse {
+    pub status_code: u16,
+    pub status: Result<WarRound, String>,
+}
+
+impl WarResponse {
+    pub fn new(result: Result<WarRound, WarError>) -> Self {
+        match result {
+            Ok(round) => Self {
+                status_code: 200,
+                status: Ok(round),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WarStatusResponse {
+    pub status_code: u16,
+    /// Ok(None) when no tie is waiting
+    pub status: Result<Option<WarRound>, String>,
+}
+
+#[cfg(feature = "auto_save")]
+impl WarStatusResponse {
+    pub fn new(result: Result<Option<WarRound>, WarError>) -> Self {
+        match result {
+            Ok(round) => Self {
+                status_code: 200,
+                status: Ok(round),
+            },
+            Err(err) => Self {
+                status_code: err.status_code(),
+                status: Err(err.to_string()),
+            },
+        }
+    }
+}
@@ -0,0 +1,209 @@
+#[cfg(feature = "auto_save")]
+use diesel;
+#[cfg(feature = "auto_save")]
+use diesel::prelude::*;
+#[cfg(feature = "auto_save")]
+use diesel::result::Error as DieselResultError;
+#[cfg(feature = "auto_save")]
+use diesel::sql_types::{BigInt, Text};
+use games::slot_machine::Combination;
+#[cfg(feature = "auto_save")]
+use r2d2::Error as R2d2Error;
+#[cfg(feature = "auto_save")]
+use std::error::Error as StdError;
+#[cfg(feature = "auto_save")]
+use std::fmt::{Display, Formatter, Result as FmtResult};
+#[cfg(feature = "auto_save")]
+use {ConnectionPool, PgConnection};
+
+/// Row of the `jackpot` table holding the shared pool
+pub const JACKPOT_NAME: &str = "progressive";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct JackpotConfig {
+    /// Fraction of every slot wager added to the pool
+    pub contribution: f64,
+    /// Fraction of every BlackJack bet added to the pool, off by default
+    pub blackjack_contribution: f64,
+    /// The pool restarts from this amount once won
+    pub seed: u64,
+    /// Landing this on the 3 reel slot machine wins the pool
+    pub combination: Combination,
+}
+
+impl Default for JackpotConfig {
+    fn default() -> Self {
+        Self {
+            contribution: 0.01,
+            blackjack_contribution: 0.0,
+            seed: 1000,
+            combination: Combination::Symbols(vec![String::from("⭐"); 3]),
+        }
+    }
+}
+
+impl JackpotConfig {
+    /// Part of `bet` that goes to the pool
+    pub fn contribution_of(&self, bet: u64) -> u64 {
+        (bet as f64 * self.contribution) as u64
+    }
+
+    /// Part of a BlackJack `bet` that goes to the pool
+    pub fn blackjack_contribution_of(&self, bet: u64) -> u64 {
+        (bet as f64 * self.blackjack_contribution) as u64
+    }
+
+    /// Returns every problem with the config
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+
+        for &(name, fraction) in &[
+            ("contribution", self.contribution),
+            ("blackjack_contribution", self.blackjack_contribution),
+        ] {
+            if fraction < 0.0 || fraction >= 1.0 {
+                problems.push(format!("jackpot.{} must be at least 0 and below 1", name));
+            }
+        }
+        if self.seed > i64::max_value() as u64 {
+            problems.push(String::from("jackpot.seed is too large"));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(Debug)]
+pub enum JackpotError {
+    DieselResult(DieselResultError),
+    R2d2(R2d2Error),
+}
+
+#[cfg(feature = "auto_save")]
+impl Display for JackpotError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        f.write_str(self.description())
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl StdError for JackpotError {
+    fn description(&self) -> &str {
+        match *self {
+            JackpotError::DieselResult(ref inner) => inner.description(),
+            JackpotError::R2d2(ref inner) => inner.description(),
+        }
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<DieselResultError> for JackpotError {
+    fn from(err: DieselResultError) -> Self {
+        JackpotError::DieselResult(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<R2d2Error> for JackpotError {
+    fn from(err: R2d2Error) -> Self {
+        JackpotError::R2d2(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+#[derive(QueryableByName)]
+struct PoolAmount {
+    #[sql_type = "BigInt"]
+    amount: i64,
+}
+
+/// Outcome of a wager for the jackpot
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
+pub struct JackpotResult {
+    /// Pool after the wager
+    pub pool: u64,
+    /// Amount won, 0 unless the jackpot was hit
+    pub won: u64,
+}
+
+/// The pool shared by every game and every client
+/// Every update is a single statement, so concurrent wagers can't lose contributions
+#[cfg(feature = "auto_save")]
+pub struct Jackpot;
+
+#[cfg(feature = "auto_save")]
+impl Jackpot {
+    /// Current pool, `seed` if nothing was wagered yet
+    pub fn pool(db_pool: &ConnectionPool, seed: u64) -> Result<u64, JackpotError> {
+        let conn = db_pool.get()?;
+
+        Ok(Self::current(&*conn, seed)?)
+    }
+
+    pub fn current(conn: &PgConnection, seed: u64) -> QueryResult<u64> {
+        use schema::jackpot::dsl::*;
+
+        let pool = jackpot
+            .filter(name.eq(JACKPOT_NAME))
+            .select(amount)
+            .first::<i64>(conn)
+            .optional()?;
+
+        Ok(pool.map_or(seed, |pool| pool as u64))
+    }
+
+    /// Adds `contribution` to the pool, returning the new pool
+    pub fn contribute(conn: &PgConnection, contribution: u64, seed: u64) -> QueryResult<u64> {
+        let pool = diesel::sql_query(
+            "INSERT INTO jackpot (name, amount) VALUES ($1, $2 + $3) \
+             ON CONFLICT (name) DO UPDATE SET amount = jackpot.amount + $3 \
+             RETURNING amount",
+        ).bind::<Text, _>(JACKPOT_NAME)
+            .bind::<BigInt, _>(seed as i64)
+            .bind::<BigInt, _>(contribution as i64)
+            .get_result::<PoolAmount>(conn)?;
+
+        Ok(pool.amount as u64)
+    }
+
+    /// Empties the pool back to `seed`, returning what it held
+    pub fn claim(conn: &PgConnection, seed: u64) -> QueryResult<u64> {
+        let won = diesel::sql_query(
+            "UPDATE jackpot SET amount = $2 \
+             FROM (SELECT amount FROM jackpot WHERE name = $1 FOR UPDATE) AS won \
+             WHERE jackpot.name = $1 \
+             RETURNING won.amount",
+        ).bind::<Text, _>(JACKPOT_NAME)
+            .bind::<BigInt, _>(seed as i64)
+            .get_result::<PoolAmount>(conn)?;
+
+        Ok(won.amount as u64)
+    }
+
+    /// Adds `contribution` to the pool and, if `hit`, wins all of it
+    pub fn wager(
+        conn: &PgConnection,
+        contribution: u64,
+        hit: bool,
+        seed: u64,
+    ) -> QueryResult<JackpotResult> {
+        conn.transaction(|| {
+            let pool = Self::contribute(conn, contribution, seed)?;
+
+            if hit {
+                Ok(JackpotResult {
+                    won: Self::claim(conn, seed)?,
+                    pool: seed,
+                })
+            } else {
+                Ok(JackpotResult { pool, won: 0 })
+            }
+        })
+    }
+}
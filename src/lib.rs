@@ -27,21 +27,36 @@ extern crate r2d2;
 extern crate r2d2_diesel;
 extern crate rand;
 extern crate regex;
+extern crate sha2;
+extern crate toml;
 
 #[cfg(feature = "auto_save")]
 pub use diesel::prelude::*;
 #[cfg(feature = "auto_save")]
 pub use diesel::pg::PgConnection;
-#[cfg(feature = "auto_save")]
 use dotenv::dotenv;
 #[cfg(feature = "auto_save")]
 use r2d2::Pool;
 #[cfg(feature = "auto_save")]
 use r2d2_diesel::ConnectionManager;
 #[cfg(feature = "auto_save")]
+use config::{Config, ConfigError, DatabaseConfig};
+#[cfg(feature = "auto_save")]
+use diesel_migrations::RunMigrationsError;
+#[cfg(feature = "auto_save")]
 use std::env;
+#[cfg(feature = "auto_save")]
+use std::error::Error as StdError;
+#[cfg(feature = "auto_save")]
+use std::fmt::{Display, Formatter, Result as FmtResult};
 
+#[cfg(feature = "auto_save")]
+pub mod auth;
+pub mod config;
 pub mod games;
+pub mod jackpot;
+pub mod metrics;
+pub mod rate_limit;
 #[cfg(feature = "auto_save")]
 pub mod models;
 #[cfg(feature = "auto_save")]
@@ -51,37 +66,115 @@ pub mod schema;
 pub type ConnectionPool = Pool<ConnectionManager<PgConnection>>;
 #[cfg(feature = "auto_save")]
 embed_migrations!("migrations");
-cfg_if! {
-    if #[cfg(not(feature = "auto_save"))] {
-    }
-    else if #[cfg(test)] {
-        fn db_url() -> String {
-            env::var("GAMESERVICE_TEST_DATABASE_URL")
-                .expect("GAMESERVICE_DATABASE_URL must be set")
+
+/// Errors setting up the DB connection pool
+#[cfg(feature = "auto_save")]
+#[derive(Debug)]
+pub enum SetupError {
+    Config(ConfigError),
+    Migration(RunMigrationsError),
+    R2d2(r2d2::Error),
+}
+
+#[cfg(feature = "auto_save")]
+impl Display for SetupError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        use self::SetupError::*;
+        match *self {
+            Config(ref inner) => write!(f, "{}", inner),
+            Migration(ref inner) => write!(f, "Error running migrations: {}", inner),
+            R2d2(ref inner) => write!(f, "Failed to create connection pool: {}", inner),
         }
-    } else {
-        fn db_url() -> String {
-            env::var("GAMESERVICE_TEST_DATABASE_URL")
-                .expect("GAMESERVICE_DATABASE_URL must be set")
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl StdError for SetupError {
+    fn description(&self) -> &str {
+        use self::SetupError::*;
+        match *self {
+            Config(ref inner) => inner.description(),
+            Migration(ref inner) => inner.description(),
+            R2d2(ref inner) => inner.description(),
         }
     }
 }
 
+#[cfg(feature = "auto_save")]
+impl From<ConfigError> for SetupError {
+    fn from(err: ConfigError) -> Self {
+        SetupError::Config(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<RunMigrationsError> for SetupError {
+    fn from(err: RunMigrationsError) -> Self {
+        SetupError::Migration(err)
+    }
+}
+
+#[cfg(feature = "auto_save")]
+impl From<r2d2::Error> for SetupError {
+    fn from(err: r2d2::Error) -> Self {
+        SetupError::R2d2(err)
+    }
+}
+
+/// Loads `.env` into the environment if there is one
+/// A missing `.env` is fine, the variables may come from the environment
+pub fn load_dotenv() {
+    dotenv().ok();
+}
+
+/// Loads the config, creates the connection pool and runs migrations, panicking on failure
+/// Use `try_establish_connection_pool` to handle errors
 #[cfg(feature = "auto_save")]
 pub fn establish_connection_pool() -> ConnectionPool {
-    dotenv().expect("Error loading env variables");
+    match try_establish_connection_pool() {
+        Ok(pool) => pool,
+        Err(why) => panic!("{}", why),
+    }
+}
+
+/// Same as `establish_connection_pool`, but uses `GAMESERVICE_TEST_DATABASE_URL`
+#[cfg(feature = "auto_save")]
+pub fn establish_test_connection_pool() -> ConnectionPool {
+    load_dotenv();
+
+    let mut database = Config::load().map(|config| config.database).unwrap_or_default();
+    database.url = env::var("GAMESERVICE_TEST_DATABASE_URL")
+        .expect("GAMESERVICE_TEST_DATABASE_URL must be set");
 
-    let manager = ConnectionManager::<PgConnection>::new(db_url());
+    match connect(&database) {
+        Ok(pool) => pool,
+        Err(why) => panic!("{}", why),
+    }
+}
 
-    let pool = match env::var("MAX_POOL") {
-        Ok(size) => r2d2::Pool::builder()
-            .max_size(size.parse::<u32>().expect("MAX_POOL is not a u32"))
-            .build(manager),
-        Err(_) => r2d2::Pool::new(manager),
-    }.expect("Failed to create connection pool");
+#[cfg(feature = "auto_save")]
+pub fn try_establish_connection_pool() -> Result<ConnectionPool, SetupError> {
+    load_dotenv();
+
+    connect(&Config::load()?.database)
+}
+
+#[cfg(feature = "auto_save")]
+fn pool_builder(database: &DatabaseConfig) -> r2d2::Builder<ConnectionManager<PgConnection>> {
+    match database.pool_size {
+        Some(size) => r2d2::Pool::builder().max_size(size),
+        None => r2d2::Pool::builder(),
+    }
+}
+
+/// Creates the connection pool for `database` and runs migrations
+#[cfg(feature = "auto_save")]
+pub fn connect(database: &DatabaseConfig) -> Result<ConnectionPool, SetupError> {
+    let manager = ConnectionManager::<PgConnection>::new(database.url.clone());
+    let pool = pool_builder(database).build(manager)?;
 
     // Run migrations
-    run_migrations(pool.clone());
+    try_run_migrations(&pool)?;
     #[cfg(test)]
     {
         use schema::blackjack::dsl::*;
@@ -91,19 +184,36 @@ pub fn establish_connection_pool() -> ConnectionPool {
             .expect("Error deleting Previous BlackJack Test data");
     }
 
-    pool
+    Ok(pool)
+}
+
+/// Creates the connection pool for `database` without waiting for the DB to be reachable
+/// Connections are made as they are needed, migrations are left to the caller
+#[cfg(feature = "auto_save")]
+pub fn connect_lazily(database: &DatabaseConfig) -> ConnectionPool {
+    let manager = ConnectionManager::<PgConnection>::new(database.url.clone());
+    pool_builder(database).build_unchecked(manager)
 }
 
 /// Run Migrations on a connection
 /// There may be colissions between your current DB, run with caution
 #[cfg(feature = "auto_save")]
 pub fn run_migrations(pool: ConnectionPool) {
-    match pool.get() {
-        Ok(conn) => {
-            embedded_migrations::run(&*conn).expect("Error running migrations");
-        }
-        Err(why) => {
-            error!("Error obtaining conn to run migrations: {:?}", why);
-        }
+    if let Err(why) = try_run_migrations(&pool) {
+        error!("{}", why);
     }
 }
+
+#[cfg(feature = "auto_save")]
+pub fn try_run_migrations(pool: &ConnectionPool) -> Result<(), SetupError> {
+    let conn = pool.get()?;
+    embedded_migrations::run(&*conn)?;
+
+    Ok(())
+}
+
+/// Whether the DB behind `conn` is missing any migration, used for readiness checks
+#[cfg(feature = "auto_save")]
+pub fn pending_migrations(conn: &PgConnection) -> Result<bool, RunMigrationsError> {
+    diesel_migrations::any_pending_migrations(conn)
+}
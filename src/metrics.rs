@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Upper bounds (seconds) of the request latency histogram buckets
+const LATENCY_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0
+];
+
+lazy_static! {
+    /// Process wide metrics, rendered by `GET /metrics`
+    pub static ref METRICS: Metrics = Metrics::new();
+}
+
+#[derive(Clone, Debug, Default)]
+struct Histogram {
+    // Cumulative counts, one per LATENCY_BUCKETS entry
+    buckets: Vec<u64>,
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if self.buckets.is_empty() {
+            self.buckets = vec![0; LATENCY_BUCKETS.len()];
+        }
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS.iter()) {
+            if value <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += value;
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct GameStats {
+    played: u64,
+    wagered: u64,
+    paid_out: u64,
+}
+
+#[derive(Default)]
+struct Inner {
+    // (method, route, status)
+    requests: BTreeMap<(String, String, u16), u64>,
+    // (method, route)
+    latencies: BTreeMap<(String, String), Histogram>,
+    games: BTreeMap<String, GameStats>,
+}
+
+/// A value only known when scraping (DB pool state, active sessions)
+pub struct Gauge<'a> {
+    pub name: &'a str,
+    pub help: &'a str,
+    pub value: f64,
+}
+
+pub struct Metrics {
+    started: Instant,
+    inner: Mutex<Inner>,
+}
+
+/// What a player got back from a game, the bet included
+pub fn payout(bet: u64, gain: i64) -> u64 {
+    let total = bet as i64 + gain;
+    if total > 0 {
+        total as u64
+    } else {
+        0
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn as_secs(duration: Duration) -> f64 {
+    duration.as_secs() as f64 + f64::from(duration.subsec_nanos()) / 1e9
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// Nanoseconds since the metrics were created, used to time requests
+    pub fn clock(&self) -> u64 {
+        let elapsed = self.started.elapsed();
+        elapsed.as_secs() * 1_000_000_000 + u64::from(elapsed.subsec_nanos())
+    }
+
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        *inner
+            .requests
+            .entry((method.to_owned(), route.to_owned(), status))
+            .or_insert(0) += 1;
+        inner
+            .latencies
+            .entry((method.to_owned(), route.to_owned()))
+            .or_insert_with(Histogram::default)
+            .observe(as_secs(elapsed));
+    }
+
+    /// Records a finished game, `paid_out` includes the returned bet
+    pub fn record_game(&self, game: &str, wagered: u64, paid_out: u64) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let stats = inner
+            .games
+            .entry(game.to_owned())
+            .or_insert_with(GameStats::default);
+
+        stats.played += 1;
+        stats.wagered += wagered;
+        stats.paid_out += paid_out;
+    }
+
+    /// Renders every metric in the Prometheus text format
+    pub fn render(&self, gauges: &[Gauge]) -> String {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let mut out = String::new();
+
+        // Writing to a String can't fail
+        let _ = writeln!(out, "# HELP game_service_http_requests_total HTTP requests handled");
+        let _ = writeln!(out, "# TYPE game_service_http_requests_total counter");
+        for (&(ref method, ref route, status), count) in &inner.requests {
+            let _ = writeln!(
+                out,
+                "game_service_http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                method,
+                escape_label(route),
+                status,
+                count
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP game_service_http_request_duration_seconds HTTP request latency"
+        );
+        let _ = writeln!(out, "# TYPE game_service_http_request_duration_seconds histogram");
+        for (&(ref method, ref route), histogram) in &inner.latencies {
+            let labels = format!("method=\"{}\",route=\"{}\"", method, escape_label(route));
+            for (bucket, bound) in histogram.buckets.iter().zip(LATENCY_BUCKETS.iter()) {
+                let _ = writeln!(
+                    out,
+                    "game_service_http_request_duration_seconds_bucket{{{},le=\"{}\"}} {}",
+                    labels, bound, bucket
+                );
+            }
+            let _ = writeln!(
+                out,
+                "game_service_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} {}",
+                labels, histogram.count
+            );
+            let _ = writeln!(
+                out,
+                "game_service_http_request_duration_seconds_sum{{{}}} {}",
+                labels, histogram.sum
+            );
+            let _ = writeln!(
+                out,
+                "game_service_http_request_duration_seconds_count{{{}}} {}",
+                labels, histogram.count
+            );
+        }
+
+        let game_metrics: [(&str, &str, fn(&GameStats) -> u64); 3] = [
+            ("game_service_games_played_total", "Games played", |s| s.played),
+            ("game_service_wagered_total", "Total amount wagered", |s| s.wagered),
+            (
+                "game_service_paid_out_total",
+                "Total amount paid back to players, bets included",
+                |s| s.paid_out,
+            ),
+        ];
+        for &(name, help, value) in &game_metrics {
+            let _ = writeln!(out, "# HELP {} {}", name, help);
+            let _ = writeln!(out, "# TYPE {} counter", name);
+            for (game, stats) in &inner.games {
+                let _ = writeln!(
+                    out,
+                    "{}{{game=\"{}\"}} {}",
+                    name,
+                    escape_label(game),
+                    value(stats)
+                );
+            }
+        }
+
+        for gauge in gauges {
+            let _ = writeln!(out, "# HELP {} {}", gauge.name, gauge.help);
+            let _ = writeln!(out, "# TYPE {} gauge", gauge.name);
+            let _ = writeln!(out, "{} {}", gauge.name, gauge.value);
+        }
+
+        out
+    }
+}
@@ -1,7 +1,10 @@
-use schema::blackjack;
+use schema::{api_keys, blackjack, coin_streaks, crash_bets, crash_rounds, craps, hi_lo, holdem,
+             lottery_draws, lottery_tickets, mines, roulette_spins, rps_challenges, rps_matches,
+             rps_models, slot_free_spins, video_poker, war};
 
 #[derive(Insertable, Queryable, Identifiable, AsChangeset)]
 #[table_name = "blackjack"]
+#[primary_key(namespace, id)]
 pub struct BJSession {
     pub id: i64,
     // None means it was claimed
@@ -22,4 +25,294 @@ pub struct BJSession {
     pub dealer_stay: bool,
     // True by default
     pub first_turn: bool,
+    // Id of the API key that owns this session
+    pub namespace: i64,
+}
+
+#[derive(Clone, Debug, Queryable, Identifiable)]
+#[table_name = "api_keys"]
+pub struct ApiKey {
+    // Also used as the namespace for the client's users
+    pub id: i64,
+    pub name: String,
+    pub key_hash: String,
+    pub revoked: bool,
+}
+
+#[derive(Insertable)]
+#[table_name = "api_keys"]
+pub struct NewApiKey<'a> {
+    pub name: &'a str,
+    pub key_hash: &'a str,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "slot_free_spins"]
+#[primary_key(namespace, id)]
+pub struct FreeSpinsSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    // Rows are removed once this reaches 0
+    pub remaining: i32,
+    pub line_bet: i64,
+    pub lines: i32,
+    pub multiplier: f64,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "coin_streaks"]
+#[primary_key(namespace, id)]
+pub struct CoinStreakSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    // Bet that started the streak
+    pub bet: i64,
+    // Paid on cash out
+    pub pot: i64,
+    pub wins: i32,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "rps_matches"]
+#[primary_key(namespace, id)]
+pub struct RpsMatchSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    pub bet: i64,
+    pub best_of: i32,
+    pub player_score: i32,
+    pub computer_score: i32,
+    // Throws of every round in order, draws included
+    pub player_throws: Vec<String>,
+    pub computer_throws: Vec<String>,
+}
+
+#[derive(Clone, Debug, Queryable, Identifiable)]
+#[table_name = "rps_challenges"]
+pub struct RpsChallengeSession {
+    pub id: i64,
+    // Id of the API key that owns both users
+    pub namespace: i64,
+    pub challenger: i64,
+    pub opponent: i64,
+    pub stake: i64,
+    pub variant: String,
+    // sha256 of the challenger's throw and salt
+    pub commitment: String,
+    pub opponent_throw: Option<String>,
+    // Set once revealed
+    pub challenger_throw: Option<String>,
+    // Set once revealed or timed out
+    pub settled: bool,
+    // Unix time of the accept deadline, then of the reveal deadline
+    pub expires_at: i64,
+}
+
+#[derive(Insertable)]
+#[table_name = "rps_challenges"]
+pub struct NewRpsChallenge<'a> {
+    pub namespace: i64,
+    pub challenger: i64,
+    pub opponent: i64,
+    pub stake: i64,
+    pub variant: &'a str,
+    pub commitment: &'a str,
+    pub expires_at: i64,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "rps_models"]
+#[primary_key(namespace, id, variant)]
+pub struct RpsModelSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    pub variant: String,
+    // Indexed by the weapons of the variant, see `games::rps::ThrowModel`
+    pub counts: Vec<i32>,
+    pub transitions: Vec<i32>,
+    pub last_throw: Option<i32>,
+    pub throws: i32,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "video_poker"]
+#[primary_key(namespace, id)]
+pub struct VideoPokerSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    pub bet: i64,
+    // The 5 dealt cards, removed on the draw
+    pub hand: Vec<String>,
+    // What is left of the deck the hand was dealt from
+    pub deck: Vec<String>,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "holdem"]
+#[primary_key(namespace, id)]
+pub struct HoldemSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    pub ante: i64,
+    // Hole cards of the player
+    pub player_cards: Vec<String>,
+    // Hole cards of the dealer
+    pub dealer_cards: Vec<String>,
+    // The flop, the turn and river are dealt on the call
+    pub board: Vec<String>,
+    // What is left of the deck
+    pub deck: Vec<String>,
+}
+
+#[derive(Clone, Debug, Queryable, Identifiable)]
+#[table_name = "roulette_spins"]
+pub struct RouletteSpinRecord {
+    pub id: i64,
+    // Id of the API key the table was spun for
+    pub namespace: i64,
+    pub table_name: String,
+    // 0 to 36, 37 for 00
+    pub pocket: i32,
+}
+
+#[derive(Insertable)]
+#[table_name = "roulette_spins"]
+pub struct NewRouletteSpin<'a> {
+    pub namespace: i64,
+    pub table_name: &'a str,
+    pub pocket: i32,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "craps"]
+#[primary_key(namespace, id)]
+pub struct CrapsTableSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    // None on the come-out roll
+    pub point: Option<i32>,
+    // KIND:NUMBER:AMOUNT:ODDS, see `games::craps::CrapsBet`
+    pub bets: Vec<String>,
+    // Bumped on every change, so concurrent requests can't both apply
+    pub version: i32,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "war"]
+#[primary_key(namespace, id)]
+pub struct WarSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    pub bet: i64,
+    // The tied cards
+    pub player_card: String,
+    pub dealer_card: String,
+    // What is left of the shoe
+    pub shoe: Vec<String>,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "hi_lo"]
+#[primary_key(namespace, id)]
+pub struct HiLoSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    // Bet that started the streak
+    pub bet: i64,
+    // Cashing out pays the bet times this
+    pub multiplier: f64,
+    // The next guess is on this card
+    pub card: String,
+    // What is left of the deck
+    pub deck: Vec<String>,
+    // Bumped on every guess, so concurrent guesses can't both apply
+    pub guesses: i32,
+}
+
+#[derive(Clone, Debug, Queryable, QueryableByName, Identifiable)]
+#[table_name = "lottery_tickets"]
+pub struct LotteryTicket {
+    pub id: i64,
+    // Draw the ticket was bought for
+    pub draw: i64,
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub user_id: i64,
+    // Sorted
+    pub numbers: Vec<i32>,
+    pub price: i64,
+    // Set once drawn, 0 for a losing ticket
+    pub won: Option<i64>,
+    // Set once the winnings are paid
+    pub claimed: bool,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable)]
+#[table_name = "lottery_draws"]
+pub struct LotteryDraw {
+    // Draw number, the draw closed at this times the interval
+    pub id: i64,
+    // Winning numbers, sorted
+    pub numbers: Vec<i32>,
+    pub tickets: i32,
+    pub pool: i64,
+    pub winners: i32,
+    // Paid to each winner
+    pub payout: i64,
+    // Carried into the next draw
+    pub rollover: i64,
+    // Unix time of the draw
+    pub drawn_at: i64,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable)]
+#[table_name = "crash_rounds"]
+pub struct CrashRoundRecord {
+    pub id: i64,
+    // Hex encoded, kept secret until the round crashed
+    pub seed: String,
+    // sha256 of the seed, shown from the start
+    pub commitment: String,
+    pub crash_point: f64,
+    // Unix time in milliseconds
+    pub starts_at: i64,
+    pub crashes_at: i64,
+    // Set once the bets were recorded in the metrics
+    pub settled: bool,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable)]
+#[table_name = "crash_bets"]
+#[primary_key(round_id, namespace, user_id)]
+pub struct CrashBetRecord {
+    pub round_id: i64,
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub user_id: i64,
+    pub bet: i64,
+    // Set once cashed out
+    pub cashed_out: Option<f64>,
+}
+
+#[derive(Clone, Debug, Insertable, Queryable, Identifiable, AsChangeset)]
+#[table_name = "mines"]
+#[primary_key(namespace, id)]
+pub struct MinesSession {
+    // Id of the API key that owns this user
+    pub namespace: i64,
+    pub id: i64,
+    pub bet: i64,
+    // Tiles hiding a mine, 0 to 24 row by row
+    pub mine_tiles: Vec<i32>,
+    // Safe tiles revealed so far, changes on every reveal so concurrent ones can't both apply
+    pub revealed: Vec<i32>,
 }
@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Buckets are pruned once the map grows past this many entries
+const MAX_BUCKETS: usize = 10_000;
+
+/// Route groups that are limited by default, named after their mount point
+pub const ROUTE_GROUPS: [&str; 17] = [
+    "blackjack",
+    "slot_machine",
+    "coin_toss",
+    "rps",
+    "video_poker",
+    "holdem",
+    "baccarat",
+    "roulette",
+    "craps",
+    "dice",
+    "war",
+    "hi_lo",
+    "keno",
+    "lottery",
+    "crash",
+    "mines",
+    "plinko",
+];
+
+/// Groups whose routes take a user id right after the group, with the segment that follows it,
+/// `""` for routes ending at the user id. Other segments in that place are bets, coins or weapons
+pub const USER_ROUTES: [(&str, &[&str]); 12] = [
+    ("blackjack", &["", "create", "hit", "stay", "claim"]),
+    ("slot_machine", &["grid", "free_spin", "free_spins"]),
+    ("coin_toss", &["streak"]),
+    ("rps", &["vs", "match", "challenge", "accept", "reveal", "timeout"]),
+    ("video_poker", &["", "deal", "draw"]),
+    ("holdem", &["", "ante", "call", "fold"]),
+    ("craps", &["", "bet", "place", "odds", "roll"]),
+    ("war", &["", "deal", "war", "surrender"]),
+    ("hi_lo", &["", "start", "guess", "cash_out"]),
+    ("lottery", &["tickets", "ticket", "quick_pick", "claim"]),
+    ("crash", &["", "bet", "cash_out"]),
+    ("mines", &["", "start", "reveal", "cash_out"]),
+];
+
+/// User id of a request path split into segments, if its route takes one
+pub fn route_user(segments: &[&str]) -> Option<u64> {
+    let (group, user) = match (segments.get(0), segments.get(1)) {
+        (Some(group), Some(user)) => (group, user),
+        _ => return None,
+    };
+    let next = segments.get(2).cloned().unwrap_or("");
+
+    match USER_ROUTES.iter().find(|&&(name, _)| name == *group) {
+        Some(&(_, routes)) if routes.contains(&next) => user.parse::<u64>().ok(),
+        _ => None,
+    }
+}
+
+/// `requests` may be made every `seconds`, refilled continuously
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Quota {
+    pub requests: u32,
+    pub seconds: u32,
+}
+
+#[derive(Clone, Debug)]
+pub enum QuotaParseError {
+    InvalidFormat(String),
+    Zero(String),
+}
+
+impl Display for QuotaParseError {
+    fn fmt(&self, f: &mut Formatter) -> FmtResult {
+        match *self {
+            QuotaParseError::InvalidFormat(ref quota) | QuotaParseError::Zero(ref quota) => {
+                write!(f, "{}: {:?}", self.description(), quota)
+            }
+        }
+    }
+}
+
+impl StdError for QuotaParseError {
+    fn description(&self) -> &str {
+        match *self {
+            QuotaParseError::InvalidFormat(_) => "Quota must look like <requests>/<seconds>",
+            QuotaParseError::Zero(_) => "Quota requests and seconds must be above 0",
+        }
+    }
+}
+
+impl FromStr for Quota {
+    type Err = QuotaParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(2, '/');
+        let parsed = match (parts.next(), parts.next()) {
+            (Some(requests), Some(seconds)) => {
+                (requests.trim().parse::<u32>(), seconds.trim().parse::<u32>())
+            }
+            _ => return Err(QuotaParseError::InvalidFormat(s.to_owned())),
+        };
+
+        match parsed {
+            (Ok(0), Ok(_)) | (Ok(_), Ok(0)) => Err(QuotaParseError::Zero(s.to_owned())),
+            (Ok(requests), Ok(seconds)) => Ok(Quota { requests, seconds }),
+            _ => Err(QuotaParseError::InvalidFormat(s.to_owned())),
+        }
+    }
+}
+
+impl Quota {
+    pub fn new(requests: u32, seconds: u32) -> Self {
+        Self { requests, seconds }
+    }
+
+    /// Tokens regained per second
+    fn refill_rate(&self) -> f64 {
+        f64::from(self.requests) / f64::from(self.seconds)
+    }
+}
+
+/// Limits of a single route group
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default)]
+pub struct GroupLimits {
+    /// Shared by every request made with the same API key
+    pub per_key: Quota,
+    /// Shared by every request for the same user of an API key
+    pub per_user: Quota,
+}
+
+impl Default for GroupLimits {
+    fn default() -> Self {
+        Self {
+            per_key: Quota::new(600, 60),
+            per_user: Quota::new(60, 60),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// A full bucket
+    pub fn new(quota: &Quota, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(quota.requests),
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, quota: &Quota, now: Instant) {
+        if now > self.last_refill {
+            let elapsed = now - self.last_refill;
+            let elapsed = elapsed.as_secs() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9;
+            self.tokens =
+                (self.tokens + elapsed * quota.refill_rate()).min(f64::from(quota.requests));
+            self.last_refill = now;
+        }
+    }
+
+    /// How long until a token is available, None if one is available now
+    pub fn wait_time(&mut self, quota: &Quota, now: Instant) -> Option<Duration> {
+        self.refill(quota, now);
+
+        if self.tokens >= 1.0 {
+            None
+        } else {
+            let secs = (1.0 - self.tokens) / quota.refill_rate();
+            Some(Duration::new(secs as u64, (secs.fract() * 1e9) as u32))
+        }
+    }
+
+    /// Takes a token, returning how long to wait if there isn't one
+    pub fn take(&mut self, quota: &Quota, now: Instant) -> Result<(), Duration> {
+        match self.wait_time(quota, now) {
+            Some(wait) => Err(wait),
+            None => {
+                self.tokens -= 1.0;
+                Ok(())
+            }
+        }
+    }
+
+    fn is_full(&mut self, quota: &Quota, now: Instant) -> bool {
+        self.refill(quota, now);
+        self.tokens >= f64::from(quota.requests)
+    }
+}
+
+/// Route group, client and optionally user a bucket belongs to
+type BucketKey = (String, String, Option<u64>);
+
+/// Token bucket rate limiter keyed by route group, API key and user id
+pub struct RateLimiter {
+    groups: HashMap<String, GroupLimits>,
+    buckets: Mutex<HashMap<BucketKey, TokenBucket>>,
+}
+
+impl RateLimiter {
+    /// Routes outside of `groups` are not limited
+    pub fn new(groups: HashMap<String, GroupLimits>) -> Self {
+        Self {
+            groups,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a token for `client` (and `user` if given) in `group`
+    /// Returns the seconds to wait before retrying when limited
+    pub fn check(&self, group: &str, client: &str, user: Option<u64>) -> Result<(), u64> {
+        self.check_at(group, client, user, Instant::now())
+    }
+
+    pub fn check_at(
+        &self,
+        group: &str,
+        client: &str,
+        user: Option<u64>,
+        now: Instant,
+    ) -> Result<(), u64> {
+        let limits = match self.groups.get(group) {
+            Some(limits) => limits,
+            None => return Ok(()),
+        };
+
+        let mut checks = vec![((group.to_owned(), client.to_owned(), None), limits.per_key)];
+        if user.is_some() {
+            checks.push(((group.to_owned(), client.to_owned(), user), limits.per_user));
+        }
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+
+        if buckets.len() > MAX_BUCKETS {
+            self.prune(&mut buckets, now);
+        }
+
+        // Only take tokens once every bucket has one, so a limited user doesn't drain its key
+        let mut wait = None;
+        for &(ref key, ref quota) in &checks {
+            let bucket = buckets
+                .entry(key.clone())
+                .or_insert_with(|| TokenBucket::new(quota, now));
+            if let Some(time) = bucket.wait_time(quota, now) {
+                wait = Some(wait.map_or(time, |longest| ::std::cmp::max(longest, time)));
+            }
+        }
+
+        if let Some(wait) = wait {
+            // Round up, Retry-After is in whole seconds
+            return Err(wait.as_secs() + if wait.subsec_nanos() > 0 { 1 } else { 0 });
+        }
+
+        for (key, quota) in checks {
+            if let Some(bucket) = buckets.get_mut(&key) {
+                let _ = bucket.take(&quota, now);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Drops buckets that are full, they behave the same as a new bucket
+    fn prune(&self, buckets: &mut HashMap<BucketKey, TokenBucket>, now: Instant) {
+        let groups = &self.groups;
+        buckets.retain(|&(ref group, _, user), bucket| {
+            match groups.get(group) {
+                Some(limits) => {
+                    let quota = if user.is_some() {
+                        &limits.per_user
+                    } else {
+                        &limits.per_key
+                    };
+                    !bucket.is_full(quota, now)
+                }
+                None => false,
+            }
+        });
+    }
+}
@@ -1,5 +1,14 @@
 table! {
-    blackjack (id) {
+    api_keys (id) {
+        id -> Int8,
+        name -> Text,
+        key_hash -> Text,
+        revoked -> Bool,
+    }
+}
+
+table! {
+    blackjack (namespace, id) {
         id -> Int8,
         bet -> Nullable<Int8>,
         status -> Nullable<Bool>,
@@ -9,5 +18,197 @@ table! {
         player_stay -> Bool,
         dealer_stay -> Bool,
         first_turn -> Bool,
+        namespace -> Int8,
+    }
+}
+
+table! {
+    slot_free_spins (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        remaining -> Int4,
+        line_bet -> Int8,
+        lines -> Int4,
+        multiplier -> Float8,
+    }
+}
+
+table! {
+    jackpot (name) {
+        name -> Text,
+        amount -> Int8,
+    }
+}
+
+table! {
+    coin_streaks (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        bet -> Int8,
+        pot -> Int8,
+        wins -> Int4,
+    }
+}
+
+table! {
+    rps_matches (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        bet -> Int8,
+        best_of -> Int4,
+        player_score -> Int4,
+        computer_score -> Int4,
+        player_throws -> Array<Text>,
+        computer_throws -> Array<Text>,
+    }
+}
+
+table! {
+    rps_challenges (id) {
+        id -> Int8,
+        namespace -> Int8,
+        challenger -> Int8,
+        opponent -> Int8,
+        stake -> Int8,
+        variant -> Text,
+        commitment -> Text,
+        opponent_throw -> Nullable<Text>,
+        challenger_throw -> Nullable<Text>,
+        settled -> Bool,
+        expires_at -> Int8,
+    }
+}
+
+table! {
+    rps_models (namespace, id, variant) {
+        namespace -> Int8,
+        id -> Int8,
+        variant -> Text,
+        counts -> Array<Int4>,
+        transitions -> Array<Int4>,
+        last_throw -> Nullable<Int4>,
+        throws -> Int4,
+    }
+}
+
+table! {
+    video_poker (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        bet -> Int8,
+        hand -> Array<Text>,
+        deck -> Array<Text>,
+    }
+}
+
+table! {
+    holdem (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        ante -> Int8,
+        player_cards -> Array<Text>,
+        dealer_cards -> Array<Text>,
+        board -> Array<Text>,
+        deck -> Array<Text>,
+    }
+}
+
+table! {
+    roulette_spins (id) {
+        id -> Int8,
+        namespace -> Int8,
+        table_name -> Text,
+        pocket -> Int4,
+    }
+}
+
+table! {
+    craps (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        point -> Nullable<Int4>,
+        bets -> Array<Text>,
+        version -> Int4,
+    }
+}
+
+table! {
+    war (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        bet -> Int8,
+        player_card -> Text,
+        dealer_card -> Text,
+        shoe -> Array<Text>,
+    }
+}
+
+table! {
+    hi_lo (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        bet -> Int8,
+        multiplier -> Float8,
+        card -> Text,
+        deck -> Array<Text>,
+        guesses -> Int4,
+    }
+}
+
+table! {
+    lottery_tickets (id) {
+        id -> Int8,
+        draw -> Int8,
+        namespace -> Int8,
+        user_id -> Int8,
+        numbers -> Array<Int4>,
+        price -> Int8,
+        won -> Nullable<Int8>,
+        claimed -> Bool,
+    }
+}
+
+table! {
+    lottery_draws (id) {
+        id -> Int8,
+        numbers -> Array<Int4>,
+        tickets -> Int4,
+        pool -> Int8,
+        winners -> Int4,
+        payout -> Int8,
+        rollover -> Int8,
+        drawn_at -> Int8,
+    }
+}
+
+table! {
+    crash_rounds (id) {
+        id -> Int8,
+        seed -> Text,
+        commitment -> Text,
+        crash_point -> Float8,
+        starts_at -> Int8,
+        crashes_at -> Int8,
+        settled -> Bool,
+    }
+}
+
+table! {
+    crash_bets (round_id, namespace, user_id) {
+        round_id -> Int8,
+        namespace -> Int8,
+        user_id -> Int8,
+        bet -> Int8,
+        cashed_out -> Nullable<Float8>,
+    }
+}
+
+table! {
+    mines (namespace, id) {
+        namespace -> Int8,
+        id -> Int8,
+        bet -> Int8,
+        mine_tiles -> Array<Int4>,
+        revealed -> Array<Int4>,
     }
 }
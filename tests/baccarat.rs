@@ -0,0 +1,151 @@
+extern crate games_microservice;
+use games_microservice::games::{StandardCard, StandardDeck};
+use games_microservice::games::baccarat::{deal, play, value_of, BaccaratBets, Rules, Winner};
+
+/// Shoe dealing `cards` in order
+fn shoe(cards: &[&str]) -> StandardDeck {
+    StandardDeck {
+        cards: cards.iter().rev().map(|card| card.parse().unwrap()).collect(),
+    }
+}
+
+#[test]
+fn test_values() {
+    let value = |card: &str| value_of(&card.parse::<StandardCard>().unwrap());
+    assert_eq!(value("HEARTS:ACE"), 1);
+    assert_eq!(value("HEARTS:NINE"), 9);
+    assert_eq!(value("HEARTS:TEN"), 0);
+    assert_eq!(value("HEARTS:KING"), 0);
+}
+
+#[test]
+fn test_shoe() {
+    assert_eq!(StandardDeck::shoe(8).cards.len(), 8 * 52);
+}
+
+#[test]
+fn test_natural_stands() {
+    // Player 4 + 5, banker King + 2
+    let coup = deal(&mut shoe(&["HEARTS:FOUR", "CLUBS:KING", "SPADES:FIVE", "CLUBS:TWO"])).unwrap();
+    assert_eq!(coup.player.total, 9);
+    assert_eq!(coup.player.rule, "Player has a natural 9");
+    assert_eq!(coup.banker.cards.len(), 2);
+    assert_eq!(coup.banker.rule, "Banker stands, a natural was dealt");
+    assert_eq!(coup.winner, Winner::Player);
+}
+
+#[test]
+fn test_banker_stands_on_three_against_an_eight() {
+    // Player 2 + 3 draws an 8, banker 2 + Ace
+    let coup = deal(&mut shoe(&[
+        "HEARTS:TWO",
+        "CLUBS:TWO",
+        "SPADES:THREE",
+        "CLUBS:ACE",
+        "DIAMONDS:EIGHT",
+    ])).unwrap();
+    assert_eq!(coup.player.rule, "Player draws on 0-5");
+    assert_eq!(coup.player.total, 3);
+    assert_eq!(coup.banker.cards.len(), 2);
+    assert_eq!(coup.winner, Winner::Tie);
+}
+
+#[test]
+fn test_banker_draws_on_six_against_a_six() {
+    // Player Ace + 4 draws a 6, banker 3 + 3 draws a 2
+    let coup = deal(&mut shoe(&[
+        "HEARTS:ACE",
+        "CLUBS:THREE",
+        "SPADES:FOUR",
+        "DIAMONDS:THREE",
+        "DIAMONDS:SIX",
+        "HEARTS:TWO",
+    ])).unwrap();
+    assert_eq!(coup.player.total, 1);
+    assert_eq!(
+        coup.banker.rule,
+        "Banker draws on 6, the player's third card is 6-7"
+    );
+    assert_eq!(coup.banker.total, 8);
+    assert_eq!(coup.winner, Winner::Banker);
+}
+
+#[test]
+fn test_banker_draws_when_the_player_stands() {
+    // Player 3 + 4 stands, banker 2 + 3 draws a King
+    let coup = deal(&mut shoe(&[
+        "HEARTS:THREE",
+        "CLUBS:TWO",
+        "SPADES:FOUR",
+        "DIAMONDS:THREE",
+        "DIAMONDS:KING",
+    ])).unwrap();
+    assert_eq!(coup.player.rule, "Player stands on 6-7");
+    assert_eq!(coup.banker.cards.len(), 3);
+    assert_eq!(coup.winner, Winner::Player);
+}
+
+#[test]
+fn test_gains() {
+    let rules = Rules::default();
+
+    // Banker wins with 8 over 1, commission comes off the banker bet
+    let cards = [
+        "HEARTS:ACE",
+        "CLUBS:THREE",
+        "SPADES:FOUR",
+        "DIAMONDS:THREE",
+        "DIAMONDS:SIX",
+        "HEARTS:TWO",
+    ];
+    let bets = BaccaratBets {
+        banker: 100,
+        banker_pair: 10,
+        ..Default::default()
+    };
+    let game = play(bets, &mut shoe(&cards), &rules).unwrap();
+    assert_eq!(game.bet, 110);
+    assert!(game.coup.banker.pair);
+    assert_eq!(game.gain, 95 + 110);
+
+    // A tie pushes player and banker bets
+    let cards = [
+        "HEARTS:TWO",
+        "CLUBS:TWO",
+        "SPADES:THREE",
+        "CLUBS:ACE",
+        "DIAMONDS:EIGHT",
+    ];
+    let bets = BaccaratBets {
+        player: 100,
+        tie: 10,
+        player_pair: 10,
+        ..Default::default()
+    };
+    let game = play(bets, &mut shoe(&cards), &rules).unwrap();
+    assert_eq!(game.gain, 80 - 10);
+
+    assert!(play(BaccaratBets::default(), &mut shoe(&cards), &rules).is_err());
+
+    // Bets and payouts that don't fit are refused rather than wrapped
+    let bets = BaccaratBets {
+        player: u64::max_value(),
+        tie: 1,
+        ..Default::default()
+    };
+    assert!(bets.total().is_err());
+    assert!(play(bets, &mut shoe(&cards), &rules).is_err());
+    let bets = BaccaratBets {
+        tie: u64::max_value() / 2,
+        ..Default::default()
+    };
+    assert!(play(bets, &mut shoe(&cards), &rules).is_err());
+}
+
+#[test]
+fn test_single_bets() {
+    assert_eq!(BaccaratBets::single("banker", 10).unwrap().banker, 10);
+    assert_eq!(BaccaratBets::single("Punto", 10).unwrap().player, 10);
+    assert_eq!(BaccaratBets::single("tie", 10).unwrap().total().unwrap(), 10);
+    assert!(BaccaratBets::single("dealer", 10).is_err());
+}
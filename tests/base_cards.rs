@@ -1,5 +1,6 @@
 extern crate games_microservice;
-use games_microservice::games::{StandardCard, StandardDeck};
+use games_microservice::games::{StandardCard, StandardCardFace, StandardDeck};
+use std::cmp::Ordering;
 
 const CARDS: [&'static str; 56] = [
     "HEARTS:ACE",
@@ -73,3 +74,34 @@ fn test_card_from_str() {
         };
     }
 }
+
+#[test]
+fn test_card_rank() {
+    let card = |card: &str| card.parse::<StandardCard>().unwrap();
+
+    assert_eq!(card("HEARTS:ACE").rank(), 14);
+    assert_eq!(card("CLUBS:TWO").rank(), 2);
+    assert_eq!(card("SPADES:JOKER").rank(), 0);
+    assert!(StandardCardFace::King > StandardCardFace::Queen);
+    assert!(StandardCardFace::Ace > StandardCardFace::King);
+    assert!(StandardCardFace::Joker < StandardCardFace::Two);
+
+    assert_eq!(
+        card("HEARTS:TEN").cmp_rank(&card("DIAMONDS:TEN")),
+        Ordering::Equal
+    );
+    assert_eq!(
+        card("HEARTS:QUEEN").cmp_rank(&card("DIAMONDS:KING")),
+        Ordering::Less
+    );
+
+    let mut faces = CARDS[..14]
+        .iter()
+        .map(|name| card(name).face())
+        .collect::<Vec<_>>();
+    faces.sort();
+    assert_eq!(
+        faces.iter().map(StandardCardFace::rank).collect::<Vec<_>>(),
+        vec![0, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14]
+    );
+}
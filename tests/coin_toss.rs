@@ -0,0 +1,77 @@
+extern crate games_microservice;
+use games_microservice::games::coin_toss::{paytable, toss_coins, Coin, CoinBet, Rules};
+
+#[test]
+fn test_parse_side() {
+    assert_eq!("h".parse::<Coin>().unwrap(), Coin::Heads);
+    assert_eq!("Heads".parse::<Coin>().unwrap(), Coin::Heads);
+    assert_eq!("T".parse::<Coin>().unwrap(), Coin::Tails);
+    assert!("edge".parse::<Coin>().is_err());
+}
+
+#[test]
+fn test_probabilities() {
+    let total: f64 = (0..6).map(|heads| CoinBet::Heads(heads).probability(5, 0.5)).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+
+    assert!((CoinBet::Heads(1).probability(2, 0.5) - 0.5).abs() < 1e-12);
+    assert!((CoinBet::Heads(3).probability(3, 0.6) - 0.216).abs() < 1e-12);
+    assert!((CoinBet::AllSame.probability(3, 0.5) - 0.25).abs() < 1e-12);
+    assert_eq!(CoinBet::Heads(4).probability(3, 0.5), 0.0);
+}
+
+#[test]
+fn test_paytable() {
+    let rules = Rules::default();
+    let entries = paytable(4, &rules).unwrap();
+
+    // Heads(0..=4) and AllSame
+    assert_eq!(entries.len(), 6);
+    for entry in &entries {
+        assert!((entry.probability * entry.pays - (1.0 - rules.house_edge)).abs() < 1e-12);
+    }
+    assert!((entries[0].pays - 16.0 * 0.98).abs() < 1e-9);
+
+    assert!(paytable(0, &rules).is_err());
+    assert!(paytable(rules.max_coins + 1, &rules).is_err());
+}
+
+#[test]
+fn test_toss_coins() {
+    let rules = Rules::default();
+
+    assert!(toss_coins(100, 3, CoinBet::Heads(4), &rules).is_err());
+
+    for _ in 0..100 {
+        let result = toss_coins(100, 3, CoinBet::AllSame, &rules).unwrap();
+        assert_eq!(result.coins.len(), 3);
+        assert!((result.pays - 3.92).abs() < 1e-9);
+        if CoinBet::AllSame.wins(&result.coins) {
+            assert_eq!(result.gain, 292);
+        } else {
+            assert_eq!(result.gain, -100);
+        }
+    }
+
+    // A coin that always lands heads
+    let rules = Rules {
+        heads_chance: 1.0 - 1e-12,
+        ..Rules::default()
+    };
+    let result = toss_coins(100, 5, CoinBet::Heads(5), &rules).unwrap();
+    assert_eq!(result.heads, 5);
+}
+
+#[test]
+fn test_validate() {
+    assert!(Rules::default().validate().is_ok());
+
+    let rules = Rules {
+        heads_chance: 1.0,
+        house_edge: -0.1,
+        max_coins: 0,
+        max_streak: 0,
+        ..Rules::default()
+    };
+    assert_eq!(rules.validate().unwrap_err().len(), 4);
+}
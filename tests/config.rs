@@ -0,0 +1,157 @@
+extern crate games_microservice;
+use games_microservice::config::{Config, ConfigError};
+use std::fs::File;
+use std::io::Read;
+
+#[test]
+fn test_example_config() {
+    let mut contents = String::new();
+    File::open("GameService.example.toml")
+        .unwrap()
+        .read_to_string(&mut contents)
+        .unwrap();
+
+    let config = Config::from_toml(&contents).unwrap();
+    assert_eq!(config.database.pool_size, Some(10));
+    assert_eq!(config.bets.max, 1_000_000);
+    assert_eq!(config.rate_limits["slot_machine"].per_user.requests, 60);
+    config.validate().unwrap();
+}
+
+#[test]
+fn test_partial_config() {
+    let config = Config::from_toml("[database]\nurl = \"postgres://localhost\"").unwrap();
+    assert_eq!(config.coin_toss.house_edge, 0.02);
+    assert_eq!(config.slot_machine.reels.len(), 3);
+    assert!(config.bets.check(100).is_ok());
+}
+
+#[test]
+fn test_invalid_config() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\npool_size = 0\n[bets]\nmin = 10\nmax = 5",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+    assert!(config.bets.check(7).is_err());
+}
+
+#[test]
+fn test_invalid_rps_variant() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[[rps.variants]]\nname = \"rps\"\n\
+         weapons = [{ name = \"Rock\", beats = [\"Paper\"] }, \
+         { name = \"Paper\", beats = [\"Rock\"] }, { name = \"Scissors\", beats = [] }]",
+    ).unwrap();
+
+    match config.validate() {
+        // Rock and Paper beat each other, twice, and Scissors beats nothing
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 3),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_rps_adaptive_rules() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[rps.adaptive]\n\
+         markov_uniform_share = 1.5\nhouse_edge = 1.0",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_holdem_rules() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[holdem]\ndealer_qualifier = 15\n\
+         call_multiplier = 0",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_roulette_tables() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[[roulette.tables]]\nname = \"main\"\n\
+         wheel = \"European\"\n[[roulette.tables]]\nname = \"Main\"\nwheel = \"American\"",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 1),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_over_under() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[over_under]\ndice = 100\nsides = 255\n\
+         house_edge = 1.0",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_hi_lo_and_war() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[war]\ndecks = 0\n[hi_lo]\nmax_guesses = 0",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_keno_and_lottery() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[keno]\npaytable = [[0, 3]]\n\
+         [lottery]\nticket_price = 0",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_crash() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[crash]\ngrowth = 0.0\nhouse_edge = -0.1",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_invalid_mines_and_plinko() {
+    let config = Config::from_toml(
+        "[database]\nurl = \"postgres://localhost\"\n[mines]\nhouse_edge = 1.5\n\
+         [plinko]\nmax_rows = 4",
+    ).unwrap();
+
+    match config.validate() {
+        Err(ConfigError::Invalid(problems)) => assert_eq!(problems.len(), 2),
+        other => panic!("Expected an invalid config, got {:?}", other),
+    }
+}
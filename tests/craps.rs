@@ -0,0 +1,176 @@
+extern crate games_microservice;
+use games_microservice::games::craps::{BetKind, CrapsBet, CrapsTable, Outcome, Rules};
+
+fn table_with(kind: BetKind, amount: u64) -> CrapsTable {
+    let mut table = CrapsTable::new();
+    table.bet(kind, None, amount).unwrap();
+    table
+}
+
+#[test]
+fn test_come_out() {
+    let roll = table_with(BetKind::Pass, 10).roll([3, 4]).unwrap();
+    assert_eq!(roll.resolved[0].outcome, Outcome::Win);
+    assert_eq!(roll.gain, 20);
+    assert!(roll.point.is_none());
+
+    let roll = table_with(BetKind::Pass, 10).roll([1, 2]).unwrap();
+    assert_eq!(roll.resolved[0].outcome, Outcome::Lose);
+    assert_eq!(roll.gain, 0);
+
+    let roll = table_with(BetKind::DontPass, 10).roll([1, 1]).unwrap();
+    assert_eq!(roll.resolved[0].outcome, Outcome::Win);
+    assert_eq!(roll.gain, 20);
+
+    // Bar 12
+    let roll = table_with(BetKind::DontPass, 10).roll([6, 6]).unwrap();
+    assert_eq!(roll.resolved[0].outcome, Outcome::Push);
+    assert_eq!(roll.gain, 10);
+    assert_eq!(roll.settled, 10);
+}
+
+#[test]
+fn test_point_with_odds() {
+    let rules = Rules::default();
+
+    let mut table = table_with(BetKind::Pass, 10);
+    let roll = table.roll([2, 2]).unwrap();
+    assert_eq!(roll.point, Some(4));
+    assert!(roll.resolved.is_empty());
+    assert_eq!(table.odds(BetKind::Pass, None, 20, &rules).unwrap().gain, -20);
+
+    // Point made: even money on the bet, 2 to 1 on the odds
+    let roll = table.roll([1, 3]).unwrap();
+    assert_eq!(roll.gain, 20 + 60);
+    assert_eq!(roll.settled, 30);
+    assert!(roll.point.is_none());
+    assert!(table.bets.is_empty());
+
+    let mut table = table_with(BetKind::DontPass, 10);
+    table.roll([4, 2]).unwrap();
+    table.odds(BetKind::DontPass, None, 12, &rules).unwrap();
+
+    // Seven-out: even money on the bet, 5 to 6 laid on the odds
+    let roll = table.roll([5, 2]).unwrap();
+    assert_eq!(roll.resolved[0].outcome, Outcome::Win);
+    assert_eq!(roll.gain, 20 + 22);
+}
+
+#[test]
+fn test_odds_limit() {
+    let rules = Rules { max_odds: 3 };
+    let mut table = table_with(BetKind::Pass, 10);
+
+    assert!(table.odds(BetKind::Pass, None, 10, &rules).is_err());
+    table.roll([4, 5]).unwrap();
+    assert!(table.odds(BetKind::Pass, None, 30, &rules).is_ok());
+    assert!(table.odds(BetKind::Pass, None, 1, &rules).is_err());
+    assert!(table.odds(BetKind::Come, Some(9), 10, &rules).is_err());
+}
+
+#[test]
+fn test_come_bets() {
+    let rules = Rules::default();
+    let mut table = table_with(BetKind::Pass, 10);
+
+    assert!(table.bet(BetKind::Come, None, 10).is_err());
+    table.roll([3, 3]).unwrap();
+    assert!(table.bet(BetKind::Pass, None, 10).is_err());
+    table.bet(BetKind::Come, None, 10).unwrap();
+
+    // The come bet moves to 5
+    let roll = table.roll([2, 3]).unwrap();
+    assert!(roll.resolved.is_empty());
+    assert_eq!(roll.point, Some(6));
+    assert_eq!(table.bets[1].number, Some(5));
+    table.odds(BetKind::Come, Some(5), 20, &rules).unwrap();
+
+    // Come bet on 5 wins, 3 to 2 on the odds
+    let roll = table.roll([4, 1]).unwrap();
+    assert_eq!(roll.gain, 20 + 50);
+    assert_eq!(table.bets.len(), 1);
+}
+
+#[test]
+fn test_come_odds_off_on_come_out() {
+    let rules = Rules::default();
+    let mut table = table_with(BetKind::Pass, 10);
+    table.roll([4, 4]).unwrap();
+    table.bet(BetKind::Come, None, 10).unwrap();
+    table.roll([5, 5]).unwrap();
+    table.odds(BetKind::Come, Some(10), 10, &rules).unwrap();
+    table.roll([4, 4]).unwrap();
+    table.bet(BetKind::Pass, None, 10).unwrap();
+
+    // Come-out seven: the pass bet wins, the come bet loses but its odds are returned
+    let roll = table.roll([6, 1]).unwrap();
+    assert!(roll.come_out);
+    assert_eq!(roll.gain, 20 + 10);
+}
+
+#[test]
+fn test_come_odds_returned_on_come_out_win() {
+    let rules = Rules::default();
+    let mut table = table_with(BetKind::Pass, 10);
+    table.roll([4, 4]).unwrap();
+    table.bet(BetKind::Come, None, 10).unwrap();
+    table.roll([5, 5]).unwrap();
+    table.odds(BetKind::Come, Some(10), 10, &rules).unwrap();
+    table.roll([4, 4]).unwrap();
+    table.bet(BetKind::Pass, None, 10).unwrap();
+
+    // Come-out ten: the come bet wins even money, its odds come back unpaid
+    let roll = table.roll([5, 5]).unwrap();
+    assert!(roll.come_out);
+    let come = roll.resolved
+        .iter()
+        .find(|resolved| resolved.bet.kind == BetKind::Come)
+        .unwrap();
+    assert_eq!(come.returned, 20 + 10);
+}
+
+#[test]
+fn test_validate() {
+    assert!(Rules::default().validate().is_ok());
+    assert!(Rules { max_odds: 0 }.validate().is_err());
+    assert!(Rules { max_odds: 1000 }.validate().is_err());
+}
+
+#[test]
+fn test_place_bets() {
+    let mut table = CrapsTable::new();
+    assert!(table.bet(BetKind::Place, Some(7), 10).is_err());
+    table.bet(BetKind::Place, Some(6), 12).unwrap();
+    table.bet(BetKind::Pass, None, 10).unwrap();
+
+    // Off on the come-out
+    let roll = table.roll([3, 3]).unwrap();
+    assert!(roll.resolved.is_empty());
+
+    // 7 to 6, and the bet stays up
+    let roll = table.roll([5, 1]).unwrap();
+    assert_eq!(roll.resolved.len(), 2);
+    assert_eq!(roll.gain, 20 + 14);
+    assert_eq!(roll.settled, 10);
+    assert_eq!(table.bets.len(), 1);
+
+    table.roll([4, 4]).unwrap();
+    let roll = table.roll([3, 4]).unwrap();
+    assert_eq!(roll.resolved[0].outcome, Outcome::Lose);
+    assert!(table.bets.is_empty());
+}
+
+#[test]
+fn test_bet_strings() {
+    let bet = CrapsBet {
+        kind: BetKind::DontCome,
+        number: Some(9),
+        amount: 15,
+        odds: 30,
+    };
+    assert_eq!(bet.to_string(), "DONT_COME:9:15:30");
+    assert_eq!("DONT_COME:9:15:30".parse::<CrapsBet>().unwrap(), bet);
+    assert_eq!("PASS:0:10:0".parse::<CrapsBet>().unwrap().number, None);
+    assert!("PASS:0:10".parse::<CrapsBet>().is_err());
+    assert!("FIELD:0:10:0".parse::<CrapsBet>().is_err());
+}
@@ -0,0 +1,69 @@
+extern crate games_microservice;
+extern crate rand;
+use games_microservice::games::crash::{commitment, crash_point, new_seed, payout, Rules};
+
+#[test]
+fn test_commitment() {
+    let seed = new_seed(&mut rand::thread_rng());
+    assert_eq!(seed.len(), 64);
+    assert!(seed.chars().all(|c| c.is_digit(16)));
+
+    assert_eq!(
+        commitment("abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_ne!(commitment(&seed), commitment(&new_seed(&mut rand::thread_rng())));
+}
+
+#[test]
+fn test_crash_point() {
+    let rules = Rules::default();
+    let seed = new_seed(&mut rand::thread_rng());
+    assert_eq!(crash_point(&seed, &rules), crash_point(&seed, &rules));
+
+    let points: Vec<f64> = (0..20_000)
+        .map(|_| crash_point(&new_seed(&mut rand::thread_rng()), &rules))
+        .collect();
+    assert!(points.iter().all(|&point| point >= 1.0 && point <= 1000.0));
+    assert!(points.iter().all(|&point| (point * 100.0 - (point * 100.0).round()).abs() < 1e-6));
+
+    // A round reaches 2x with a chance of 0.99 / 2
+    let doubled = points.iter().filter(|&&point| point >= 2.0).count() as f64 / 20_000.0;
+    assert!((doubled - 0.495).abs() < 0.03, "{} reached 2x", doubled);
+
+    let capped = Rules {
+        max_multiplier: 1.5,
+        ..rules
+    };
+    assert!(crash_point(&seed, &capped) <= 1.5);
+}
+
+#[test]
+fn test_multiplier() {
+    let rules = Rules::default();
+
+    assert_eq!(rules.multiplier_at(0), 1.0);
+    assert_eq!(rules.multiplier_at(10_000), 1.82);
+    assert_eq!(rules.duration(1.0), 0);
+    for &point in &[1.01, 2.0, 7.77, 1000.0] {
+        let duration = rules.duration(point);
+        assert!(rules.multiplier_at(duration - 1) < point);
+        assert!(rules.multiplier_at(duration + 1) >= point);
+    }
+
+    assert_eq!(payout(100, 1.82), 182);
+    assert_eq!(payout(3, 1.5), 4);
+}
+
+#[test]
+fn test_rules() {
+    assert!(Rules::default().validate().is_ok());
+
+    let broken = Rules {
+        betting_secs: 0,
+        growth: 0.0,
+        house_edge: 1.0,
+        max_multiplier: 1.0,
+    };
+    assert_eq!(broken.validate().unwrap_err().len(), 4);
+}
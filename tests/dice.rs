@@ -0,0 +1,153 @@
+extern crate games_microservice;
+use games_microservice::games::dice::{play_over_under, play_sic_bo, BetKind, Dice, DiceRoller,
+                                      Direction, OverUnderRules, SicBoBet, SicBoRules};
+
+fn bet(bet: &str, amount: u64) -> SicBoBet {
+    SicBoBet {
+        bet: bet.to_string(),
+        amount,
+    }
+}
+
+#[test]
+fn test_parse_dice() {
+    assert_eq!("3d6".parse::<Dice>().unwrap(), Dice { count: 3, sides: 6 });
+    assert_eq!("D20".parse::<Dice>().unwrap(), Dice { count: 1, sides: 20 });
+    assert_eq!(Dice { count: 2, sides: 10 }.to_string(), "2d10");
+    for invalid in &["0d6", "3d1", "3x6", "6", "101d6", "3d256", "d"] {
+        assert!(invalid.parse::<Dice>().is_err(), "{} parsed", invalid);
+    }
+}
+
+#[test]
+fn test_distribution() {
+    let two = Dice { count: 2, sides: 6 };
+    assert_eq!(two.outcomes(), Some(36));
+    assert_eq!(
+        two.distribution().unwrap(),
+        vec![0, 0, 1, 2, 3, 4, 5, 6, 5, 4, 3, 2, 1]
+    );
+
+    let three = Dice { count: 3, sides: 6 };
+    let ways = three.distribution().unwrap();
+    assert_eq!(ways.iter().sum::<u64>(), 216);
+    assert_eq!(ways[10], 27);
+    assert_eq!(ways.len() as u32, three.max() + 1);
+
+    let many = Dice {
+        count: 100,
+        sides: 255,
+    };
+    assert_eq!(many.outcomes(), None);
+    assert_eq!(many.distribution(), None);
+}
+
+#[test]
+fn test_seeded_rolls() {
+    let dice = Dice { count: 10, sides: 8 };
+    let rolls = DiceRoller::seeded(42).roll(dice);
+    assert_eq!(rolls, DiceRoller::seeded(42).roll(dice));
+    assert_eq!(rolls.len(), 10);
+    assert!(rolls.iter().all(|&roll| roll >= 1 && roll <= 8));
+
+    let mut roller = DiceRoller::seeded(7);
+    let first = roller.roll(dice);
+    assert_eq!(roller.seed(), 7);
+    assert_ne!(first, roller.roll(dice));
+
+    let max = Dice {
+        count: 100,
+        sides: 255,
+    };
+    assert!(DiceRoller::new().roll(max).iter().all(|&roll| roll >= 1));
+}
+
+#[test]
+fn test_sic_bo_payouts() {
+    let pays = |bet: &str, dice: [u8; 3]| BetKind::parse(bet).unwrap().pays(&dice);
+
+    assert_eq!(pays("small", [1, 2, 3]), Some(1));
+    assert_eq!(pays("big", [1, 2, 3]), None);
+    assert_eq!(pays("even", [1, 2, 3]), Some(1));
+    assert_eq!(pays("total 6", [1, 2, 3]), Some(17));
+    assert_eq!(pays("total 4", [1, 1, 2]), Some(60));
+    assert_eq!(pays("total 10", [6, 3, 1]), Some(6));
+    assert_eq!(pays("combination 3-1", [1, 2, 3]), Some(5));
+    assert_eq!(pays("combination 1-4", [1, 2, 3]), None);
+    assert_eq!(pays("single 5", [5, 5, 1]), Some(2));
+    assert_eq!(pays("double 5", [5, 5, 1]), Some(10));
+
+    // Triples
+    assert_eq!(pays("small", [2, 2, 2]), None);
+    assert_eq!(pays("even", [2, 2, 2]), None);
+    assert_eq!(pays("any_triple", [2, 2, 2]), Some(30));
+    assert_eq!(pays("triple 2", [2, 2, 2]), Some(180));
+    assert_eq!(pays("triple 3", [2, 2, 2]), None);
+    assert_eq!(pays("single 2", [2, 2, 2]), Some(3));
+    assert_eq!(pays("double 2", [2, 2, 2]), Some(10));
+
+    for invalid in &["total 3", "total 18", "triple 7", "combination 2-2", "double", "field"] {
+        assert!(BetKind::parse(invalid).is_err(), "{} parsed", invalid);
+    }
+}
+
+#[test]
+fn test_sic_bo() {
+    let rules = SicBoRules::default();
+    let game = play_sic_bo(
+        &[bet("big", 10), bet("total 15", 5), bet("triple 1", 1)],
+        [6, 5, 4],
+        &rules,
+    ).unwrap();
+    assert_eq!(game.total, 15);
+    assert_eq!(game.bet, 16);
+    assert_eq!(game.gain, 10 + 5 * 17 - 1);
+    assert!(game.bets[0].won && !game.bets[2].won);
+
+    assert!(play_sic_bo(&[bet("big", 0)], [6, 5, 4], &rules).is_err());
+    assert!(play_sic_bo(&[bet("big", 10), bet("huge", 10)], [6, 5, 4], &rules).is_err());
+    // Totals and payouts that don't fit are refused rather than wrapped
+    let huge = u64::max_value() / 2;
+    assert!(play_sic_bo(&[bet("big", huge), bet("small", huge + 3)], [6, 5, 4], &rules).is_err());
+    assert!(play_sic_bo(&[bet("any_triple", huge / 8)], [2, 2, 2], &rules).is_err());
+    let rules = SicBoRules { max_bets: 1 };
+    assert!(play_sic_bo(&[bet("big", 1), bet("small", 1)], [6, 5, 4], &rules).is_err());
+}
+
+#[test]
+fn test_over_under_odds() {
+    let rules = OverUnderRules::default();
+
+    let (probability, pays) = rules.odds(Direction::Over, 7).unwrap();
+    assert!((probability - 15.0 / 36.0).abs() < 1e-12);
+    assert!((pays - 0.98 * 36.0 / 15.0).abs() < 1e-12);
+
+    let (probability, _) = rules.odds(Direction::Under, 3).unwrap();
+    assert!((probability - 1.0 / 36.0).abs() < 1e-12);
+
+    // Can't win, or always wins
+    assert!(rules.odds(Direction::Over, 12).is_err());
+    assert!(rules.odds(Direction::Under, 2).is_err());
+    assert!(rules.odds(Direction::Over, 1).is_err());
+
+    let fair = OverUnderRules {
+        house_edge: 0.0,
+        ..rules
+    };
+    assert!((fair.odds(Direction::Under, 7).unwrap().1 - 2.4).abs() < 1e-12);
+}
+
+#[test]
+fn test_over_under() {
+    let rules = OverUnderRules::default();
+
+    let game = play_over_under(100, Direction::Over, 7, vec![6, 6], &rules).unwrap();
+    assert_eq!(game.total, 12);
+    assert_eq!(game.gain, 135);
+
+    let game = play_over_under(100, Direction::Over, 7, vec![3, 4], &rules).unwrap();
+    assert_eq!(game.gain, -100);
+
+    assert_eq!("Under".parse::<Direction>().unwrap(), Direction::Under);
+    assert!("sideways".parse::<Direction>().is_err());
+}
@@ -0,0 +1,81 @@
+extern crate games_microservice;
+use games_microservice::games::hi_lo::{guess_next, probability, Guess, HiLoOutcome, Rules};
+use games_microservice::games::{StandardCard, StandardDeck, STANDARD_DECK_OF_CARDS};
+
+fn card(card: &str) -> StandardCard {
+    card.parse().unwrap()
+}
+
+/// Full deck without `without`, `next` is drawn first
+fn deck(without: &str, next: &str) -> StandardDeck {
+    let mut cards: Vec<StandardCard> = STANDARD_DECK_OF_CARDS
+        .iter()
+        .filter(|card| card.to_string() != without && card.to_string() != next)
+        .cloned()
+        .collect();
+    cards.push(card(next));
+    StandardDeck { cards }
+}
+
+#[test]
+fn test_probability() {
+    let seven = card("HEARTS:SEVEN");
+    let cards = deck("HEARTS:SEVEN", "CLUBS:TWO").cards;
+
+    // 28 cards above a seven and 20 below, the 3 other sevens push
+    assert!((probability(&seven, &cards, Guess::Higher) - 28.0 / 48.0).abs() < 1e-12);
+    assert!((probability(&seven, &cards, Guess::Lower) - 20.0 / 48.0).abs() < 1e-12);
+    assert_eq!(probability(&card("HEARTS:ACE"), &cards, Guess::Higher), 0.0);
+    assert_eq!(probability(&seven, &[card("CLUBS:SEVEN")], Guess::Lower), 0.0);
+}
+
+#[test]
+fn test_guesses() {
+    let rules = Rules::default();
+    let seven = card("HEARTS:SEVEN");
+    let play = |next: &str, guess: Guess| {
+        guess_next(&seven, &mut deck("HEARTS:SEVEN", next), guess, 2.0, &rules).unwrap()
+    };
+
+    let result = play("CLUBS:KING", Guess::Higher);
+    assert_eq!(result.outcome, HiLoOutcome::Win);
+    assert!((result.multiplier - 2.0 * 0.98 * 48.0 / 28.0).abs() < 1e-12);
+
+    let result = play("CLUBS:SEVEN", Guess::Lower);
+    assert_eq!(result.outcome, HiLoOutcome::Push);
+    assert_eq!(result.multiplier, 2.0);
+
+    let result = play("CLUBS:KING", Guess::Lower);
+    assert_eq!(result.outcome, HiLoOutcome::Lose);
+    assert_eq!(result.multiplier, 0.0);
+}
+
+#[test]
+fn test_invalid_guesses() {
+    let rules = Rules::default();
+
+    // Nothing is higher than an ace
+    let ace = card("HEARTS:ACE");
+    let mut cards = deck("HEARTS:ACE", "CLUBS:KING");
+    assert!(guess_next(&ace, &mut cards, Guess::Higher, 1.0, &rules).is_err());
+    assert_eq!(cards.cards.len(), 51);
+
+    // Everything that doesn't push is higher than a two, a win would pay less than the bet
+    let two = card("HEARTS:TWO");
+    let mut cards = deck("HEARTS:TWO", "CLUBS:KING");
+    assert!(guess_next(&two, &mut cards, Guess::Higher, 1.0, &rules).is_err());
+
+    assert_eq!("hi".parse::<Guess>().unwrap(), Guess::Higher);
+    assert_eq!("Lower".parse::<Guess>().unwrap(), Guess::Lower);
+    assert!("same".parse::<Guess>().is_err());
+}
+
+#[test]
+fn test_rules() {
+    assert!(Rules::default().validate().is_ok());
+    let rules = Rules {
+        house_edge: -0.1,
+        max_guesses: 52,
+    };
+    assert_eq!(rules.validate().unwrap_err().len(), 2);
+}
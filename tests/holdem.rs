@@ -0,0 +1,95 @@
+extern crate games_microservice;
+use games_microservice::games::StandardCard;
+use games_microservice::games::holdem::{settle, HoldemHand, HoldemOutcome, Rules};
+
+fn cards(cards: &[&str]) -> Vec<StandardCard> {
+    cards.iter().map(|card| card.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_dealer_does_not_qualify() {
+    let rules = Rules::default();
+    let board = cards(&[
+        "HEARTS:TWO",
+        "CLUBS:SEVEN",
+        "SPADES:NINE",
+        "DIAMONDS:JACK",
+        "HEARTS:KING",
+    ]);
+    // Pair of threes is below the pair of fours the dealer needs
+    let dealer = cards(&["CLUBS:THREE", "SPADES:THREE"]);
+    let player = cards(&["CLUBS:KING", "SPADES:FIVE"]);
+
+    // Ante pays 1 to 1 and the call of 20 is returned
+    assert_eq!(
+        settle(&player, &dealer, &board, 10, &rules),
+        Some((HoldemOutcome::DealerDoesNotQualify, 40))
+    );
+}
+
+#[test]
+fn test_win_lose_push() {
+    let rules = Rules::default();
+    let board = cards(&[
+        "HEARTS:TWO",
+        "CLUBS:SEVEN",
+        "SPADES:NINE",
+        "DIAMONDS:JACK",
+        "HEARTS:KING",
+    ]);
+    let dealer = cards(&["CLUBS:NINE", "SPADES:FOUR"]);
+
+    // Kings beat nines: ante 10 + 10, call 20 + 20
+    let player = cards(&["CLUBS:KING", "SPADES:FIVE"]);
+    assert_eq!(
+        settle(&player, &dealer, &board, 10, &rules),
+        Some((HoldemOutcome::Win, 60))
+    );
+
+    let player = cards(&["CLUBS:EIGHT", "SPADES:FIVE"]);
+    assert_eq!(
+        settle(&player, &dealer, &board, 10, &rules),
+        Some((HoldemOutcome::Lose, 0))
+    );
+
+    // Both play nines with the same kickers from the board
+    let player = cards(&["HEARTS:NINE", "DIAMONDS:THREE"]);
+    assert_eq!(
+        settle(&player, &dealer, &board, 10, &rules),
+        Some((HoldemOutcome::Push, 30))
+    );
+}
+
+#[test]
+fn test_ante_paytable() {
+    let rules = Rules::default();
+    let board = cards(&["HEARTS:TWO", "HEARTS:SEVEN", "SPADES:NINE", "HEARTS:JACK", "CLUBS:KING"]);
+    let dealer = cards(&["CLUBS:NINE", "SPADES:FOUR"]);
+    let player = cards(&["HEARTS:ACE", "HEARTS:FIVE"]);
+
+    // The flush pays the ante 2 to 1
+    let hand = HoldemHand::called(&player, &dealer, &board, 10, &rules).unwrap();
+    assert_eq!(hand.outcome, Some(HoldemOutcome::Win));
+    assert_eq!(hand.player_hand, "Flush, Ace High");
+    assert_eq!(hand.dealer_hand, Some("Pair of Nines".to_string()));
+    assert_eq!(hand.dealer_qualifies, Some(true));
+    assert_eq!(hand.call, 20);
+    assert_eq!(hand.gain, 10 + 20 + 40 - 20);
+}
+
+#[test]
+fn test_deal_and_fold() {
+    let flop = cards(&["HEARTS:TWO", "HEARTS:SEVEN", "SPADES:NINE"]);
+    let player = cards(&["CLUBS:THREE", "DIAMONDS:FIVE"]);
+    let dealer = cards(&["CLUBS:NINE", "SPADES:FOUR"]);
+
+    let dealt = HoldemHand::dealt(&player, &flop, 10).unwrap();
+    assert_eq!(dealt.gain, -10);
+    assert!(dealt.dealer.is_none());
+    assert_eq!(dealt.player_hand, "Nine High");
+
+    let folded = HoldemHand::folded(&player, &dealer, &flop, 10).unwrap();
+    assert_eq!(folded.outcome, Some(HoldemOutcome::Fold));
+    assert_eq!(folded.gain, 0);
+    assert_eq!(folded.dealer.unwrap().len(), 2);
+}
@@ -0,0 +1,58 @@
+extern crate games_microservice;
+use games_microservice::establish_test_connection_pool;
+use games_microservice::games::slot_machine::Combination;
+use games_microservice::jackpot::{Jackpot, JackpotConfig};
+use std::thread;
+
+#[test]
+fn test_contribution() {
+    let config = JackpotConfig::default();
+    assert_eq!(config.contribution_of(1000), 10);
+    assert_eq!(config.contribution_of(99), 0);
+    assert_eq!(config.blackjack_contribution_of(1000), 0);
+    assert_eq!(
+        config.combination,
+        Combination::Symbols(vec![String::from("⭐"); 3])
+    );
+}
+
+#[test]
+fn test_validate() {
+    assert!(JackpotConfig::default().validate().is_ok());
+
+    let config = JackpotConfig {
+        contribution: 1.0,
+        blackjack_contribution: -0.5,
+        ..JackpotConfig::default()
+    };
+    assert_eq!(config.validate().unwrap_err().len(), 2);
+}
+
+#[test]
+fn test_concurrent_wagers() {
+    let pool = establish_test_connection_pool();
+    let seed = 1000;
+    let before = Jackpot::contribute(&*pool.get().unwrap(), 0, seed).unwrap();
+
+    let threads: Vec<_> = (0..8)
+        .map(|_| {
+            let pool = pool.clone();
+            thread::spawn(move || {
+                let conn = pool.get().unwrap();
+                for _ in 0..25 {
+                    Jackpot::wager(&*conn, 3, false, seed).unwrap();
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+
+    assert_eq!(Jackpot::pool(&pool, seed).unwrap(), before + 8 * 25 * 3);
+
+    let result = Jackpot::wager(&*pool.get().unwrap(), 3, true, seed).unwrap();
+    assert_eq!(result.won, before + 8 * 25 * 3 + 3);
+    assert_eq!(result.pool, seed);
+    assert_eq!(Jackpot::pool(&pool, seed).unwrap(), seed);
+}
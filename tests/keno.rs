@@ -0,0 +1,71 @@
+extern crate games_microservice;
+extern crate rand;
+use games_microservice::games::keno::{draw, paytable, play, probability, quick_pick, KenoError,
+                                      Picks, Rules, DRAWN, MAX_PICKS};
+
+#[test]
+fn test_probability() {
+    for picks in 1..MAX_PICKS + 1 {
+        let total: f64 = (0..picks + 1).map(|hits| probability(picks, hits)).sum();
+        assert!((total - 1.0).abs() < 1e-9, "{} picks sum to {}", picks, total);
+    }
+
+    assert!((probability(1, 1) - 0.25).abs() < 1e-12);
+    assert!((probability(2, 2) - 19.0 / 316.0).abs() < 1e-12);
+    assert_eq!(probability(3, 4), 0.0);
+}
+
+#[test]
+fn test_default_paytable() {
+    let rules = Rules::default();
+    assert!(rules.validate().is_ok());
+
+    for row in paytable(&rules) {
+        assert_eq!(row.entries.len(), row.picks + 1);
+        assert!(row.return_to_player > 0.7 && row.return_to_player < 1.0);
+    }
+    assert_eq!(rules.pays(1, 1), 3);
+    assert_eq!(rules.pays(10, 0), 3);
+    assert_eq!(rules.pays(11, 0), 0);
+
+    let broken = Rules {
+        paytable: vec![vec![0, 3], vec![0, 1]],
+    };
+    assert_eq!(broken.validate().unwrap_err().len(), 2);
+}
+
+#[test]
+fn test_picks() {
+    assert_eq!(Picks::parse("42-3-17").unwrap(), Picks(vec![3, 17, 42]));
+    match Picks::parse("3-3") {
+        Err(KenoError::DuplicateNumber(3)) => {}
+        other => panic!("Expected a duplicate, got {:?}", other),
+    }
+    assert!(Picks::parse("0-5").is_err());
+    assert!(Picks::parse("81").is_err());
+    assert!(Picks::parse("1-2-3-4-5-6-7-8-9-10-11").is_err());
+    assert!(quick_pick(&mut rand::thread_rng(), 0).is_err());
+    assert_eq!(quick_pick(&mut rand::thread_rng(), 10).unwrap().0.len(), 10);
+}
+
+#[test]
+fn test_play() {
+    let rules = Rules::default();
+    let drawn = draw(&mut rand::thread_rng());
+    assert_eq!(drawn.len(), DRAWN);
+
+    let picks = Picks(drawn[..4].to_vec());
+    let game = play(10, &picks, drawn.clone(), &rules);
+    assert_eq!(game.hits, picks.0);
+    assert_eq!(game.pays, 150);
+    assert_eq!(game.gain, 1490);
+
+    let game = play(10, &Picks(missed(&drawn)), drawn, &rules);
+    assert!(game.hits.is_empty());
+    assert_eq!(game.gain, -10);
+}
+
+/// Two numbers that weren't drawn
+fn missed(drawn: &[u8]) -> Vec<u8> {
+    (1..81).filter(|n| !drawn.contains(n)).take(2).collect()
+}
@@ -0,0 +1,71 @@
+extern crate games_microservice;
+extern crate rand;
+use games_microservice::games::lottery::{settle, LotteryError, Rules, Settlement};
+
+#[test]
+fn test_tickets() {
+    let rules = Rules::default();
+
+    assert_eq!(rules.ticket("12-3-7").unwrap(), vec![3, 7, 12]);
+    for ticket in &["3-7", "3-7-12-14", "3-3-7", "0-7-12", "3-7-16", "3-seven-12"] {
+        match rules.ticket(ticket) {
+            Err(LotteryError::InvalidTicket(_)) => {}
+            other => panic!("Expected {} to be invalid, got {:?}", ticket, other),
+        }
+    }
+
+    let ticket = rules.quick_pick(&mut rand::thread_rng());
+    assert_eq!(ticket.len(), 3);
+    assert!(ticket.windows(2).all(|pair| pair[0] < pair[1]));
+    assert!(ticket.iter().all(|&number| number >= 1 && number <= 15));
+}
+
+#[test]
+fn test_draw_windows() {
+    let rules = Rules::default();
+
+    assert_eq!(rules.draw_at(0), 0);
+    assert_eq!(rules.draw_at(3599), 0);
+    assert_eq!(rules.draw_at(3600), 1);
+    assert_eq!(rules.closes_at(0), 3600);
+    assert_eq!(rules.closes_at(rules.draw_at(7250)), 10800);
+}
+
+#[test]
+fn test_settle() {
+    let numbers = [2, 5, 9];
+    let tickets = vec![vec![2, 5, 9], vec![1, 5, 9], vec![2, 5, 9], vec![2, 5, 9]];
+
+    assert_eq!(
+        settle(&tickets, &numbers, 100),
+        Settlement {
+            winners: vec![0, 2, 3],
+            payout: 33,
+            rollover: 1,
+        }
+    );
+    assert_eq!(
+        settle(&tickets[1..2], &numbers, 100),
+        Settlement {
+            winners: vec![],
+            payout: 0,
+            rollover: 100,
+        }
+    );
+}
+
+#[test]
+fn test_rules() {
+    let rules = Rules::default();
+    assert!(rules.validate().is_ok());
+    // 10% of the sales is kept, the rollover is added whole
+    assert_eq!(rules.pool(1000, 7), 907);
+
+    let broken = Rules {
+        interval: 0,
+        picks: 15,
+        house_cut: 1.0,
+        ..Rules::default()
+    };
+    assert_eq!(broken.validate().unwrap_err().len(), 3);
+}
@@ -0,0 +1,41 @@
+extern crate games_microservice;
+use games_microservice::metrics::{payout, Gauge, Metrics};
+use std::time::Duration;
+
+#[test]
+fn test_payout() {
+    assert_eq!(payout(100, -100), 0);
+    assert_eq!(payout(100, 0), 100);
+    assert_eq!(payout(100, 50), 150);
+}
+
+#[test]
+fn test_render() {
+    let metrics = Metrics::new();
+    metrics.record_game("slot_machine", 100, 0);
+    metrics.record_game("slot_machine", 100, 200);
+    metrics.record_request("GET", "/slot_machine/<bet>", 200, Duration::from_millis(20));
+
+    let text = metrics.render(&[
+        Gauge {
+            name: "game_service_blackjack_active_sessions",
+            help: "BlackJack sessions that are still in progress",
+            value: 3.0,
+        },
+    ]);
+
+    assert!(text.contains("game_service_games_played_total{game=\"slot_machine\"} 2"));
+    assert!(text.contains("game_service_wagered_total{game=\"slot_machine\"} 200"));
+    assert!(text.contains("game_service_paid_out_total{game=\"slot_machine\"} 200"));
+    assert!(text.contains(
+        "game_service_http_requests_total{method=\"GET\",route=\"/slot_machine/<bet>\",status=\"200\"} 1"
+    ));
+    assert!(text.contains(
+        "game_service_http_request_duration_seconds_bucket{method=\"GET\",route=\"/slot_machine/<bet>\",le=\"0.01\"} 0"
+    ));
+    assert!(text.contains(
+        "game_service_http_request_duration_seconds_bucket{method=\"GET\",route=\"/slot_machine/<bet>\",le=\"0.025\"} 1"
+    ));
+    assert!(text.contains("# TYPE game_service_blackjack_active_sessions gauge"));
+    assert!(text.contains("game_service_blackjack_active_sessions 3"));
+}
@@ -0,0 +1,69 @@
+extern crate games_microservice;
+extern crate rand;
+use games_microservice::games::mines::{paytable, probability, MinesBoard, MinesError, MinesGame,
+                                       Rules, TILES};
+
+#[test]
+fn test_probability() {
+    assert_eq!(probability(1, 0), 1.0);
+    assert!((probability(1, 1) - 24.0 / 25.0).abs() < 1e-12);
+    assert!((probability(3, 2) - 22.0 / 25.0 * 21.0 / 24.0).abs() < 1e-12);
+    // 1 in 25 choose 1 of revealing every safe tile around 24 mines
+    assert!((probability(24, 1) - 1.0 / 25.0).abs() < 1e-12);
+    assert_eq!(probability(24, 2), 0.0);
+}
+
+#[test]
+fn test_paytable() {
+    let rules = Rules::default();
+
+    let steps = paytable(3, &rules).unwrap();
+    assert_eq!(steps.len(), 22);
+    for step in &steps {
+        assert!((step.multiplier * step.probability - 0.99).abs() < 1e-9);
+    }
+    assert!(steps.windows(2).all(|pair| pair[0].multiplier < pair[1].multiplier));
+    assert!((paytable(24, &rules).unwrap()[0].multiplier - 24.75).abs() < 1e-9);
+
+    for &mines in &[0, TILES] {
+        match paytable(mines, &rules) {
+            Err(MinesError::InvalidMines(_)) => {}
+            other => panic!("Expected invalid mines, got {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn test_board() {
+    let rules = Rules::default();
+    let mut board = MinesBoard::new(&mut rand::thread_rng(), 5).unwrap();
+    assert_eq!(board.mines.len(), 5);
+    assert!(board.mines.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let safe: Vec<u8> = (0..TILES).filter(|tile| !board.mines.contains(tile)).collect();
+    assert!(!board.reveal(safe[0]).unwrap());
+    match board.reveal(safe[0]) {
+        Err(MinesError::AlreadyRevealed(_)) => {}
+        other => panic!("Expected an already revealed tile, got {:?}", other),
+    }
+    assert!(board.reveal(TILES).is_err());
+
+    let game = MinesGame::new(100, &board, &rules);
+    assert_eq!(game.pot, (100.0 * 0.99 * 25.0 / 20.0) as u64);
+    assert!(game.next_multiplier.unwrap() > game.multiplier);
+
+    for &tile in &safe[1..] {
+        assert!(!board.reveal(tile).unwrap());
+    }
+    assert!(board.cleared());
+    assert!(MinesGame::new(100, &board, &rules).next_multiplier.is_none());
+
+    let mine = board.mines[0];
+    assert!(board.reveal(mine).unwrap());
+}
+
+#[test]
+fn test_rules() {
+    assert!(Rules::default().validate().is_ok());
+    assert!(Rules { house_edge: 1.0 }.validate().is_err());
+}
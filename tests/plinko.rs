@@ -0,0 +1,86 @@
+extern crate games_microservice;
+use games_microservice::games::plinko::{paytable, play, probability, Bounce, PlinkoError, Risk,
+                                        Rules};
+
+#[test]
+fn test_probability() {
+    assert!((probability(8, 0) - 1.0 / 256.0).abs() < 1e-12);
+    assert!((probability(8, 4) - 70.0 / 256.0).abs() < 1e-12);
+    assert_eq!(probability(8, 9), 0.0);
+
+    for rows in 1..17 {
+        let total: f64 = (0..rows + 1).map(|slot| probability(rows, slot)).sum();
+        assert!((total - 1.0).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_paytable() {
+    let rules = Rules::default();
+
+    for rows in rules.min_rows..rules.max_rows + 1 {
+        for &risk in &[Risk::Low, Risk::Medium, Risk::High] {
+            let slots = paytable(rows, risk, &rules).unwrap();
+            assert_eq!(slots.len(), rows as usize + 1);
+
+            // Rounding down to cents only takes from the player
+            let returned: f64 = slots
+                .iter()
+                .map(|slot| slot.probability * slot.multiplier)
+                .sum();
+            assert!(returned <= 0.99 && returned > 0.97, "{} on {} rows", returned, rows);
+
+            let middle = rows as usize / 2;
+            assert!(slots[0].multiplier > slots[middle].multiplier);
+            assert_eq!(slots[0].multiplier, slots[rows as usize].multiplier);
+        }
+    }
+
+    let low = paytable(16, Risk::Low, &rules).unwrap();
+    let high = paytable(16, Risk::High, &rules).unwrap();
+    assert!(high[0].multiplier > low[0].multiplier);
+    assert!(high[8].multiplier < low[8].multiplier);
+
+    match paytable(7, Risk::Low, &rules) {
+        Err(PlinkoError::InvalidRows(7)) => {}
+        other => panic!("Expected invalid rows, got {:?}", other),
+    }
+    assert!("extreme".parse::<Risk>().is_err());
+    assert_eq!("HIGH".parse::<Risk>().unwrap(), Risk::High);
+}
+
+#[test]
+fn test_play() {
+    let rules = Rules::default();
+    let path = vec![Bounce::Right; 8];
+
+    let game = play(100, 8, Risk::Medium, path, &rules).unwrap();
+    assert_eq!(game.slot, 8);
+    assert_eq!(game.multiplier, paytable(8, Risk::Medium, &rules).unwrap()[8].multiplier);
+    assert_eq!(game.gain, (100.0 * game.multiplier) as i64 - 100);
+
+    let mut path = vec![Bounce::Left; 4];
+    path.extend(vec![Bounce::Right; 4]);
+    let game = play(100, 8, Risk::High, path, &rules).unwrap();
+    assert_eq!(game.slot, 4);
+    assert!(game.gain < 0);
+
+    match play(100, 8, Risk::Low, vec![Bounce::Right; 9], &rules) {
+        Err(PlinkoError::InvalidPath(9, 8)) => {}
+        other => panic!("Expected invalid path, got {:?}", other),
+    }
+    assert!(play(100, 8, Risk::Low, vec![Bounce::Left; 7], &rules).is_err());
+}
+
+#[test]
+fn test_rules() {
+    assert!(Rules::default().validate().is_ok());
+
+    let broken = Rules {
+        house_edge: -0.5,
+        min_rows: 20,
+        high: 0.5,
+        ..Rules::default()
+    };
+    assert_eq!(broken.validate().unwrap_err().len(), 3);
+}
@@ -0,0 +1,296 @@
+extern crate games_microservice;
+use games_microservice::games::{StandardCard, STANDARD_DECK_OF_CARDS};
+use games_microservice::games::poker::{best_hand, evaluate, HandCategory, ACE};
+use std::collections::HashMap;
+
+fn hand(cards: &[&str]) -> Vec<StandardCard> {
+    cards.iter().map(|card| card.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_categories() {
+    let cases = [
+        (
+            ["HEARTS:TEN", "HEARTS:JACK", "HEARTS:QUEEN", "HEARTS:KING", "HEARTS:ACE"],
+            HandCategory::StraightFlush,
+        ),
+        (
+            ["CLUBS:NINE", "SPADES:NINE", "HEARTS:NINE", "DIAMONDS:NINE", "HEARTS:TWO"],
+            HandCategory::FourOfAKind,
+        ),
+        (
+            ["CLUBS:KING", "SPADES:KING", "HEARTS:KING", "DIAMONDS:FIVE", "HEARTS:FIVE"],
+            HandCategory::FullHouse,
+        ),
+        (
+            ["CLUBS:TWO", "CLUBS:SEVEN", "CLUBS:NINE", "CLUBS:JACK", "CLUBS:KING"],
+            HandCategory::Flush,
+        ),
+        (
+            ["CLUBS:SIX", "SPADES:SEVEN", "HEARTS:EIGHT", "DIAMONDS:NINE", "HEARTS:TEN"],
+            HandCategory::Straight,
+        ),
+        (
+            ["CLUBS:FOUR", "SPADES:FOUR", "HEARTS:FOUR", "DIAMONDS:NINE", "HEARTS:TEN"],
+            HandCategory::ThreeOfAKind,
+        ),
+        (
+            ["CLUBS:FOUR", "SPADES:FOUR", "HEARTS:NINE", "DIAMONDS:NINE", "HEARTS:TEN"],
+            HandCategory::TwoPair,
+        ),
+        (
+            ["CLUBS:FOUR", "SPADES:FOUR", "HEARTS:EIGHT", "DIAMONDS:NINE", "HEARTS:TEN"],
+            HandCategory::OnePair,
+        ),
+        (
+            ["CLUBS:TWO", "SPADES:FOUR", "HEARTS:EIGHT", "DIAMONDS:NINE", "HEARTS:TEN"],
+            HandCategory::HighCard,
+        ),
+    ];
+
+    for &(ref cards, category) in &cases {
+        assert_eq!(evaluate(&hand(cards)).unwrap().category, category);
+    }
+}
+
+#[test]
+fn test_wheel() {
+    let wheel = evaluate(&hand(&[
+        "CLUBS:ACE",
+        "SPADES:TWO",
+        "HEARTS:THREE",
+        "DIAMONDS:FOUR",
+        "HEARTS:FIVE",
+    ])).unwrap();
+    assert_eq!(wheel.category, HandCategory::Straight);
+    assert_eq!(wheel.ranks, vec![5]);
+
+    let six_high = evaluate(&hand(&[
+        "CLUBS:SIX",
+        "SPADES:TWO",
+        "HEARTS:THREE",
+        "DIAMONDS:FOUR",
+        "HEARTS:FIVE",
+    ])).unwrap();
+    assert!(six_high > wheel);
+
+    // Aces don't wrap around
+    let wrapped = evaluate(&hand(&[
+        "CLUBS:QUEEN",
+        "SPADES:KING",
+        "HEARTS:ACE",
+        "DIAMONDS:TWO",
+        "HEARTS:THREE",
+    ])).unwrap();
+    assert_eq!(wrapped.category, HandCategory::HighCard);
+}
+
+#[test]
+fn test_royal() {
+    let royal = evaluate(&hand(&[
+        "SPADES:ACE",
+        "SPADES:KING",
+        "SPADES:QUEEN",
+        "SPADES:JACK",
+        "SPADES:TEN",
+    ])).unwrap();
+    assert!(royal.is_royal());
+    assert_eq!(royal.ranks, vec![ACE]);
+
+    let steel_wheel = evaluate(&hand(&[
+        "SPADES:ACE",
+        "SPADES:TWO",
+        "SPADES:THREE",
+        "SPADES:FOUR",
+        "SPADES:FIVE",
+    ])).unwrap();
+    assert_eq!(steel_wheel.category, HandCategory::StraightFlush);
+    assert!(!steel_wheel.is_royal());
+}
+
+#[test]
+fn test_kickers() {
+    let kings_over_fives = evaluate(&hand(&[
+        "CLUBS:KING",
+        "SPADES:KING",
+        "HEARTS:FIVE",
+        "DIAMONDS:FIVE",
+        "HEARTS:TWO",
+    ])).unwrap();
+    assert_eq!(kings_over_fives.ranks, vec![13, 5, 2]);
+
+    let better_kicker = evaluate(&hand(&[
+        "HEARTS:KING",
+        "DIAMONDS:KING",
+        "CLUBS:FIVE",
+        "SPADES:FIVE",
+        "HEARTS:THREE",
+    ])).unwrap();
+    assert!(better_kicker > kings_over_fives);
+
+    let same = evaluate(&hand(&[
+        "HEARTS:KING",
+        "DIAMONDS:KING",
+        "CLUBS:FIVE",
+        "SPADES:FIVE",
+        "CLUBS:TWO",
+    ])).unwrap();
+    assert_eq!(same, kings_over_fives);
+}
+
+#[test]
+fn test_invalid_hands() {
+    assert!(evaluate(&hand(&["CLUBS:KING", "SPADES:KING"])).is_none());
+    assert!(evaluate(&hand(&[
+        "CLUBS:KING",
+        "SPADES:KING",
+        "HEARTS:FIVE",
+        "DIAMONDS:FIVE",
+        "HEARTS:JOKER",
+    ])).is_none());
+}
+
+#[test]
+fn test_every_five_card_hand() {
+    let deck = &STANDARD_DECK_OF_CARDS;
+    let mut counts = HashMap::new();
+    let mut royals = 0;
+    let mut hand = Vec::with_capacity(5);
+
+    for a in 0..deck.len() {
+        for b in a + 1..deck.len() {
+            for c in b + 1..deck.len() {
+                for d in c + 1..deck.len() {
+                    for e in d + 1..deck.len() {
+                        hand.clear();
+                        hand.extend_from_slice(&[deck[a], deck[b], deck[c], deck[d], deck[e]]);
+                        let rank = evaluate(&hand).unwrap();
+                        if rank.is_royal() {
+                            royals += 1;
+                        }
+                        *counts.entry(rank.category).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    assert_eq!(counts.values().sum::<u32>(), 2_598_960);
+    assert_eq!(royals, 4);
+    assert_eq!(counts[&HandCategory::StraightFlush], 40);
+    assert_eq!(counts[&HandCategory::FourOfAKind], 624);
+    assert_eq!(counts[&HandCategory::FullHouse], 3_744);
+    assert_eq!(counts[&HandCategory::Flush], 5_108);
+    assert_eq!(counts[&HandCategory::Straight], 10_200);
+    assert_eq!(counts[&HandCategory::ThreeOfAKind], 54_912);
+    assert_eq!(counts[&HandCategory::TwoPair], 123_552);
+    assert_eq!(counts[&HandCategory::OnePair], 1_098_240);
+    assert_eq!(counts[&HandCategory::HighCard], 1_302_540);
+}
+
+#[test]
+fn test_best_of_seven() {
+    // The flush beats the straight the same cards make
+    let best = best_hand(&hand(&[
+        "HEARTS:TWO",
+        "HEARTS:SIX",
+        "CLUBS:SEVEN",
+        "HEARTS:EIGHT",
+        "HEARTS:NINE",
+        "SPADES:TEN",
+        "HEARTS:KING",
+    ])).unwrap();
+    assert_eq!(best.rank.category, HandCategory::Flush);
+    assert_eq!(best.rank.ranks, vec![13, 9, 8, 6, 2]);
+    assert_eq!(best.cards.len(), 5);
+
+    // Two sets make a full house, the higher one is the three
+    let best = best_hand(&hand(&[
+        "HEARTS:FOUR",
+        "CLUBS:FOUR",
+        "SPADES:FOUR",
+        "HEARTS:JACK",
+        "CLUBS:JACK",
+        "SPADES:JACK",
+        "HEARTS:ACE",
+    ])).unwrap();
+    assert_eq!(best.rank.category, HandCategory::FullHouse);
+    assert_eq!(best.rank.ranks, vec![11, 4]);
+
+    // Three pairs play the best two with the best kicker left
+    let best = best_hand(&hand(&[
+        "HEARTS:THREE",
+        "CLUBS:THREE",
+        "SPADES:SEVEN",
+        "HEARTS:SEVEN",
+        "CLUBS:QUEEN",
+        "SPADES:QUEEN",
+        "HEARTS:FIVE",
+    ])).unwrap();
+    assert_eq!(best.rank.ranks, vec![12, 7, 5]);
+
+    // A six on top of the wheel makes it six high
+    let best = best_hand(&hand(&[
+        "HEARTS:ACE",
+        "CLUBS:TWO",
+        "SPADES:THREE",
+        "HEARTS:FOUR",
+        "CLUBS:FIVE",
+        "SPADES:SIX",
+    ])).unwrap();
+    assert_eq!(best.rank.category, HandCategory::Straight);
+    assert_eq!(best.rank.ranks, vec![6]);
+
+    let four_cards = hand(&["HEARTS:ACE", "CLUBS:TWO", "SPADES:THREE", "HEARTS:FOUR"]);
+    assert!(best_hand(&four_cards).is_none());
+}
+
+#[test]
+fn test_descriptions() {
+    let cases = [
+        (
+            ["SPADES:ACE", "SPADES:KING", "SPADES:QUEEN", "SPADES:JACK", "SPADES:TEN"],
+            "Royal Flush",
+        ),
+        (
+            ["SPADES:NINE", "SPADES:KING", "SPADES:QUEEN", "SPADES:JACK", "SPADES:TEN"],
+            "Straight Flush, King High",
+        ),
+        (
+            ["CLUBS:NINE", "SPADES:NINE", "HEARTS:NINE", "DIAMONDS:NINE", "HEARTS:TWO"],
+            "Four of a Kind, Nines",
+        ),
+        (
+            ["CLUBS:KING", "SPADES:KING", "HEARTS:KING", "DIAMONDS:FIVE", "HEARTS:FIVE"],
+            "Full House, Kings over Fives",
+        ),
+        (
+            ["CLUBS:TWO", "CLUBS:SEVEN", "CLUBS:NINE", "CLUBS:JACK", "CLUBS:KING"],
+            "Flush, King High",
+        ),
+        (
+            ["CLUBS:ACE", "SPADES:TWO", "HEARTS:THREE", "DIAMONDS:FOUR", "HEARTS:FIVE"],
+            "Straight, Five High",
+        ),
+        (
+            ["CLUBS:SIX", "SPADES:SIX", "HEARTS:SIX", "DIAMONDS:NINE", "HEARTS:TEN"],
+            "Three of a Kind, Sixes",
+        ),
+        (
+            ["CLUBS:FOUR", "SPADES:FOUR", "HEARTS:NINE", "DIAMONDS:NINE", "HEARTS:TEN"],
+            "Two Pair, Nines and Fours",
+        ),
+        (
+            ["CLUBS:JACK", "SPADES:JACK", "HEARTS:EIGHT", "DIAMONDS:NINE", "HEARTS:TEN"],
+            "Pair of Jacks",
+        ),
+        (
+            ["CLUBS:TWO", "SPADES:FOUR", "HEARTS:EIGHT", "DIAMONDS:NINE", "HEARTS:ACE"],
+            "Ace High",
+        ),
+    ];
+
+    for &(ref cards, description) in &cases {
+        assert_eq!(evaluate(&hand(cards)).unwrap().description(), description);
+    }
+}
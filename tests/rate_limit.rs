@@ -0,0 +1,76 @@
+extern crate games_microservice;
+use games_microservice::rate_limit::{route_user, GroupLimits, Quota, RateLimiter, TokenBucket};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+fn limiter(per_key: Quota, per_user: Quota) -> RateLimiter {
+    let mut groups = HashMap::new();
+    groups.insert(String::from("slot_machine"), GroupLimits { per_key, per_user });
+    RateLimiter::new(groups)
+}
+
+#[test]
+fn test_quota_parse() {
+    assert_eq!("30/60".parse::<Quota>().unwrap(), Quota::new(30, 60));
+    assert_eq!(" 5 / 1 ".parse::<Quota>().unwrap(), Quota::new(5, 1));
+    assert!("30".parse::<Quota>().is_err());
+    assert!("0/60".parse::<Quota>().is_err());
+    assert!("30/0".parse::<Quota>().is_err());
+    assert!("a/b".parse::<Quota>().is_err());
+}
+
+#[test]
+fn test_token_bucket_refills() {
+    let quota = Quota::new(2, 10);
+    let now = Instant::now();
+    let mut bucket = TokenBucket::new(&quota, now);
+
+    assert!(bucket.take(&quota, now).is_ok());
+    assert!(bucket.take(&quota, now).is_ok());
+    assert_eq!(bucket.take(&quota, now), Err(Duration::from_secs(5)));
+
+    // One token every 5 seconds
+    assert!(bucket.take(&quota, now + Duration::from_secs(5)).is_ok());
+    assert!(bucket.take(&quota, now + Duration::from_secs(5)).is_err());
+}
+
+#[test]
+fn test_rate_limiter_per_user() {
+    let limiter = limiter(Quota::new(3, 60), Quota::new(1, 60));
+    let now = Instant::now();
+
+    assert_eq!(limiter.check_at("slot_machine", "key", Some(1), now), Ok(()));
+    assert_eq!(limiter.check_at("slot_machine", "key", Some(1), now), Err(60));
+    // Other users and other keys have their own buckets
+    assert_eq!(limiter.check_at("slot_machine", "key", Some(2), now), Ok(()));
+    assert_eq!(limiter.check_at("slot_machine", "other", Some(1), now), Ok(()));
+    // The limited request didn't use up the key's quota
+    assert_eq!(limiter.check_at("slot_machine", "key", None, now), Ok(()));
+    assert_eq!(limiter.check_at("slot_machine", "key", None, now), Err(20));
+}
+
+#[test]
+fn test_rate_limiter_ignores_unknown_groups() {
+    let limiter = limiter(Quota::new(1, 60), Quota::new(1, 60));
+    let now = Instant::now();
+
+    for _ in 0..10 {
+        assert_eq!(limiter.check_at("healthz", "key", None, now), Ok(()));
+    }
+}
+
+#[test]
+fn test_route_user() {
+    assert_eq!(route_user(&["blackjack", "7"]), Some(7));
+    assert_eq!(route_user(&["blackjack", "7", "hit"]), Some(7));
+    assert_eq!(route_user(&["coin_toss", "7", "streak", "heads", "10"]), Some(7));
+    assert_eq!(route_user(&["slot_machine", "7", "grid", "9", "10"]), Some(7));
+
+    // Bets, coin counts and weapons aren't users
+    assert_eq!(route_user(&["slot_machine", "10"]), None);
+    assert_eq!(route_user(&["coin_toss", "heads", "10"]), None);
+    assert_eq!(route_user(&["coin_toss", "3", "heads", "2", "10"]), None);
+    assert_eq!(route_user(&["rps", "rock", "10"]), None);
+    assert_eq!(route_user(&["dice", "roll", "3d6"]), None);
+    assert_eq!(route_user(&["lottery", "draws", "3"]), None);
+}
@@ -0,0 +1,128 @@
+extern crate games_microservice;
+use games_microservice::games::roulette::{color_of, spin_bets, BetKind, Color, History,
+                                          RouletteBet, Rules, Table, Wheel, DOUBLE_ZERO};
+
+fn bet(bet: &str, amount: u64) -> RouletteBet {
+    RouletteBet {
+        bet: bet.to_string(),
+        amount,
+    }
+}
+
+#[test]
+fn test_payouts() {
+    let wheel = Wheel::European;
+    let pays = |bet: &str| BetKind::parse(bet, wheel).unwrap().pays();
+
+    assert_eq!(pays("straight 17"), 35);
+    assert_eq!(pays("split 17-20"), 17);
+    assert_eq!(pays("street 4"), 11);
+    assert_eq!(pays("corner 1"), 8);
+    assert_eq!(pays("six_line 31"), 5);
+    assert_eq!(pays("dozen 3"), 2);
+    assert_eq!(pays("column 1"), 2);
+    for even_money in &["red", "black", "odd", "even", "low", "high"] {
+        assert_eq!(pays(even_money), 1);
+    }
+}
+
+#[test]
+fn test_layout() {
+    let european = Wheel::European;
+    let american = Wheel::American;
+
+    assert_eq!(
+        BetKind::parse("corner 1", european).unwrap().pockets(),
+        vec![1, 2, 4, 5]
+    );
+    assert_eq!(
+        BetKind::parse("column 2", european).unwrap().pockets()[..3],
+        [2, 5, 8]
+    );
+    assert_eq!(
+        BetKind::parse("dozen 2", european).unwrap().pockets(),
+        (13..25).collect::<Vec<u8>>()
+    );
+
+    // 3 and 4 are on different rows, 3 has no corner to its right
+    assert!(BetKind::parse("split 3-4", european).is_err());
+    assert!(BetKind::parse("split 17-19", european).is_err());
+    assert!(BetKind::parse("corner 3", european).is_err());
+    assert!(BetKind::parse("street 5", european).is_err());
+    assert!(BetKind::parse("six_line 34", european).is_err());
+    assert!(BetKind::parse("dozen 4", european).is_err());
+    assert!(BetKind::parse("straight 37", european).is_err());
+    assert!(BetKind::parse("red 5", european).is_err());
+
+    // 00 only exists on the American wheel
+    assert!(BetKind::parse("straight 00", european).is_err());
+    assert_eq!(
+        BetKind::parse("straight 00", american).unwrap().pockets(),
+        vec![DOUBLE_ZERO]
+    );
+    assert!(BetKind::parse("split 0-00", american).is_ok());
+    assert!(BetKind::parse("split 0-3", american).is_err());
+    assert!(BetKind::parse("split 0-3", european).is_ok());
+}
+
+#[test]
+fn test_colors() {
+    assert_eq!(color_of(0), Color::Green);
+    assert_eq!(color_of(DOUBLE_ZERO), Color::Green);
+    assert_eq!(color_of(1), Color::Red);
+    assert_eq!(color_of(2), Color::Black);
+    assert_eq!(color_of(36), Color::Red);
+    assert_eq!(Wheel::European.pockets().len(), 37);
+    assert_eq!(Wheel::American.pockets().len(), 38);
+}
+
+#[test]
+fn test_settle_one_spin() {
+    let rules = Rules::default();
+    let table = rules.table("European").unwrap();
+    let bets = [
+        bet("straight 17", 10),
+        bet("split 17-20", 10),
+        bet("red", 10),
+        bet("odd", 10),
+    ];
+
+    let spin = spin_bets(table, &bets, 17, &rules).unwrap();
+    assert_eq!(spin.pocket, "17");
+    assert_eq!(spin.color, Color::Black);
+    assert_eq!(spin.bet, 40);
+    assert_eq!(spin.gain, 350 + 170 - 10 + 10);
+    assert!(!spin.bets[2].won);
+
+    // Zero loses every outside bet
+    let spin = spin_bets(table, &[bet("low", 10), bet("even", 10)], 0, &rules).unwrap();
+    assert_eq!(spin.gain, -20);
+
+    assert!(spin_bets(table, &[bet("straight 00", 10)], 0, &rules).is_err());
+    assert!(spin_bets(table, &[bet("red", 0)], 0, &rules).is_err());
+    let too_many = vec![bet("red", 1); rules.max_bets + 1];
+    assert!(spin_bets(table, &too_many, 0, &rules).is_err());
+
+    // Totals and payouts that don't fit are refused rather than wrapped
+    let huge = u64::max_value() / 2;
+    assert!(spin_bets(table, &[bet("red", huge), bet("black", huge + 3)], 0, &rules).is_err());
+    assert!(spin_bets(table, &[bet("straight 17", huge / 4)], 17, &rules).is_err());
+}
+
+#[test]
+fn test_hot_and_cold() {
+    let table = Table {
+        name: String::from("test"),
+        wheel: Wheel::American,
+    };
+    let history = History::new(&table, &[DOUBLE_ZERO, 17, 3, 17, 0, DOUBLE_ZERO, 17], 2);
+
+    assert_eq!(history.spins[0], "00");
+    assert_eq!(history.counts.len(), 38);
+    assert_eq!(history.hot[0].pocket, "17");
+    assert_eq!(history.hot[0].count, 3);
+    assert_eq!(history.hot[1].pocket, "00");
+    // Never spun, lowest first
+    assert_eq!(history.cold[0].pocket, "1");
+    assert_eq!(history.cold[1].pocket, "2");
+}
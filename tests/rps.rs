@@ -0,0 +1,205 @@
+extern crate games_microservice;
+use games_microservice::games::rps::{adaptive_multiplier, commitment, computer_throw, is_commitment,
+                                     AdaptiveRules, RpsMatch, Rules, Strategy, ThrowModel, Variant,
+                                     Weapons};
+
+#[test]
+fn test_match_lengths() {
+    assert!(RpsMatch::new(100, 3).is_ok());
+    assert!(RpsMatch::new(100, 7).is_ok());
+    assert!(RpsMatch::new(100, 4).is_err());
+    assert!(RpsMatch::new(100, 9).is_err());
+    assert_eq!(RpsMatch::new(100, 5).unwrap().majority(), 3);
+}
+
+#[test]
+fn test_match_settles_at_majority() {
+    let rules = Rules::default();
+    let mut rps_match = RpsMatch::new(100, 3).unwrap();
+
+    let round = rps_match.play(Weapons::Rock, Weapons::Scissors).unwrap();
+    assert_eq!(round.won, Some(true));
+    // Draws don't count
+    let round = rps_match.play(Weapons::Paper, Weapons::Paper).unwrap();
+    assert_eq!(round.won, None);
+    rps_match.play(Weapons::Paper, Weapons::Scissors).unwrap();
+    assert_eq!(rps_match.winner(), None);
+    assert_eq!(rps_match.payout(&rules), 0);
+
+    rps_match.play(Weapons::Scissors, Weapons::Paper).unwrap();
+    assert_eq!(rps_match.winner(), Some(true));
+    assert_eq!((rps_match.player_score, rps_match.computer_score), (2, 1));
+    assert_eq!(rps_match.rounds.len(), 4);
+    assert_eq!(rps_match.payout(&rules), 190);
+
+    assert!(rps_match.play(Weapons::Rock, Weapons::Rock).is_err());
+}
+
+#[test]
+fn test_match_lost() {
+    let mut rps_match = RpsMatch::new(100, 5).unwrap();
+
+    for _ in 0..3 {
+        rps_match.play(Weapons::Rock, Weapons::Paper).unwrap();
+    }
+    assert_eq!(rps_match.winner(), Some(false));
+    assert_eq!(rps_match.payout(&Rules::default()), 0);
+}
+
+#[test]
+fn test_default_variants_are_valid() {
+    let rules = Rules::default();
+
+    assert!(rules.validate().is_ok());
+    assert!(rules.variant("RPSLS").is_some());
+    assert!(rules.variant("rpsls-7").is_none());
+}
+
+#[test]
+fn test_rpsls() {
+    let rpsls = Variant::rpsls();
+
+    assert!(rpsls.beats("Spock", "Scissors"));
+    assert!(rpsls.beats("Lizard", "Spock"));
+    assert!(rpsls.beats("Rock", "Lizard"));
+    assert!(!rpsls.beats("Lizard", "Rock"));
+    assert_eq!(rpsls.outcome("Paper", "Paper"), None);
+    assert_eq!(rpsls.outcome("Paper", "Scissors"), Some(false));
+
+    // Every weapon beats exactly two others and loses to the other two
+    for weapon in &rpsls.weapons {
+        let losses = rpsls
+            .weapons
+            .iter()
+            .filter(|other| rpsls.beats(&other.name, &weapon.name))
+            .count();
+        assert_eq!((weapon.beats.len(), losses), (2, 2));
+    }
+}
+
+#[test]
+fn test_parse_variant_weapon() {
+    let rpsls = Variant::rpsls();
+
+    assert_eq!(rpsls.parse("spock").unwrap().name, "Spock");
+    assert_eq!(rpsls.parse("Sc").unwrap().name, "Scissors");
+    assert_eq!(rpsls.parse("l").unwrap().name, "Lizard");
+    // Scissors and Spock
+    assert!(rpsls.parse("s").is_err());
+    assert!(rpsls.parse("stone").is_err());
+}
+
+#[test]
+fn test_cycle_variant() {
+    let rps7 = Variant::cycle(
+        "rps7",
+        &["Rock", "Water", "Air", "Paper", "Sponge", "Scissors", "Fire"],
+    );
+
+    assert!(rps7.validate().is_ok());
+    assert!(rps7.beats("Rock", "Fire"));
+    assert!(rps7.beats("Rock", "Scissors"));
+    assert!(rps7.beats("Rock", "Sponge"));
+    assert!(rps7.beats("Paper", "Rock"));
+}
+
+#[test]
+fn test_commitment() {
+    let committed = commitment("Rock", "salt");
+
+    assert!(is_commitment(&committed));
+    // Weapons are committed to in lowercase
+    assert_eq!(committed, commitment("rock", "salt"));
+    assert_eq!(
+        committed,
+        "ec1e7a2967f31c5156f5b45ddcca46b66e4a5df06ed609645651de987c520a57"
+    );
+    assert_ne!(committed, commitment("rock", "pepper"));
+    assert!(!is_commitment("rock"));
+}
+
+#[test]
+fn test_parse_strategy() {
+    assert_eq!("uniform".parse::<Strategy>().unwrap(), Strategy::Uniform);
+    assert_eq!("Medium".parse::<Strategy>().unwrap(), Strategy::Frequency);
+    assert_eq!("hard".parse::<Strategy>().unwrap(), Strategy::Markov);
+    assert!("impossible".parse::<Strategy>().is_err());
+}
+
+#[test]
+fn test_throw_model() {
+    let mut model = ThrowModel::new(3);
+    assert_eq!(model.predict(Strategy::Frequency), None);
+    assert_eq!(model.predict(Strategy::Markov), None);
+
+    // Rock, Rock, Paper, Rock, Paper
+    for &weapon in &[0, 0, 1, 0, 1] {
+        model.record(weapon);
+    }
+
+    assert_eq!(model.weights(Strategy::Uniform), None);
+    assert_eq!(model.weights(Strategy::Frequency), Some(&[3, 2, 0][..]));
+    // Paper was always followed by Rock
+    assert_eq!(model.weights(Strategy::Markov), Some(&[1, 0, 0][..]));
+    assert_eq!(model.predict(Strategy::Markov), Some(0));
+    for _ in 0..20 {
+        assert_ne!(model.predict(Strategy::Frequency), Some(2));
+    }
+
+    model.record(2);
+    // Nothing followed Scissors yet
+    assert_eq!(model.weights(Strategy::Markov), Some(&[3, 2, 1][..]));
+    assert_eq!(model.counts, vec![3, 2, 1]);
+    // Rock followed by Paper
+    assert_eq!(model.transitions[1], 2);
+}
+
+#[test]
+fn test_computer_counters_prediction() {
+    let classic = Variant::classic();
+    let mut model = ThrowModel::new(3);
+    model.record(0);
+
+    let predictable = AdaptiveRules {
+        frequency_uniform_share: 0.0,
+        ..AdaptiveRules::default()
+    };
+    for _ in 0..20 {
+        // Paper beats Rock
+        assert_eq!(computer_throw(&classic, &model, Strategy::Frequency, &predictable), 1);
+    }
+
+    // Half of the throws are random, a rock only player doesn't always lose
+    let rules = AdaptiveRules {
+        frequency_uniform_share: 0.5,
+        ..AdaptiveRules::default()
+    };
+    let throws = (0..200)
+        .map(|_| computer_throw(&classic, &model, Strategy::Frequency, &rules))
+        .collect::<Vec<_>>();
+    assert!(throws.iter().any(|&throw| throw != 1));
+    assert!(throws.iter().filter(|&&throw| throw == 1).count() > 100);
+}
+
+#[test]
+fn test_adaptive_multiplier() {
+    let classic = Variant::classic();
+    let rpsls = Variant::rpsls();
+
+    // Nothing to exploit, a win pays as much as a loss costs
+    assert!((adaptive_multiplier(&classic, 1.0, 0.0) - 1.0).abs() < 1e-9);
+    // Countering Paper half the time: Rock wins 2/3 of the throws and loses 1/6
+    assert!((adaptive_multiplier(&classic, 0.5, 0.0) - 0.25).abs() < 1e-9);
+    assert!((adaptive_multiplier(&classic, 0.5, 0.02) - 0.22).abs() < 1e-9);
+    // Both counters of a weapon are beaten by the weapon it beats: 7/10 wins and 2/10 losses
+    assert!((adaptive_multiplier(&rpsls, 0.5, 0.0) - 2.0 / 7.0).abs() < 1e-9);
+    // Always countering can't pay anything
+    assert_eq!(adaptive_multiplier(&classic, 0.0, 0.0), 0.0);
+
+    let rules = Rules::default();
+    assert_eq!(rules.strategy_multiplier(Strategy::Uniform, &classic), rules.win_multiplier);
+    assert!(
+        rules.strategy_multiplier(Strategy::Markov, &classic)
+            < rules.strategy_multiplier(Strategy::Frequency, &classic)
+    );
+}
@@ -0,0 +1,260 @@
+extern crate games_microservice;
+use games_microservice::games::slot_machine::{Combination, GridConfig, GridSpin, LinePayout,
+                                               Payout, Reel, Scatter, SlotConfig, SlotMachine,
+                                               Stop};
+
+fn reel(stops: &[(&str, u32)]) -> Reel {
+    Reel {
+        stops: stops
+            .iter()
+            .map(|&(symbol, weight)| Stop {
+                symbol: symbol.to_string(),
+                weight,
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_combination_matches() {
+    let three_stars = Combination::Symbols(vec![String::from("⭐"); 3]);
+    let two_stars = Combination::Symbols(vec![
+        String::from("⭐"),
+        String::from("⭐"),
+        String::from("*"),
+    ]);
+
+    assert!(three_stars.matches(&["⭐", "⭐", "⭐"]));
+    assert!(!three_stars.matches(&["⭐", "🍒", "⭐"]));
+    assert!(two_stars.matches(&["⭐", "🍒", "⭐"]));
+    assert!(two_stars.matches(&["⭐", "⭐", "⭐"]));
+    assert!(!two_stars.matches(&["⭐", "🍒", "🍒"]));
+    assert!(Combination::OfAKind(2).matches(&["🍒", "⭐", "🍒"]));
+    assert!(!Combination::OfAKind(3).matches(&["🍒", "⭐", "🍒"]));
+}
+
+#[test]
+fn test_best_payout_wins() {
+    let config = SlotConfig::default();
+
+    assert_eq!(config.pays(&["⭐", "⭐", "⭐"]), 50.0);
+    assert_eq!(config.pays(&["⭐", "🍒", "⭐"]), 5.0);
+    assert_eq!(config.pays(&["🍒", "🍒", "🍒"]), 10.0);
+    assert_eq!(config.pays(&["🍒", "🍊", "🍒"]), 1.75);
+    assert_eq!(config.pays(&["🍒", "🍊", "⭐"]), 0.0);
+}
+
+#[test]
+fn test_rtp() {
+    // Two equally likely symbols on two reels, a pair pays 2x: half the spins pay 2x
+    let config = SlotConfig {
+        reels: vec![reel(&[("A", 1), ("B", 1)]), reel(&[("A", 1), ("B", 1)])],
+        paytable: vec![
+            Payout {
+                combination: Combination::OfAKind(2),
+                pays: 2.0,
+            },
+        ],
+        ..SlotConfig::default()
+    };
+    assert!((config.rtp() - 1.0).abs() < 1e-9);
+
+    // A weighted reel, only A A pays: 3/4 * 1/2 * 4 = 1.5
+    let config = SlotConfig {
+        reels: vec![reel(&[("A", 3), ("B", 1)]), reel(&[("A", 1), ("B", 1)])],
+        paytable: vec![
+            Payout {
+                combination: Combination::Symbols(vec![String::from("A"), String::from("A")]),
+                pays: 4.0,
+            },
+        ],
+        ..SlotConfig::default()
+    };
+    assert!((config.rtp() - 1.5).abs() < 1e-9);
+
+    assert!((SlotConfig::default().rtp() - 0.944_116_516_272).abs() < 1e-9);
+}
+
+#[test]
+fn test_validate() {
+    assert!(SlotConfig::default().validate().is_ok());
+
+    let config = SlotConfig {
+        reels: vec![reel(&[("A", 0)])],
+        paytable: vec![
+            Payout {
+                combination: Combination::OfAKind(2),
+                pays: -1.0,
+            },
+        ],
+        ..SlotConfig::default()
+    };
+    assert_eq!(config.validate().unwrap_err().len(), 3);
+}
+
+#[test]
+fn test_spin() {
+    let config = SlotConfig::default();
+
+    for _ in 0..100 {
+        let spin = SlotMachine::with_config(100, &config);
+        assert_eq!(spin.picks.len(), 3);
+        assert!([-100, 75, 400, 900, 4900].contains(&spin.gain));
+    }
+}
+
+#[test]
+fn test_line_pays() {
+    let config = GridConfig::default();
+
+    assert_eq!(
+        config.line_pays(&["⭐", "⭐", "⭐", "⭐", "⭐"]),
+        Some((String::from("⭐"), 5, 500.0))
+    );
+    assert_eq!(
+        config.line_pays(&["🍒", "🍒", "🍒", "🍒", "⭐"]),
+        Some((String::from("🍒"), 4, 30.0))
+    );
+    // Matches have to start on the leftmost reel
+    assert_eq!(config.line_pays(&["⭐", "🍒", "🍒", "🍒", "🍒"]), None);
+    assert_eq!(config.line_pays(&["🍒", "🍒", "⭐", "🍒", "🍒"]), None);
+}
+
+#[test]
+fn test_wilds() {
+    let config = GridConfig::default();
+
+    // Wilds stand in for the first other symbol
+    assert_eq!(
+        config.line_pays(&["🃏", "🍒", "🃏", "🍒", "⭐"]),
+        Some((String::from("🍒"), 4, 30.0))
+    );
+    assert_eq!(
+        config.line_pays(&["🍒", "🃏", "🃏", "🃏", "🃏"]),
+        Some((String::from("🍒"), 5, 150.0))
+    );
+    // Three wilds pay more on their own than four cherries
+    assert_eq!(
+        config.line_pays(&["🃏", "🃏", "🃏", "🍒", "⭐"]),
+        Some((String::from("🃏"), 3, 50.0))
+    );
+    // Wilds don't stand in for the scatter
+    assert_eq!(config.line_pays(&["🃏", "💎", "💎", "💎", "💎"]), None);
+    assert_eq!(config.line_pays(&["💎", "💎", "💎", "💎", "💎"]), None);
+}
+
+#[test]
+fn test_scatters() {
+    let config = GridConfig::default();
+
+    assert_eq!(config.scatter_pays(2), 0.0);
+    assert_eq!(config.scatter_pays(3), 2.0);
+    assert_eq!(config.scatter_pays(5), 50.0);
+    assert_eq!(config.free_spins_won(2), 0);
+    assert_eq!(config.free_spins_won(4), 10);
+
+    // One scatter per 22 stop reel, seen 3 times out of 22 on each of the 5 reels
+    let chances = config.scatter_chances();
+    let seen: f64 = 3.0 / 22.0;
+    assert_eq!(chances.len(), 16);
+    assert!((chances[0] - (1.0 - seen).powi(5)).abs() < 1e-12);
+    assert!((chances[5] - seen.powi(5)).abs() < 1e-12);
+    assert!(chances[6..].iter().all(|chance| *chance == 0.0));
+}
+
+#[test]
+fn test_grid_rtp() {
+    // Two equally likely symbols, A pays 1x on the first reel and 4x on both
+    let config = GridConfig {
+        rows: 1,
+        reels: vec![reel(&[("A", 1), ("B", 1)]), reel(&[("A", 1), ("B", 1)])],
+        paylines: vec![vec![0, 0]],
+        paytable: vec![
+            LinePayout {
+                symbol: String::from("A"),
+                count: 1,
+                pays: 1.0,
+            },
+            LinePayout {
+                symbol: String::from("A"),
+                count: 2,
+                pays: 4.0,
+            },
+        ],
+        wild: None,
+        scatter: None,
+    };
+    assert!((config.rtp(1) - 1.25).abs() < 1e-9);
+
+    // B is wild: A A, A B, B A pay 4x and B B pays nothing
+    let config = GridConfig {
+        wild: Some(String::from("B")),
+        ..config
+    };
+    assert!((config.rtp(1) - 3.0).abs() < 1e-9);
+
+    // Every row of the default reels has the same odds
+    let config = GridConfig::default();
+    for line in 0..config.paylines.len() {
+        assert!((config.line_rtp(line) - 0.581_401_621_225_6).abs() < 1e-9);
+    }
+    assert!((config.scatter_rtp() - 0.055_114_528_938).abs() < 1e-9);
+    assert!((config.trigger_chance() - 0.020_453_148_381_6).abs() < 1e-9);
+    assert!((config.rtp(9) - 0.963_839_205_738_7).abs() < 1e-9);
+}
+
+#[test]
+fn test_grid_validate() {
+    assert!(GridConfig::default().validate().is_ok());
+
+    let config = GridConfig {
+        rows: 2,
+        paylines: vec![vec![0, 1, 2, 1, 0], vec![0, 0]],
+        ..GridConfig::default()
+    };
+    assert_eq!(config.validate().unwrap_err().len(), 2);
+
+    // A trigger every spin would never run out of free spins
+    let config = GridConfig {
+        scatter: Some(Scatter {
+            trigger: 1,
+            free_spins: 10,
+            ..Scatter::default()
+        }),
+        ..GridConfig::default()
+    };
+    assert_eq!(config.validate().unwrap_err().len(), 1);
+}
+
+#[test]
+fn test_grid_spin() {
+    let config = GridConfig::default();
+
+    assert!(GridSpin::spin(10, 0, &config).is_err());
+    assert!(GridSpin::spin(10, 10, &config).is_err());
+
+    for _ in 0..100 {
+        let spin = GridSpin::spin(10, 9, &config).unwrap();
+        assert_eq!(spin.bet, 90);
+        assert_eq!(spin.grid.len(), 3);
+        assert!(spin.grid.iter().all(|row| row.len() == 5));
+        assert!(spin.wins.iter().all(|win| win.line < 9 && win.count >= 3));
+        assert_eq!(spin.free_spins_won, config.free_spins_won(spin.scatters));
+
+        let paid: u64 = spin.wins.iter().map(|win| win.payout).sum();
+        assert_eq!(spin.gain, (paid + spin.scatter_payout) as i64 - 90);
+    }
+}
+
+#[test]
+fn test_free_spin() {
+    let config = GridConfig::default();
+
+    for _ in 0..100 {
+        let spin = GridSpin::free_spin(10, 9, 2.0, &config).unwrap();
+        assert_eq!(spin.bet, 0);
+        assert!(spin.free_spin);
+        assert!(spin.gain >= 0);
+        assert!(spin.wins.iter().all(|win| win.payout % 20 == 0));
+    }
+}
@@ -0,0 +1,100 @@
+extern crate games_microservice;
+use games_microservice::games::{StandardCard, StandardDeck};
+use games_microservice::games::video_poker::{draw, parse_held, Paytable, VideoPokerHand};
+
+fn hand(cards: &[&str]) -> Vec<StandardCard> {
+    cards.iter().map(|card| card.parse().unwrap()).collect()
+}
+
+#[test]
+fn test_parse_held() {
+    assert_eq!(parse_held("11001").unwrap(), vec![true, true, false, false, true]);
+    assert!(parse_held("1100").is_err());
+    assert!(parse_held("110011").is_err());
+    assert!(parse_held("11x01").is_err());
+}
+
+#[test]
+fn test_draw_replaces_unheld_cards() {
+    let mut cards = hand(&[
+        "CLUBS:KING",
+        "SPADES:KING",
+        "HEARTS:FIVE",
+        "DIAMONDS:EIGHT",
+        "HEARTS:TWO",
+    ]);
+    // The deck draws from the back
+    let mut deck = StandardDeck {
+        cards: hand(&["HEARTS:KING", "DIAMONDS:KING"]),
+    };
+
+    draw(&mut cards, &[true, true, false, true, false], &mut deck).unwrap();
+    assert_eq!(cards[2].to_string(), "DIAMONDS:KING");
+    assert_eq!(cards[4].to_string(), "HEARTS:KING");
+    assert!(deck.cards.is_empty());
+
+    assert!(draw(&mut cards, &[true, false, true, true, true], &mut deck).is_err());
+    assert!(draw(&mut cards, &[true], &mut deck).is_err());
+}
+
+#[test]
+fn test_jacks_or_better() {
+    let paytable = Paytable::default();
+
+    let jacks = hand(&[
+        "CLUBS:JACK",
+        "SPADES:JACK",
+        "HEARTS:FIVE",
+        "DIAMONDS:EIGHT",
+        "HEARTS:TWO",
+    ]);
+    let result = VideoPokerHand::new(&jacks, Some(vec![true; 5]), 10, &paytable).unwrap();
+    assert_eq!(result.paying_hand, Some("Jacks or Better".to_string()));
+    assert_eq!(result.gain, 10);
+
+    let tens = hand(&[
+        "CLUBS:TEN",
+        "SPADES:TEN",
+        "HEARTS:FIVE",
+        "DIAMONDS:EIGHT",
+        "HEARTS:TWO",
+    ]);
+    let result = VideoPokerHand::new(&tens, Some(vec![true; 5]), 10, &paytable).unwrap();
+    assert_eq!(result.paying_hand, None);
+    assert_eq!(result.gain, 0);
+}
+
+#[test]
+fn test_paytable() {
+    let paytable = Paytable::default();
+
+    let royal = hand(&[
+        "SPADES:ACE",
+        "SPADES:KING",
+        "SPADES:QUEEN",
+        "SPADES:JACK",
+        "SPADES:TEN",
+    ]);
+    // Before the draw the bet is taken, whatever the hand
+    let dealt = VideoPokerHand::new(&royal, None, 5, &paytable).unwrap();
+    assert_eq!(dealt.paying_hand, Some("Royal Flush".to_string()));
+    assert_eq!(dealt.gain, -5);
+
+    let drawn = VideoPokerHand::new(&royal, Some(vec![true; 5]), 5, &paytable).unwrap();
+    assert_eq!(drawn.gain, 4000);
+
+    let full_house = hand(&[
+        "CLUBS:KING",
+        "SPADES:KING",
+        "HEARTS:KING",
+        "DIAMONDS:FIVE",
+        "HEARTS:FIVE",
+    ]);
+    let drawn = VideoPokerHand::new(&full_house, Some(vec![true; 5]), 5, &paytable).unwrap();
+    assert_eq!(drawn.gain, 45);
+
+    let entries = paytable.entries();
+    assert_eq!(entries.len(), 9);
+    assert_eq!(entries[0].hand, "Royal Flush");
+    assert_eq!(entries[8].pays, 1);
+}
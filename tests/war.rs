@@ -0,0 +1,89 @@
+extern crate games_microservice;
+use games_microservice::games::war::{deal, go_to_war, surrender, Rules, WarOutcome};
+use games_microservice::games::{StandardCard, StandardDeck};
+
+fn card(card: &str) -> StandardCard {
+    card.parse().unwrap()
+}
+
+/// Deck dealing `cards` in order
+fn stacked(cards: &[&str]) -> StandardDeck {
+    StandardDeck {
+        cards: cards.iter().rev().map(|name| card(name)).collect(),
+    }
+}
+
+#[test]
+fn test_deal() {
+    let round = deal(&mut stacked(&["SPADES:ACE", "HEARTS:KING"]), 10).unwrap();
+    assert_eq!(round.outcome, WarOutcome::Win);
+    assert_eq!(round.player, "SPADES:ACE");
+    assert_eq!(round.gain, 10);
+
+    let round = deal(&mut stacked(&["SPADES:TWO", "HEARTS:THREE"]), 10).unwrap();
+    assert_eq!(round.outcome, WarOutcome::Lose);
+    assert_eq!(round.gain, -10);
+
+    // Suits don't break ties
+    let round = deal(&mut stacked(&["SPADES:QUEEN", "HEARTS:QUEEN"]), 10).unwrap();
+    assert_eq!(round.outcome, WarOutcome::Tie);
+    assert_eq!(round.gain, -10);
+
+    assert!(deal(&mut stacked(&["SPADES:QUEEN"]), 10).is_err());
+}
+
+#[test]
+fn test_war() {
+    let rules = Rules::default();
+    let (player, dealer) = (card("SPADES:NINE"), card("HEARTS:NINE"));
+    let burned = ["CLUBS:TWO", "CLUBS:THREE", "CLUBS:FOUR"];
+    let war = |player_war: &str, dealer_war: &str| {
+        let mut cards = burned.to_vec();
+        cards.push(player_war);
+        cards.push(dealer_war);
+        go_to_war(&player, &dealer, 10, &mut stacked(&cards), &rules).unwrap()
+    };
+
+    // The raise pays 1 to 1, the bet is returned
+    let round = war("SPADES:KING", "HEARTS:JACK");
+    assert_eq!(round.outcome, WarOutcome::WonWar);
+    assert_eq!(round.raise, 10);
+    assert_eq!(round.gain, 20);
+    assert_eq!(round.war.unwrap().burned.len(), 3);
+
+    let round = war("SPADES:FIVE", "HEARTS:FIVE");
+    assert_eq!(round.outcome, WarOutcome::TiedWar);
+    assert_eq!(round.gain, 30);
+
+    let round = war("SPADES:FIVE", "HEARTS:ACE");
+    assert_eq!(round.outcome, WarOutcome::LostWar);
+    assert_eq!(round.gain, -10);
+
+    let rules = Rules {
+        burn_cards: 0,
+        ..rules
+    };
+    let mut deck = stacked(&["SPADES:FIVE", "HEARTS:TWO"]);
+    let round = go_to_war(&player, &dealer, 10, &mut deck, &rules).unwrap();
+    assert!(round.war.unwrap().burned.is_empty());
+}
+
+#[test]
+fn test_surrender() {
+    let (player, dealer) = (card("SPADES:NINE"), card("HEARTS:NINE"));
+    let round = surrender(&player, &dealer, 11);
+    assert_eq!(round.outcome, WarOutcome::Surrender);
+    assert_eq!(round.gain, 5);
+}
+
+#[test]
+fn test_rules() {
+    assert!(Rules::default().validate().is_ok());
+    assert!(
+        Rules {
+            decks: 0,
+            ..Rules::default()
+        }.validate()
+            .is_err()
+    );
+}
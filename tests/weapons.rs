@@ -2,14 +2,23 @@ extern crate games_microservice;
 use games_microservice::games::rps::Weapons;
 
 #[test]
-fn test_comparision() {
-    assert_eq!(Weapons::Rock, Weapons::Rock);
-    assert!(Weapons::Rock > Weapons::Scissors);
-    assert!(Weapons::Rock < Weapons::Paper);
-    assert_eq!(Weapons::Paper, Weapons::Paper);
-    assert!(Weapons::Paper > Weapons::Rock);
-    assert!(Weapons::Paper < Weapons::Scissors);
-    assert_eq!(Weapons::Scissors, Weapons::Scissors);
-    assert!(Weapons::Scissors > Weapons::Paper);
-    assert!(Weapons::Scissors < Weapons::Rock);
+fn test_beats() {
+    assert!(!Weapons::Rock.beats(&Weapons::Rock));
+    assert!(Weapons::Rock.beats(&Weapons::Scissors));
+    assert!(!Weapons::Rock.beats(&Weapons::Paper));
+    assert!(!Weapons::Paper.beats(&Weapons::Paper));
+    assert!(Weapons::Paper.beats(&Weapons::Rock));
+    assert!(!Weapons::Paper.beats(&Weapons::Scissors));
+    assert!(!Weapons::Scissors.beats(&Weapons::Scissors));
+    assert!(Weapons::Scissors.beats(&Weapons::Paper));
+    assert!(!Weapons::Scissors.beats(&Weapons::Rock));
+}
+
+#[test]
+fn test_parse_weapon() {
+    assert_eq!("r".parse::<Weapons>().unwrap(), Weapons::Rock);
+    assert_eq!("Paper".parse::<Weapons>().unwrap(), Weapons::Paper);
+    assert_eq!("SCISSORS".parse::<Weapons>().unwrap(), Weapons::Scissors);
+    assert!("spock".parse::<Weapons>().is_err());
+    assert!("".parse::<Weapons>().is_err());
 }